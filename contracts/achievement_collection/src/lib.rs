@@ -1,6 +1,12 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, symbol_short, token, Address, BytesN,
+    Env, String, Vec,
+};
+
+const PLAYER_TTL_THRESHOLD: u32 = 100_000;
+const PLAYER_TTL_EXTEND: u32 = 500_000;
 
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -19,7 +25,26 @@ pub struct Set {
     pub achievements: Vec<u32>,
     pub rarity: Rarity,
     pub limited_cap: Option<u32>, // Total max claims per achievement across players
-    pub bonus_points: i128,       // Internal bonus points rewarded on completion
+    pub bonus_points: i128,       // Rewarded on completion: reward tokens within the set's payout cap, else internal points
+}
+
+/// Mirror of `achievement_nft::Achievement`.
+#[contracttype]
+#[derive(Clone)]
+pub struct Achievement {
+    pub owner: Address,
+    pub puzzle_id: u32,
+    pub metadata: String,
+    pub timestamp: u64,
+}
+
+// Ownership views of the achievement NFT contract; achievement ids are its puzzle ids.
+#[contractclient(name = "AchievementNftClient")]
+pub trait AchievementNftInterface {
+    fn puzzle_ids_of(env: Env, owner: Address) -> Vec<u32>;
+    fn has_puzzle(env: Env, owner: Address, puzzle_id: u32) -> bool;
+    fn get_collection(env: Env, owner: Address) -> Vec<u32>;
+    fn get_achievement(env: Env, token_id: u32) -> Option<Achievement>;
 }
 
 #[contracttype]
@@ -27,10 +52,21 @@ pub enum DataKey {
     NextSetId,
     Set(u32),                // Set
     AchToSet(u32),           // u32 set_id
-    PlayerProgress(Address, u32), // Vec<u32> collected achievement IDs in set
+    PlayerProgress(Address, u32), // Persistent: Vec<u32> collected achievement IDs in set
     AchCount(u32),           // u32 global claim count for limited edition
-    Bonus(Address),          // i128 internal bonus ledger per player
-    Completed(Address, u32), // bool marker to avoid double bonus
+    CapToken(u32),           // Persistent: bool, achievement token already counted toward its cap
+    Bonus(Address),          // Persistent: i128 internal bonus ledger per player
+    Completed(Address, u32), // Persistent: bool marker to avoid double bonus
+    Admin,                   // Address
+    RewardToken,             // Address of the token completion rewards are paid in
+    RewardPool,              // i128 funded balance not yet paid out
+    AchievementNft,          // Address progress is derived from
+    Snapshot(u32),           // bool: completing the set consumes the qualifying tokens
+    TokenClaimed(u32, u32),  // Persistent: (set_id, token_id) -> player that completed with it
+    CompletionTokens(Address, u32), // Persistent: Vec<u32> token ids a completion consumed
+    PayoutCap(u32),          // i128 max reward tokens the set may pay out in total
+    PaidOut(u32),            // i128 reward tokens the set has paid out or owes so far
+    Owed(Address),           // Persistent: i128 reward tokens earned while the pool was short
 }
 
 #[contract]
@@ -38,14 +74,41 @@ pub struct AchievementCollection;
 
 #[contractimpl]
 impl AchievementCollection {
-    pub fn initialize(env: Env) {
-        if env.storage().instance().has(&DataKey::NextSetId) {
+    /// Set the admin of a fresh deployment. Deployments that predate the admin
+    /// go through `migrate_admin` instead.
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin)
+            || env.storage().instance().has(&DataKey::NextSetId)
+        {
             panic!("initialized");
         }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::NextSetId, &1u32);
     }
 
-    // Set management
+    /// Set the admin on a deployment that predates it, keeping existing sets.
+    /// `deployer` and `salt` must derive this contract's address, and the
+    /// deployer must authorize.
+    pub fn migrate_admin(env: Env, deployer: Address, salt: BytesN<32>, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("initialized");
+        }
+        if !env.storage().instance().has(&DataKey::NextSetId) {
+            panic!("not a legacy deployment");
+        }
+        let derived = env
+            .deployer()
+            .with_address(deployer.clone(), salt)
+            .deployed_address();
+        if derived != env.current_contract_address() {
+            panic!("not the deployer");
+        }
+        deployer.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    // Set management (admin only)
     pub fn create_set(
         env: Env,
         name: String,
@@ -54,7 +117,8 @@ impl AchievementCollection {
         limited_cap: Option<u32>,
         bonus_points: i128,
     ) -> u32 {
-        if achievements.len() == 0 {
+        Self::require_admin(&env);
+        if achievements.is_empty() {
             panic!("empty set");
         }
         let id: u32 = env
@@ -74,8 +138,7 @@ impl AchievementCollection {
         env.storage().instance().set(&DataKey::Set(id), &set);
         env.storage().instance().set(&DataKey::NextSetId, &(id + 1));
 
-        for a in achievements.iter() {
-            let aid = a.clone();
+        for aid in achievements.iter() {
             if env.storage().instance().has(&DataKey::AchToSet(aid)) {
                 panic!("achievement already mapped");
             }
@@ -88,14 +151,15 @@ impl AchievementCollection {
         env.storage().instance().get(&DataKey::Set(set_id))
     }
 
-    // Map additional achievement to an existing set
+    // Map additional achievement to an existing set (admin only)
     pub fn add_achievement_to_set(env: Env, set_id: u32, achievement_id: u32) {
+        Self::require_admin(&env);
         let mut set: Set = env
             .storage()
             .instance()
             .get(&DataKey::Set(set_id))
             .expect("set");
-        if set.achievements.contains(&achievement_id) {
+        if set.achievements.contains(achievement_id) {
             return;
         }
         if env.storage().instance().has(&DataKey::AchToSet(achievement_id)) {
@@ -123,20 +187,28 @@ impl AchievementCollection {
             .get(&DataKey::Set(set_id))
             .expect("set");
 
+        let nft = Self::get_achievement_nft(env.clone());
+        if let Some(nft) = &nft {
+            if !AchievementNftClient::new(&env, nft).has_puzzle(&player, &achievement_id) {
+                panic!("not owned");
+            }
+        }
+
+        // Achievements recorded so far; counts toward limited caps without an NFT
+        let mut progress: Vec<u32> =
+            Self::read_player(&env, &DataKey::PlayerProgress(player.clone(), set_id))
+                .unwrap_or(Vec::new(&env));
+
         // limited edition check per achievement (only when acquiring new)
-        // load current progress to determine ownership first
-        let progress_for_cap: Vec<u32> = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlayerProgress(player.clone(), set_id))
-            .unwrap_or(Vec::new(&env.clone()));
-        if let Some(cap) = set.limited_cap {
+        if let (Some(cap), Some(nft)) = (set.limited_cap, &nft) {
+            Self::count_capped_token(&env, nft, &player, achievement_id, cap);
+        } else if let Some(cap) = set.limited_cap {
             let mut cnt: u32 = env
                 .storage()
                 .instance()
                 .get(&DataKey::AchCount(achievement_id))
                 .unwrap_or(0);
-            if !progress_for_cap.contains(&achievement_id) {
+            if !progress.contains(achievement_id) {
                 if cnt >= cap {
                     panic!("limited cap reached");
                 }
@@ -147,50 +219,52 @@ impl AchievementCollection {
             }
         }
 
-        let mut progress: Vec<u32> = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlayerProgress(player.clone(), set_id))
-            .unwrap_or(Vec::new(&env.clone()));
-        if !progress.contains(&achievement_id) {
+        if !progress.contains(achievement_id) {
             progress.push_back(achievement_id);
-            env.storage()
-                .instance()
-                .set(&DataKey::PlayerProgress(player.clone(), set_id), &progress);
         }
+        Self::write_player(&env, &DataKey::PlayerProgress(player.clone(), set_id), &progress);
 
         // completion detection
-        let completed = Self::is_completed_internal(&set, &progress);
+        let (held, tokens) = match &nft {
+            Some(nft) => Self::nft_progress(&env, nft, &player, &set),
+            None => (progress, Vec::new(&env)),
+        };
+        let completed = Self::is_completed_internal(&set, &held);
         if completed {
             // award bonus only once per player per set
-            let already: bool = env
-                .storage()
-                .instance()
-                .get(&DataKey::Completed(player.clone(), set_id))
-                .unwrap_or(false);
+            let completed_key = DataKey::Completed(player.clone(), set_id);
+            let already: bool = Self::read_player(&env, &completed_key).unwrap_or(false);
             if !already {
-                let mut bonus: i128 = env
-                    .storage()
-                    .instance()
-                    .get(&DataKey::Bonus(player.clone()))
-                    .unwrap_or(0);
-                bonus += set.bonus_points;
-                env.storage()
-                    .instance()
-                    .set(&DataKey::Bonus(player.clone()), &bonus);
-                env.storage()
-                    .instance()
-                    .set(&DataKey::Completed(player.clone(), set_id), &true);
+                if Self::snapshot_enabled(env.clone(), set_id) {
+                    for token_id in tokens.iter() {
+                        env.storage()
+                            .persistent()
+                            .set(&DataKey::TokenClaimed(set_id, token_id), &player);
+                    }
+                    Self::write_player(&env, &DataKey::CompletionTokens(player.clone(), set_id), &tokens);
+                }
+                Self::pay_completion(&env, &player, set_id, set.bonus_points);
+                Self::write_player(&env, &completed_key, &true);
             }
         }
         completed
     }
 
+    /// Achievements of the set the player holds. With an achievement NFT linked
+    /// this follows live token ownership; otherwise it is the recorded list.
     pub fn progress(env: Env, player: Address, set_id: u32) -> Vec<u32> {
-        env.storage()
-            .instance()
-            .get(&DataKey::PlayerProgress(player, set_id))
-            .unwrap_or(Vec::new(&env))
+        match Self::get_achievement_nft(env.clone()) {
+            Some(nft) => {
+                let set: Set = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::Set(set_id))
+                    .expect("set");
+                Self::nft_progress(&env, &nft, &player, &set).0
+            }
+            None => Self::read_player(&env, &DataKey::PlayerProgress(player, set_id))
+                .unwrap_or(Vec::new(&env)),
+        }
     }
 
     pub fn is_completed(env: Env, player: Address, set_id: u32) -> bool {
@@ -209,69 +283,377 @@ impl AchievementCollection {
         }
         // every achievement in set must be present
         for a in set.achievements.iter() {
-            if !progress.contains(&a) {
+            if !progress.contains(a) {
                 return false;
             }
         }
         true
     }
 
-    // Trading/swapping progress marks between players within a set
+    // Trading/swapping progress marks between players within a set.
+    // With an achievement NFT linked, progress moves with the token instead.
     pub fn transfer_progress(env: Env, from: Address, to: Address, achievement_id: u32) {
         from.require_auth();
+        if Self::get_achievement_nft(env.clone()).is_some() {
+            panic!("progress follows achievement nft");
+        }
         let set_id: u32 = env
             .storage()
             .instance()
             .get(&DataKey::AchToSet(achievement_id))
             .expect("mapped");
 
-        let mut from_progress: Vec<u32> = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlayerProgress(from.clone(), set_id))
-            .unwrap_or(Vec::new(&env.clone()));
-        if !from_progress.contains(&achievement_id) {
+        let from_progress = Self::progress(env.clone(), from.clone(), set_id);
+        if !from_progress.contains(achievement_id) {
             panic!("not owned");
         }
         // remove from 'from'
-        let mut new_from = Vec::new(&env.clone());
-        for a in from_progress.iter() {
-            let v = a.clone();
+        let mut new_from = Vec::new(&env);
+        for v in from_progress.iter() {
             if v != achievement_id {
                 new_from.push_back(v);
             }
         }
-        env.storage()
-            .instance()
-            .set(&DataKey::PlayerProgress(from, set_id), &new_from);
+        Self::write_player(&env, &DataKey::PlayerProgress(from, set_id), &new_from);
 
         // add to 'to'
-        let mut to_progress: Vec<u32> = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlayerProgress(to.clone(), set_id))
-            .unwrap_or(Vec::new(&env.clone()));
-        if !to_progress.contains(&achievement_id) {
+        let mut to_progress = Self::progress(env.clone(), to.clone(), set_id);
+        if !to_progress.contains(achievement_id) {
             to_progress.push_back(achievement_id);
-            env.storage()
-                .instance()
-                .set(&DataKey::PlayerProgress(to.clone(), set_id), &to_progress);
         }
+        Self::write_player(&env, &DataKey::PlayerProgress(to, set_id), &to_progress);
     }
 
     // Bonus querying and withdrawal accounting (internal)
     pub fn bonus_of(env: Env, player: Address) -> i128 {
+        Self::read_player(&env, &DataKey::Bonus(player)).unwrap_or(0)
+    }
+
+    // Achievement NFT
+
+    /// Derive set progress from `achievement_nft` ownership (admin only).
+    pub fn set_achievement_nft(env: Env, nft: Option<Address>) {
+        Self::require_admin(&env);
+        match nft {
+            Some(nft) => env.storage().instance().set(&DataKey::AchievementNft, &nft),
+            None => env.storage().instance().remove(&DataKey::AchievementNft),
+        }
+    }
+
+    pub fn get_achievement_nft(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::AchievementNft)
+    }
+
+    /// When enabled, completing the set records the qualifying token ids and
+    /// those tokens no longer count toward the set for any other player (admin only).
+    pub fn set_completion_snapshot(env: Env, set_id: u32, enabled: bool) {
+        Self::require_admin(&env);
+        if !env.storage().instance().has(&DataKey::Set(set_id)) {
+            panic!("set");
+        }
+        env.storage().instance().set(&DataKey::Snapshot(set_id), &enabled);
+    }
+
+    pub fn snapshot_enabled(env: Env, set_id: u32) -> bool {
         env.storage()
             .instance()
-            .get(&DataKey::Bonus(player))
+            .get(&DataKey::Snapshot(set_id))
+            .unwrap_or(false)
+    }
+
+    /// Token ids consumed by the player's completion of a snapshot set.
+    pub fn completion_tokens(env: Env, player: Address, set_id: u32) -> Vec<u32> {
+        Self::read_player(&env, &DataKey::CompletionTokens(player, set_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // Reward pool
+
+    /// Pay set completion rewards in `token` from the funded pool instead of
+    /// bonus points (admin only).
+    pub fn set_reward_token(env: Env, token: Address) {
+        Self::require_admin(&env);
+        if env.storage().instance().has(&DataKey::RewardToken) {
+            panic!("reward token already set");
+        }
+        env.storage().instance().set(&DataKey::RewardToken, &token);
+    }
+
+    pub fn get_reward_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::RewardToken)
+    }
+
+    /// Deposit reward tokens into the completion pool.
+    pub fn fund_reward_pool(env: Env, funder: Address, amount: i128) {
+        funder.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let token_addr = Self::get_reward_token(env.clone()).expect("reward token");
+        token::Client::new(&env, &token_addr).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let pool = Self::reward_pool(env.clone());
+        env.storage().instance().set(&DataKey::RewardPool, &(pool + amount));
+    }
+
+    pub fn reward_pool(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::RewardPool).unwrap_or(0)
+    }
+
+    /// Most reward tokens completions of `set_id` may take from the pool in
+    /// total (admin only). Completions of sets without a cap or past their cap
+    /// earn bonus points instead; completions while the pool is short are owed
+    /// and paid out by `claim_owed` once it is funded.
+    pub fn set_payout_cap(env: Env, set_id: u32, cap: i128) {
+        Self::require_admin(&env);
+        if !env.storage().instance().has(&DataKey::Set(set_id)) {
+            panic!("set");
+        }
+        if cap < 0 {
+            panic!("invalid amount");
+        }
+        env.storage().instance().set(&DataKey::PayoutCap(set_id), &cap);
+    }
+
+    pub fn payout_cap(env: Env, set_id: u32) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PayoutCap(set_id))
             .unwrap_or(0)
     }
+
+    pub fn paid_out(env: Env, set_id: u32) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PaidOut(set_id))
+            .unwrap_or(0)
+    }
+
+    /// Reward tokens earned by completions the pool could not cover yet.
+    pub fn owed_of(env: Env, player: Address) -> i128 {
+        Self::read_player(&env, &DataKey::Owed(player)).unwrap_or(0)
+    }
+
+    /// Pay out as much of the player's owed rewards as the pool now holds.
+    /// Returns the amount transferred.
+    pub fn claim_owed(env: Env, player: Address) -> i128 {
+        player.require_auth();
+        let key = DataKey::Owed(player.clone());
+        let owed: i128 = Self::read_player(&env, &key).unwrap_or(0);
+        let pool = Self::reward_pool(env.clone());
+        let amount = owed.min(pool);
+        if amount <= 0 {
+            panic!("nothing to claim");
+        }
+        Self::write_player(&env, &key, &(owed - amount));
+        env.storage().instance().set(&DataKey::RewardPool, &(pool - amount));
+        let token_addr = Self::get_reward_token(env.clone()).expect("reward token");
+        token::Client::new(&env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &player,
+            &amount,
+        );
+        env.events()
+            .publish((symbol_short!("owed_paid"), player), amount);
+        amount
+    }
+
+    // Migration
+
+    /// Move a player's progress, completion markers and bonus from instance
+    /// storage, where earlier versions kept them, into persistent storage.
+    /// Safe to call for any player and more than once.
+    pub fn migrate_player(env: Env, player: Address) {
+        let next_set: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextSetId)
+            .unwrap_or(1);
+        for set_id in 1..next_set {
+            Self::migrate_key::<Vec<u32>>(&env, DataKey::PlayerProgress(player.clone(), set_id));
+            Self::migrate_key::<bool>(&env, DataKey::Completed(player.clone(), set_id));
+        }
+        Self::migrate_key::<i128>(&env, DataKey::Bonus(player));
+    }
+
+    fn migrate_key<V>(env: &Env, key: DataKey)
+    where
+        V: soroban_sdk::IntoVal<Env, soroban_sdk::Val> + soroban_sdk::TryFromVal<Env, soroban_sdk::Val>,
+    {
+        if let Some(value) = env.storage().instance().get::<DataKey, V>(&key) {
+            env.storage().instance().remove(&key);
+            if !env.storage().persistent().has(&key) {
+                Self::write_player(env, &key, &value);
+            }
+        }
+    }
+
+    /// Per-player records live in persistent storage; entries not yet moved by
+    /// `migrate_player` are still read from instance storage.
+    fn read_player<V>(env: &Env, key: &DataKey) -> Option<V>
+    where
+        V: soroban_sdk::TryFromVal<Env, soroban_sdk::Val>,
+    {
+        if env.storage().persistent().has(key) {
+            env.storage()
+                .persistent()
+                .extend_ttl(key, PLAYER_TTL_THRESHOLD, PLAYER_TTL_EXTEND);
+            return env.storage().persistent().get(key);
+        }
+        env.storage().instance().get(key)
+    }
+
+    fn write_player<V>(env: &Env, key: &DataKey, value: &V)
+    where
+        V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+    {
+        env.storage().persistent().set(key, value);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, PLAYER_TTL_THRESHOLD, PLAYER_TTL_EXTEND);
+        env.storage().instance().remove(key);
+    }
+
+    /// Set achievements `player` holds in `nft`, with one qualifying token id
+    /// each when the set snapshots completions (empty otherwise).
+    fn nft_progress(env: &Env, nft: &Address, player: &Address, set: &Set) -> (Vec<u32>, Vec<u32>) {
+        let client = AchievementNftClient::new(env, nft);
+        let mut held = Vec::new(env);
+        let mut tokens = Vec::new(env);
+
+        if !Self::snapshot_enabled(env.clone(), set.id) {
+            for puzzle_id in client.puzzle_ids_of(player).iter() {
+                if set.achievements.contains(puzzle_id) {
+                    held.push_back(puzzle_id);
+                }
+            }
+            return (held, tokens);
+        }
+
+        for token_id in client.get_collection(player).iter() {
+            let Some(achievement) = client.get_achievement(&token_id) else {
+                continue;
+            };
+            if !set.achievements.contains(achievement.puzzle_id) || held.contains(achievement.puzzle_id) {
+                continue;
+            }
+            // Tokens used by another player's completion no longer qualify
+            let claimed: Option<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::TokenClaimed(set.id, token_id));
+            if claimed.is_some_and(|by| by != *player) {
+                continue;
+            }
+            held.push_back(achievement.puzzle_id);
+            tokens.push_back(token_id);
+        }
+        (held, tokens)
+    }
+
+    /// With an NFT linked, limited caps count distinct tokens: a token that
+    /// already counted keeps its slot whoever holds it now.
+    fn count_capped_token(env: &Env, nft: &Address, player: &Address, achievement_id: u32, cap: u32) {
+        let client = AchievementNftClient::new(env, nft);
+        let mut fresh = None;
+        for token_id in client.get_collection(player).iter() {
+            let Some(achievement) = client.get_achievement(&token_id) else {
+                continue;
+            };
+            if achievement.puzzle_id != achievement_id {
+                continue;
+            }
+            if env.storage().persistent().has(&DataKey::CapToken(token_id)) {
+                return;
+            }
+            fresh.get_or_insert(token_id);
+        }
+        let Some(token_id) = fresh else {
+            return;
+        };
+        let cnt: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AchCount(achievement_id))
+            .unwrap_or(0);
+        if cnt >= cap {
+            panic!("limited cap reached");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AchCount(achievement_id), &(cnt + 1));
+        env.storage().persistent().set(&DataKey::CapToken(token_id), &true);
+    }
+
+    // Never block the completion itself over its reward: sets without token
+    // budget left fall back to bonus points and a short pool records a debt.
+    fn pay_completion(env: &Env, player: &Address, set_id: u32, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let paid = Self::paid_out(env.clone(), set_id);
+        let token_addr = match Self::get_reward_token(env.clone()) {
+            Some(token_addr) if paid + amount <= Self::payout_cap(env.clone(), set_id) => token_addr,
+            _ => {
+                let key = DataKey::Bonus(player.clone());
+                let bonus: i128 = Self::read_player(env, &key).unwrap_or(0);
+                Self::write_player(env, &key, &(bonus + amount));
+                env.events()
+                    .publish((symbol_short!("bonus"), player.clone()), (set_id, amount));
+                return;
+            }
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PaidOut(set_id), &(paid + amount));
+        let pool = Self::reward_pool(env.clone());
+        if pool < amount {
+            let key = DataKey::Owed(player.clone());
+            let owed: i128 = Self::read_player(env, &key).unwrap_or(0);
+            Self::write_player(env, &key, &(owed + amount));
+            env.events()
+                .publish((symbol_short!("owed"), player.clone()), (set_id, amount));
+            return;
+        }
+        env.storage().instance().set(&DataKey::RewardPool, &(pool - amount));
+        token::Client::new(env, &token_addr).transfer(
+            &env.current_contract_address(),
+            player,
+            &amount,
+        );
+        env.events()
+            .publish((symbol_short!("paid"), player.clone()), (set_id, amount));
+    }
+
+    fn require_admin(env: &Env) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized");
+        admin.require_auth();
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use achievement_nft::{AchievementNFT, AchievementNFTClient};
+    use soroban_sdk::{
+        testutils::{Address as _, Events},
+        token, IntoVal, Symbol, TryFromVal, Val,
+    };
+
+    fn has_event(env: &Env, name: &str, player: &Address, data: (u32, i128)) -> bool {
+        let topics: Vec<Val> = (Symbol::new(env, name), player.clone()).into_val(env);
+        env.events().all().iter().any(|(_, t, d)| {
+            t == topics && <(u32, i128)>::try_from_val(env, &d) == Ok(data)
+        })
+    }
 
     #[test]
     fn test_set_and_progress() {
@@ -280,7 +662,7 @@ mod test {
         let contract_id = env.register_contract(None, AchievementCollection);
         let client = AchievementCollectionClient::new(&env, &contract_id);
 
-        client.initialize();
+        client.initialize(&Address::generate(&env));
 
         let name = String::from_str(&env, "Starter Set");
         let mut ach = Vec::new(&env);
@@ -292,20 +674,20 @@ mod test {
         assert_eq!(set_id, 1);
 
         let user = Address::generate(&env);
-        assert_eq!(client.is_completed(&user, &set_id), false);
+        assert!(!client.is_completed(&user, &set_id));
 
         // record achievements
-        assert_eq!(client.record_achievement(&user, &1), false);
-        assert_eq!(client.record_achievement(&user, &2), false);
+        assert!(!client.record_achievement(&user, &1));
+        assert!(!client.record_achievement(&user, &2));
         // completion on third
-        assert_eq!(client.record_achievement(&user, &3), true);
+        assert!(client.record_achievement(&user, &3));
 
         let progress = client.progress(&user, &set_id);
         assert_eq!(progress.len(), 3);
         assert_eq!(client.bonus_of(&user), 100);
 
         // re-recording an already owned achievement should not add bonus again
-        assert_eq!(client.record_achievement(&user, &3), true);
+        assert!(client.record_achievement(&user, &3));
         assert_eq!(client.bonus_of(&user), 100);
     }
 
@@ -316,7 +698,7 @@ mod test {
         let contract_id = env.register_contract(None, AchievementCollection);
         let client = AchievementCollectionClient::new(&env, &contract_id);
 
-        client.initialize();
+        client.initialize(&Address::generate(&env));
         let name = String::from_str(&env, "Limited Set");
         let mut ach = Vec::new(&env);
         ach.push_back(10);
@@ -340,6 +722,346 @@ mod test {
         assert_eq!(bp.len(), 1);
 
         // B tries to claim again 10 - already has it, no change
-        assert_eq!(client.record_achievement(&b, &10), false);
+        assert!(!client.record_achievement(&b, &10));
+    }
+
+    #[test]
+    fn test_completion_paid_from_reward_pool() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, AchievementCollection);
+        let client = AchievementCollectionClient::new(&env, &contract_id);
+        client.initialize(&Address::generate(&env));
+
+        let token_admin = Address::generate(&env);
+        let token_addr = env.register_stellar_asset_contract_v2(token_admin).address();
+        let funder = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token_addr).mint(&funder, &1_000);
+
+        client.set_reward_token(&token_addr);
+        client.fund_reward_pool(&funder, &150);
+        assert_eq!(client.reward_pool(), 150);
+
+        let mut ach = Vec::new(&env);
+        ach.push_back(1);
+        let set_id = client.create_set(&String::from_str(&env, "Paid"), &ach, &Rarity::Epic, &None, &100);
+        client.set_payout_cap(&set_id, &100);
+
+        let user = Address::generate(&env);
+        assert!(client.record_achievement(&user, &1));
+        assert!(has_event(&env, "paid", &user, (set_id, 100)));
+        assert_eq!(token::Client::new(&env, &token_addr).balance(&user), 100);
+        assert_eq!(client.reward_pool(), 50);
+        assert_eq!(client.paid_out(&set_id), 100);
+        assert_eq!(client.bonus_of(&user), 0);
+
+        // A second completion of the same set is not paid again
+        client.record_achievement(&user, &1);
+        assert_eq!(client.reward_pool(), 50);
+    }
+
+    #[test]
+    fn test_completion_with_empty_pool() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, AchievementCollection);
+        let client = AchievementCollectionClient::new(&env, &contract_id);
+        client.initialize(&Address::generate(&env));
+
+        let token_addr = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_reward_token(&token_addr);
+
+        let mut ach = Vec::new(&env);
+        ach.push_back(1);
+        let set_id = client.create_set(&String::from_str(&env, "Unfunded"), &ach, &Rarity::Common, &None, &10);
+        client.set_payout_cap(&set_id, &10);
+
+        // Completed and owed until the pool is funded
+        let user = Address::generate(&env);
+        assert!(client.record_achievement(&user, &1));
+        assert!(has_event(&env, "owed", &user, (set_id, 10)));
+        assert!(client.is_completed(&user, &set_id));
+        assert_eq!(token::Client::new(&env, &token_addr).balance(&user), 0);
+        assert_eq!(client.paid_out(&set_id), 10);
+        assert_eq!(client.owed_of(&user), 10);
+        assert!(client.try_claim_owed(&user).is_err());
+
+        let funder = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token_addr).mint(&funder, &25);
+        client.fund_reward_pool(&funder, &25);
+        assert_eq!(client.claim_owed(&user), 10);
+        assert_eq!(token::Client::new(&env, &token_addr).balance(&user), 10);
+        assert_eq!(client.owed_of(&user), 0);
+        assert_eq!(client.reward_pool(), 15);
+        assert!(client.try_claim_owed(&user).is_err());
+    }
+
+    #[test]
+    fn test_uncapped_set_earns_bonus_points() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, AchievementCollection);
+        let client = AchievementCollectionClient::new(&env, &contract_id);
+        client.initialize(&Address::generate(&env));
+
+        let token_addr = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let funder = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token_addr).mint(&funder, &100);
+        client.set_reward_token(&token_addr);
+        client.fund_reward_pool(&funder, &100);
+
+        let mut ach = Vec::new(&env);
+        ach.push_back(1);
+        let set_id = client.create_set(&String::from_str(&env, "Uncapped"), &ach, &Rarity::Common, &None, &10);
+
+        let user = Address::generate(&env);
+        assert!(client.record_achievement(&user, &1));
+        assert!(has_event(&env, "bonus", &user, (set_id, 10)));
+        assert_eq!(token::Client::new(&env, &token_addr).balance(&user), 0);
+        assert_eq!(client.bonus_of(&user), 10);
+        assert_eq!(client.reward_pool(), 100);
+    }
+
+    #[test]
+    fn test_set_payout_cap_limits_pool_drain() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, AchievementCollection);
+        let client = AchievementCollectionClient::new(&env, &contract_id);
+        client.initialize(&Address::generate(&env));
+
+        let token_addr = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let funder = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token_addr).mint(&funder, &1_000);
+        client.set_reward_token(&token_addr);
+        client.fund_reward_pool(&funder, &1_000);
+
+        let mut ach = Vec::new(&env);
+        ach.push_back(1);
+        let set_id = client.create_set(&String::from_str(&env, "Capped"), &ach, &Rarity::Common, &None, &40);
+        client.set_payout_cap(&set_id, &80);
+
+        client.record_achievement(&Address::generate(&env), &1);
+        client.record_achievement(&Address::generate(&env), &1);
+        assert_eq!(client.paid_out(&set_id), 80);
+
+        // Past the cap the completion still counts and earns bonus points
+        let late = Address::generate(&env);
+        assert!(client.record_achievement(&late, &1));
+        assert!(has_event(&env, "bonus", &late, (set_id, 40)));
+        assert_eq!(token::Client::new(&env, &token_addr).balance(&late), 0);
+        assert_eq!(client.bonus_of(&late), 40);
+        assert_eq!(client.paid_out(&set_id), 80);
+        assert_eq!(client.reward_pool(), 920);
+    }
+
+    #[test]
+    fn test_set_management_requires_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, AchievementCollection);
+        let client = AchievementCollectionClient::new(&env, &contract_id);
+        client.initialize(&Address::generate(&env));
+
+        let mut ach = Vec::new(&env);
+        ach.push_back(1);
+        let set_id = client.create_set(&String::from_str(&env, "Admin"), &ach, &Rarity::Common, &None, &5);
+
+        env.set_auths(&[]);
+        assert!(client
+            .try_create_set(&String::from_str(&env, "Rogue"), &ach, &Rarity::Common, &None, &5)
+            .is_err());
+        assert!(client.try_add_achievement_to_set(&set_id, &2).is_err());
+        assert!(client.try_set_payout_cap(&set_id, &1_000).is_err());
+    }
+
+    #[test]
+    fn test_legacy_deployment_admin_set_by_deployer() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let deployer = Address::generate(&env);
+        let salt = BytesN::from_array(&env, &[7; 32]);
+        let address = env
+            .deployer()
+            .with_address(deployer.clone(), salt.clone())
+            .deployed_address();
+        let contract_id = env.register_contract(Some(&address), AchievementCollection);
+        let client = AchievementCollectionClient::new(&env, &contract_id);
+
+        // State as left by the admin-less version
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&DataKey::NextSetId, &2u32);
+        });
+        let admin = Address::generate(&env);
+        assert!(client.try_initialize(&admin).is_err());
+        assert!(client
+            .try_migrate_admin(&Address::generate(&env), &salt, &admin)
+            .is_err());
+
+        client.migrate_admin(&deployer, &salt, &admin);
+        assert!(client
+            .try_migrate_admin(&deployer, &salt, &Address::generate(&env))
+            .is_err());
+
+        let mut ach = Vec::new(&env);
+        ach.push_back(1);
+        let set_id = client.create_set(&String::from_str(&env, "Kept"), &ach, &Rarity::Common, &None, &5);
+        assert_eq!(set_id, 2);
+    }
+
+    #[test]
+    fn test_migrate_player_from_instance_storage() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, AchievementCollection);
+        let client = AchievementCollectionClient::new(&env, &contract_id);
+        client.initialize(&Address::generate(&env));
+
+        let mut ach = Vec::new(&env);
+        ach.push_back(1);
+        ach.push_back(2);
+        let set_id = client.create_set(&String::from_str(&env, "Legacy"), &ach, &Rarity::Common, &None, &5);
+        let user = Address::generate(&env);
+
+        // Records as written by earlier versions
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .instance()
+                .set(&DataKey::PlayerProgress(user.clone(), set_id), &ach);
+            env.storage()
+                .instance()
+                .set(&DataKey::Completed(user.clone(), set_id), &true);
+            env.storage().instance().set(&DataKey::Bonus(user.clone()), &5i128);
+        });
+
+        // Unmigrated entries remain readable
+        assert!(client.is_completed(&user, &set_id));
+        assert_eq!(client.bonus_of(&user), 5);
+
+        client.migrate_player(&user);
+        env.as_contract(&contract_id, || {
+            let key = DataKey::PlayerProgress(user.clone(), set_id);
+            assert!(!env.storage().instance().has(&key));
+            assert!(env.storage().persistent().has(&key));
+            assert!(env.storage().persistent().has(&DataKey::Completed(user.clone(), set_id)));
+            assert!(!env.storage().instance().has(&DataKey::Bonus(user.clone())));
+        });
+        assert_eq!(client.progress(&user, &set_id).len(), 2);
+        assert_eq!(client.bonus_of(&user), 5);
+
+        // Already completed: no second bonus after migration
+        client.record_achievement(&user, &2);
+        assert_eq!(client.bonus_of(&user), 5);
+    }
+
+    fn setup_nft_backed(env: &Env, snapshot: bool) -> (AchievementCollectionClient<'_>, AchievementNFTClient<'_>, Address, u32) {
+        env.mock_all_auths();
+        let admin = Address::generate(env);
+        let client = AchievementCollectionClient::new(env, &env.register_contract(None, AchievementCollection));
+        client.initialize(&admin);
+        let nft = AchievementNFTClient::new(env, &env.register_contract(None, AchievementNFT));
+        nft.initialize(&admin);
+        client.set_achievement_nft(&Some(nft.address.clone()));
+
+        let mut ach = Vec::new(env);
+        ach.push_back(1);
+        ach.push_back(2);
+        let set_id = client.create_set(&String::from_str(env, "Backed"), &ach, &Rarity::Rare, &None, &10);
+        client.set_completion_snapshot(&set_id, &snapshot);
+        (client, nft, admin, set_id)
+    }
+
+    #[test]
+    fn test_progress_follows_nft_ownership() {
+        let env = Env::default();
+        let (client, nft, admin, set_id) = setup_nft_backed(&env, false);
+        let user = Address::generate(&env);
+        let buyer = Address::generate(&env);
+
+        let first = nft.craftmint(&admin, &user, &1, &String::from_str(&env, "One"));
+        nft.craftmint(&admin, &user, &2, &String::from_str(&env, "Two"));
+        assert!(client.record_achievement(&user, &1));
+        assert_eq!(client.bonus_of(&user), 10);
+
+        nft.transfer(&user, &buyer, &first);
+        assert_eq!(client.progress(&user, &set_id).len(), 1);
+        assert!(!client.is_completed(&user, &set_id));
+        assert_eq!(client.progress(&buyer, &set_id).len(), 1);
+
+        assert!(client.try_transfer_progress(&user, &buyer, &2).is_err());
+    }
+
+    #[test]
+    fn test_buyer_of_capped_token_completes_set() {
+        let env = Env::default();
+        let (client, nft, admin, _) = setup_nft_backed(&env, false);
+        let mut ach = Vec::new(&env);
+        ach.push_back(3);
+        ach.push_back(4);
+        let set_id = client.create_set(&String::from_str(&env, "Capped"), &ach, &Rarity::Epic, &Some(1), &10);
+        let seller = Address::generate(&env);
+        let buyer = Address::generate(&env);
+
+        let first = nft.craftmint(&admin, &seller, &3, &String::from_str(&env, "Three"));
+        let second = nft.craftmint(&admin, &seller, &4, &String::from_str(&env, "Four"));
+        client.record_achievement(&seller, &3);
+        assert!(client.record_achievement(&seller, &4));
+
+        // The sold tokens keep their cap slots
+        nft.transfer(&seller, &buyer, &first);
+        nft.transfer(&seller, &buyer, &second);
+        client.record_achievement(&buyer, &3);
+        assert!(client.record_achievement(&buyer, &4));
+        assert!(client.is_completed(&buyer, &set_id));
+        assert_eq!(client.bonus_of(&buyer), 10);
+
+        // A freshly minted token does not fit under the cap
+        let late = Address::generate(&env);
+        nft.craftmint(&admin, &late, &3, &String::from_str(&env, "Three"));
+        assert!(client.try_record_achievement(&late, &3).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "not owned")]
+    fn test_record_requires_owned_token() {
+        let env = Env::default();
+        let (client, _, _, _) = setup_nft_backed(&env, false);
+
+        client.record_achievement(&Address::generate(&env), &1);
+    }
+
+    #[test]
+    fn test_snapshot_blocks_completion_with_sold_tokens() {
+        let env = Env::default();
+        let (client, nft, admin, set_id) = setup_nft_backed(&env, true);
+        let seller = Address::generate(&env);
+        let buyer = Address::generate(&env);
+
+        let first = nft.craftmint(&admin, &seller, &1, &String::from_str(&env, "One"));
+        let second = nft.craftmint(&admin, &seller, &2, &String::from_str(&env, "Two"));
+        assert!(client.record_achievement(&seller, &2));
+
+        let mut consumed = Vec::new(&env);
+        consumed.push_back(first);
+        consumed.push_back(second);
+        assert_eq!(client.completion_tokens(&seller, &set_id), consumed);
+
+        nft.transfer(&seller, &buyer, &first);
+        nft.transfer(&seller, &buyer, &second);
+        assert!(!client.record_achievement(&buyer, &1));
+        assert_eq!(client.progress(&buyer, &set_id).len(), 0);
+        assert_eq!(client.bonus_of(&buyer), 0);
+
+        // Fresh tokens still count
+        nft.craftmint(&admin, &buyer, &1, &String::from_str(&env, "One"));
+        nft.craftmint(&admin, &buyer, &2, &String::from_str(&env, "Two"));
+        assert!(client.record_achievement(&buyer, &1));
+        assert_eq!(client.bonus_of(&buyer), 10);
     }
 }
@@ -1,10 +1,11 @@
 #![no_std]
 
-use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec,
-};
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
 
+const DEFAULT_NAME: &str = "Puzzle Achievements";
+const DEFAULT_SYMBOL: &str = "ACHV";
+const MAX_URI_LEN: usize = 200;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Achievement {
@@ -14,6 +15,26 @@ pub struct Achievement {
     pub timestamp: u64,
 }
 
+/// Permissions of a contract authorized to mint through `craftmint`.
+#[contracttype]
+#[derive(Clone)]
+pub struct MinterConfig {
+    /// Puzzle ids this minter may mint for; empty means any puzzle.
+    pub puzzle_ids: Vec<u32>,
+    /// Maximum number of tokens this minter may mint; `None` is unlimited.
+    pub quota: Option<u32>,
+    /// Tokens minted so far.
+    pub minted: u32,
+}
+
+/// Single-token approval, valid up to and including `live_until_ledger`.
+#[contracttype]
+#[derive(Clone)]
+pub struct TokenApproval {
+    pub approved: Address,
+    pub live_until_ledger: u32,
+}
+
 #[contracttype]
 pub enum DataKey {
     Achievement(u32),          // Persistent: Individual NFT data
@@ -22,6 +43,13 @@ pub enum DataKey {
     TotalSupply,               // Instance: Current count of NFTs
     Admin,                     // Instance: Contract administrator
     PuzzleCompleted(Address, u32), // Tracks if a user has completed a puzzle
+    Minter(Address),           // Persistent: MinterConfig of an authorized minter
+    Verifier(Address),         // Persistent: May mark puzzles completed
+    Approval(u32),             // Persistent: TokenApproval of a token
+    Operator(Address, Address), // Persistent: (owner, operator) -> live_until_ledger
+    Name,                      // Instance: Collection name
+    Symbol,                    // Instance: Collection symbol
+    BaseUri,                   // Instance: Prefix of every token URI
 }
 
 #[contract]
@@ -39,11 +67,63 @@ impl AchievementNFT {
         env.storage().instance().set(&DataKey::TotalSupply, &0u32);
     }
 
-    /// Mark a puzzle as completed by a user.
-    /// Admin function to mark a puzzle as completed for a user.
-    pub fn mark_puzzle_completed(env: Env, user: Address, puzzle_id: u32) {
+    /// Authorize a minter, e.g. a `crafting` or `seasonal_event` contract (admin only).
+    /// Re-authorizing replaces the scope and quota and resets the minted count.
+    pub fn authorize_minter(env: Env, minter: Address, puzzle_ids: Vec<u32>, quota: Option<u32>) {
+        Self::require_admin(&env);
+        let key = DataKey::Minter(minter);
+        let config = MinterConfig {
+            puzzle_ids,
+            quota,
+            minted: 0,
+        };
+        env.storage().persistent().set(&key, &config);
+        env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+    }
+
+    /// Revoke minter authorization (admin only).
+    pub fn revoke_minter(env: Env, minter: Address) {
+        Self::require_admin(&env);
+        env.storage().persistent().remove(&DataKey::Minter(minter));
+    }
+
+    /// Returns the configuration of an authorized minter.
+    pub fn get_minter(env: Env, minter: Address) -> Option<MinterConfig> {
+        env.storage().persistent().get(&DataKey::Minter(minter))
+    }
+
+    /// Allow a contract such as `puzzle_verification` to mark puzzles completed (admin only).
+    pub fn authorize_verifier(env: Env, verifier: Address) {
+        Self::require_admin(&env);
+        let key = DataKey::Verifier(verifier);
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+    }
+
+    /// Revoke verifier authorization (admin only).
+    pub fn revoke_verifier(env: Env, verifier: Address) {
+        Self::require_admin(&env);
+        env.storage().persistent().remove(&DataKey::Verifier(verifier));
+    }
+
+    /// Check if address may mark puzzles completed. The admin always can.
+    pub fn is_verifier(env: Env, verifier: Address) -> bool {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+        if verifier == admin {
+            return true;
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::Verifier(verifier))
+            .unwrap_or(false)
+    }
+
+    /// Mark a puzzle as completed for a user (admin or authorized verifier).
+    pub fn mark_puzzle_completed(env: Env, caller: Address, user: Address, puzzle_id: u32) {
+        caller.require_auth();
+        if !Self::is_verifier(env.clone(), caller) {
+            panic!("Not authorized");
+        }
         let key = DataKey::PuzzleCompleted(user.clone(), puzzle_id);
         env.storage()
             .persistent()
@@ -68,19 +148,43 @@ impl AchievementNFT {
             panic!("Puzzle not completed");
         }
 
-        Self::craftmint(env, to, puzzle_id, metadata)
         Self::mint_internal(env, to, puzzle_id, metadata)
     }
 
-    /// Mint a new NFT for crafting purposes (testnet: no auth required).
-    pub fn craftmint(env: Env, to: Address, puzzle_id: u32, metadata: String) -> u32 {
-        // For testnet deployment, remove admin auth requirement
-        // let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        // admin.require_auth();
+    /// Mint a new NFT without a completion record (admin or authorized minter).
+    /// Minters are limited to their puzzle scope and quota.
+    pub fn craftmint(env: Env, minter: Address, to: Address, puzzle_id: u32, metadata: String) -> u32 {
+        minter.require_auth();
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if minter != admin {
+            let key = DataKey::Minter(minter);
+            let mut config: MinterConfig = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .expect("Not authorized");
+            if !config.puzzle_ids.is_empty() && !config.puzzle_ids.contains(puzzle_id) {
+                panic!("Puzzle outside minter scope");
+            }
+            if let Some(quota) = config.quota {
+                if config.minted >= quota {
+                    panic!("Minter quota exhausted");
+                }
+            }
+            config.minted += 1;
+            env.storage().persistent().set(&key, &config);
+            env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+        }
 
         Self::mint_internal(env, to, puzzle_id, metadata)
     }
 
+    fn require_admin(env: &Env) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+    }
+
     fn mint_internal(env: Env, to: Address, puzzle_id: u32, metadata: String) -> u32 {
         let token_id: u32 = env.storage().instance().get(&DataKey::NextTokenId).unwrap();
 
@@ -120,7 +224,22 @@ impl AchievementNFT {
     /// Transfers a token safely
     pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
         from.require_auth();
+        Self::transfer_internal(env, from, to, token_id);
+    }
 
+    /// Transfers a token on behalf of `from` by its owner, approved address or operator.
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32) {
+        spender.require_auth();
+        if spender != from
+            && Self::get_approved(env.clone(), token_id) != Some(spender.clone())
+            && !Self::is_approved_for_all(env.clone(), from.clone(), spender)
+        {
+            panic!("Not approved");
+        }
+        Self::transfer_internal(env, from, to, token_id);
+    }
+
+    fn transfer_internal(env: Env, from: Address, to: Address, token_id: u32) {
         if from == to {
             panic!("Cannot transfer to self");
         }
@@ -148,7 +267,8 @@ impl AchievementNFT {
         env.storage().persistent().set(&DataKey::OwnerCollection(to.clone()), &to_col);
         env.storage().persistent().extend_ttl(&DataKey::OwnerCollection(to.clone()), 100_000, 500_000);
 
-        // Update owner
+        // Update owner, dropping any approval granted by the previous owner
+        env.storage().persistent().remove(&DataKey::Approval(token_id));
         achievement.owner = to.clone();
         env.storage().persistent().set(&DataKey::Achievement(token_id), &achievement);
         env.storage().persistent().extend_ttl(&DataKey::Achievement(token_id), 100_000, 500_000);
@@ -164,6 +284,139 @@ impl AchievementNFT {
             .unwrap_or(Vec::new(&env))
     }
 
+    /// Returns the number of tokens owned by an address.
+    pub fn balance(env: Env, owner: Address) -> u32 {
+        Self::get_collection(env, owner).len()
+    }
+
+    /// Approve `approved` to transfer `token_id` until `live_until_ledger`.
+    /// Callable by the owner or one of its operators; a ledger of 0 revokes.
+    pub fn approve(env: Env, approver: Address, approved: Address, token_id: u32, live_until_ledger: u32) {
+        approver.require_auth();
+
+        let owner = Self::owner_of(env.clone(), token_id);
+        if approver != owner && !Self::is_approved_for_all(env.clone(), owner.clone(), approver) {
+            panic!("Not authorized");
+        }
+
+        let key = DataKey::Approval(token_id);
+        if live_until_ledger == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            if live_until_ledger < env.ledger().sequence() {
+                panic!("Invalid expiration");
+            }
+            let approval = TokenApproval {
+                approved: approved.clone(),
+                live_until_ledger,
+            };
+            env.storage().persistent().set(&key, &approval);
+            env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+        }
+
+        env.events()
+            .publish((symbol_short!("approve"), owner, token_id), (approved, live_until_ledger));
+    }
+
+    /// Let `operator` transfer and approve every token of `owner` until
+    /// `live_until_ledger`. A ledger of 0 revokes.
+    pub fn approve_for_all(env: Env, owner: Address, operator: Address, live_until_ledger: u32) {
+        owner.require_auth();
+
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if live_until_ledger == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            if live_until_ledger < env.ledger().sequence() {
+                panic!("Invalid expiration");
+            }
+            env.storage().persistent().set(&key, &live_until_ledger);
+            env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+        }
+
+        env.events()
+            .publish((symbol_short!("appr_all"), owner), (operator, live_until_ledger));
+    }
+
+    /// Returns the address approved for a token, if the approval is still live.
+    pub fn get_approved(env: Env, token_id: u32) -> Option<Address> {
+        let approval: TokenApproval = env.storage().persistent().get(&DataKey::Approval(token_id))?;
+        if approval.live_until_ledger < env.ledger().sequence() {
+            return None;
+        }
+        Some(approval.approved)
+    }
+
+    /// True if `operator` may currently manage every token of `owner`.
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, u32>(&DataKey::Operator(owner, operator))
+            .map(|live_until| live_until >= env.ledger().sequence())
+            .unwrap_or(false)
+    }
+
+    /// Set the collection name, symbol and token URI prefix (admin only).
+    pub fn set_collection_metadata(env: Env, name: String, symbol: String, base_uri: String) {
+        Self::require_admin(&env);
+        if base_uri.len() as usize > MAX_URI_LEN {
+            panic!("Base URI too long");
+        }
+        env.storage().instance().set(&DataKey::Name, &name);
+        env.storage().instance().set(&DataKey::Symbol, &symbol);
+        env.storage().instance().set(&DataKey::BaseUri, &base_uri);
+    }
+
+    /// Returns the collection name.
+    pub fn name(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::Name)
+            .unwrap_or(String::from_str(&env, DEFAULT_NAME))
+    }
+
+    /// Returns the collection symbol.
+    pub fn symbol(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::Symbol)
+            .unwrap_or(String::from_str(&env, DEFAULT_SYMBOL))
+    }
+
+    /// Returns the base URI followed by the token id, or an empty string when
+    /// no base URI is set.
+    pub fn token_uri(env: Env, token_id: u32) -> String {
+        Self::owner_of(env.clone(), token_id);
+
+        let base_uri: String = env
+            .storage()
+            .instance()
+            .get(&DataKey::BaseUri)
+            .unwrap_or(String::from_str(&env, ""));
+        if base_uri.is_empty() {
+            return base_uri;
+        }
+
+        let mut digits = [0u8; 10];
+        let mut start = digits.len();
+        let mut id = token_id;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (id % 10) as u8;
+            id /= 10;
+            if id == 0 {
+                break;
+            }
+        }
+
+        let base_len = base_uri.len() as usize;
+        let out_len = base_len + digits.len() - start;
+        let mut out = [0u8; MAX_URI_LEN + 10];
+        base_uri.copy_into_slice(&mut out[..base_len]);
+        out[base_len..out_len].copy_from_slice(&digits[start..]);
+        String::from_bytes(&env, &out[..out_len])
+    }
+
     /// Get owner of a specific token.
     pub fn owner_of(env: Env, token_id: u32) -> Address {
         let achievement: Achievement = env
@@ -179,17 +432,34 @@ impl AchievementNFT {
         env.storage().instance().get(&DataKey::TotalSupply).unwrap_or(0)
     }
 
-    /// Destroys a token (testnet: no auth required for crafting).
+    /// Destroys a token (owner only).
     pub fn burn(env: Env, token_id: u32) {
+        let owner = Self::owner_of(env.clone(), token_id);
+        owner.require_auth();
+        Self::burn_internal(env, token_id);
+    }
+
+    /// Destroys a token on behalf of its owner. `spender` must be the owner,
+    /// the token's approved address or one of the owner's operators.
+    pub fn burn_from(env: Env, spender: Address, token_id: u32) {
+        spender.require_auth();
+        let owner = Self::owner_of(env.clone(), token_id);
+        if spender != owner
+            && Self::get_approved(env.clone(), token_id) != Some(spender.clone())
+            && !Self::is_approved_for_all(env.clone(), owner, spender)
+        {
+            panic!("Not approved");
+        }
+        Self::burn_internal(env, token_id);
+    }
+
+    fn burn_internal(env: Env, token_id: u32) {
         let achievement: Achievement = env
             .storage()
             .persistent()
             .get(&DataKey::Achievement(token_id))
             .expect("Token does not exist");
 
-        // For testnet deployment, remove owner auth requirement
-        // achievement.owner.require_auth();
-
         let mut collection = Self::get_collection(env.clone(), achievement.owner.clone());
         if let Some(index) = collection.first_index_of(token_id) {
             collection.remove(index);
@@ -197,6 +467,7 @@ impl AchievementNFT {
         }
 
         env.storage().persistent().remove(&DataKey::Achievement(token_id));
+        env.storage().persistent().remove(&DataKey::Approval(token_id));
         let total: u32 = env.storage().instance().get(&DataKey::TotalSupply).unwrap();
         env.storage().instance().set(&DataKey::TotalSupply, &(total - 1));
 
@@ -214,9 +485,9 @@ impl AchievementNFT {
         let mut puzzles = Vec::new(&env);
 
         for tid in token_ids.iter() {
-            let token_id = tid.clone();
+            let token_id = tid;
             if let Some(a) = Self::get_achievement(env.clone(), token_id) {
-                if !puzzles.contains(&a.puzzle_id) {
+                if !puzzles.contains(a.puzzle_id) {
                     puzzles.push_back(a.puzzle_id);
                 }
             }
@@ -228,7 +499,7 @@ impl AchievementNFT {
     /// True if `owner` owns an achievement NFT whose `puzzle_id` equals `puzzle_id`.
     pub fn has_puzzle(env: Env, owner: Address, puzzle_id: u32) -> bool {
         let puzzles = Self::puzzle_ids_of(env, owner);
-        puzzles.contains(&puzzle_id)
+        puzzles.contains(puzzle_id)
     }
 }
 
@@ -1,7 +1,10 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String, Vec,
+};
 
 #[test]
 fn test_nft_lifecycle() {
@@ -19,7 +22,7 @@ fn test_nft_lifecycle() {
 
     // Mark puzzle completed (admin calls it → mocked auth allows it)
     let puzzle_id = 101u32;
-    client.mark_puzzle_completed(&user_a, &puzzle_id);
+    client.mark_puzzle_completed(&admin, &user_a, &puzzle_id);
 
     let metadata = String::from_str(&env, "First Puzzle Master");
     let token_id = client.mint(&user_a, &puzzle_id, &metadata);
@@ -52,6 +55,28 @@ fn test_nft_lifecycle() {
     assert!(client.get_achievement(&token_id).is_none());
 }
 
+#[test]
+fn test_burn_from_requires_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let token_id = client.craftmint(&admin, &owner, &1, &String::from_str(&env, "Puzzle"));
+
+    assert!(client.try_burn_from(&spender, &token_id).is_err());
+
+    client.approve(&owner, &spender, &token_id, &1_000);
+    client.burn_from(&spender, &token_id);
+    assert!(client.get_achievement(&token_id).is_none());
+    assert_eq!(client.get_collection(&owner).len(), 0);
+}
+
 #[test]
 #[should_panic(expected = "Already initialized")]
 fn test_already_initialized() {
@@ -118,10 +143,180 @@ fn test_transfer_to_self() {
     client.initialize(&admin);
 
     let puzzle_id = 303u32;
-    client.mark_puzzle_completed(&user, &puzzle_id);
+    client.mark_puzzle_completed(&admin, &user, &puzzle_id);
 
     let metadata = String::from_str(&env, "Self Transfer Test");
     let token_id = client.mint(&user, &puzzle_id, &metadata);
 
     client.transfer(&user, &user, &token_id); // should panic
-}
\ No newline at end of file
+}
+
+fn setup_minter(env: &Env, puzzle_ids: Vec<u32>, quota: Option<u32>) -> (AchievementNFTClient<'_>, Address) {
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+
+    let minter = Address::generate(env);
+    client.authorize_minter(&minter, &puzzle_ids, &quota);
+    (client, minter)
+}
+
+#[test]
+fn test_minter_within_scope_and_quota() {
+    let env = Env::default();
+    let (client, minter) = setup_minter(&env, vec![&env, 5u32], Some(2));
+    let user = Address::generate(&env);
+
+    let token_id = client.craftmint(&minter, &user, &5, &String::from_str(&env, "Crafted"));
+    client.craftmint(&minter, &user, &5, &String::from_str(&env, "Crafted"));
+
+    assert_eq!(client.owner_of(&token_id), user);
+    assert_eq!(client.get_minter(&minter).unwrap().minted, 2);
+    assert_eq!(client.total_supply(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Minter quota exhausted")]
+fn test_minter_quota_exhausted() {
+    let env = Env::default();
+    let (client, minter) = setup_minter(&env, Vec::new(&env), Some(1));
+    let user = Address::generate(&env);
+
+    client.craftmint(&minter, &user, &1, &String::from_str(&env, "First"));
+    client.craftmint(&minter, &user, &2, &String::from_str(&env, "Second")); // should panic
+}
+
+#[test]
+#[should_panic(expected = "Puzzle outside minter scope")]
+fn test_minter_outside_scope() {
+    let env = Env::default();
+    let (client, minter) = setup_minter(&env, vec![&env, 5u32], None);
+
+    client.craftmint(&minter, &Address::generate(&env), &6, &String::from_str(&env, "Other"));
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_revoked_minter_cannot_mint() {
+    let env = Env::default();
+    let (client, minter) = setup_minter(&env, Vec::new(&env), None);
+
+    client.revoke_minter(&minter);
+    assert!(client.get_minter(&minter).is_none());
+    client.craftmint(&minter, &Address::generate(&env), &1, &String::from_str(&env, "Revoked"));
+}
+
+#[test]
+fn test_verifier_marks_completion() {
+    let env = Env::default();
+    let (client, _) = setup_minter(&env, Vec::new(&env), None);
+    let verifier = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    assert!(!client.is_verifier(&verifier));
+    client.authorize_verifier(&verifier);
+    client.mark_puzzle_completed(&verifier, &user, &42);
+
+    let token_id = client.mint(&user, &42, &String::from_str(&env, "Verified"));
+    assert!(client.has_puzzle(&user, &42));
+    assert_eq!(client.owner_of(&token_id), user);
+
+    client.revoke_verifier(&verifier);
+    assert!(client.try_mark_puzzle_completed(&verifier, &user, &43).is_err());
+}
+
+fn setup_token(env: &Env) -> (AchievementNFTClient<'_>, Address, u32) {
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let owner = Address::generate(env);
+    let token_id = client.craftmint(&admin, &owner, &1, &String::from_str(env, "Token"));
+    (client, owner, token_id)
+}
+
+#[test]
+fn test_approved_transfer_from() {
+    let env = Env::default();
+    let (client, owner, token_id) = setup_token(&env);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    client.approve(&owner, &spender, &token_id, &1_000);
+    assert_eq!(client.get_approved(&token_id), Some(spender.clone()));
+
+    client.transfer_from(&spender, &owner, &buyer, &token_id);
+    assert_eq!(client.owner_of(&token_id), buyer);
+    assert_eq!(client.balance(&owner), 0);
+    assert_eq!(client.balance(&buyer), 1);
+    // The approval does not survive the transfer
+    assert_eq!(client.get_approved(&token_id), None);
+}
+
+#[test]
+fn test_operator_transfer_from_and_revoke() {
+    let env = Env::default();
+    let (client, owner, token_id) = setup_token(&env);
+    let operator = Address::generate(&env);
+    let receiver = Address::generate(&env);
+
+    client.approve_for_all(&owner, &operator, &1_000);
+    assert!(client.is_approved_for_all(&owner, &operator));
+
+    // Operators may also grant single-token approvals
+    client.approve(&operator, &receiver, &token_id, &1_000);
+    assert_eq!(client.get_approved(&token_id), Some(receiver.clone()));
+
+    client.approve_for_all(&owner, &operator, &0);
+    assert!(!client.is_approved_for_all(&owner, &operator));
+    assert!(client.try_transfer_from(&operator, &owner, &receiver, &token_id).is_err());
+}
+
+#[test]
+fn test_approval_expires() {
+    let env = Env::default();
+    let (client, owner, token_id) = setup_token(&env);
+    let spender = Address::generate(&env);
+
+    let live_until = env.ledger().sequence() + 10;
+    client.approve(&owner, &spender, &token_id, &live_until);
+    client.approve_for_all(&owner, &spender, &live_until);
+
+    env.ledger().with_mut(|li| li.sequence_number = live_until + 1);
+    assert_eq!(client.get_approved(&token_id), None);
+    assert!(!client.is_approved_for_all(&owner, &spender));
+}
+
+#[test]
+#[should_panic(expected = "Not approved")]
+fn test_transfer_from_without_approval() {
+    let env = Env::default();
+    let (client, owner, token_id) = setup_token(&env);
+    let spender = Address::generate(&env);
+
+    client.transfer_from(&spender, &owner, &spender, &token_id);
+}
+
+#[test]
+fn test_collection_metadata_and_token_uri() {
+    let env = Env::default();
+    let (client, _, token_id) = setup_token(&env);
+
+    assert_eq!(client.name(), String::from_str(&env, "Puzzle Achievements"));
+    assert_eq!(client.symbol(), String::from_str(&env, "ACHV"));
+    assert_eq!(client.token_uri(&token_id), String::from_str(&env, ""));
+
+    client.set_collection_metadata(
+        &String::from_str(&env, "Quest Badges"),
+        &String::from_str(&env, "QB"),
+        &String::from_str(&env, "ipfs://badges/"),
+    );
+    assert_eq!(client.name(), String::from_str(&env, "Quest Badges"));
+    assert_eq!(client.symbol(), String::from_str(&env, "QB"));
+    assert_eq!(client.token_uri(&token_id), String::from_str(&env, "ipfs://badges/1"));
+}
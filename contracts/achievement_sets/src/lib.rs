@@ -1,7 +1,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, String, Symbol, Vec,
+    contract, contractimpl, contracttype, symbol_short, vec, Address, Env, Map, String, Symbol,
+    Vec,
 };
 
 // ──────────────────────────────────────────────────────────
@@ -43,6 +44,43 @@ pub struct AchievementSet {
     pub unlock_key: Symbol,
 }
 
+/// Extra claim conditions of a set. Zero values disable a condition.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SetRules {
+    /// Claims are refused before this timestamp.
+    pub claim_start: u64,
+    /// Claims are refused after this timestamp.
+    pub claim_end: u64,
+    /// Required puzzles must have been completed in `required_puzzle_ids` order.
+    pub ordered: bool,
+    /// Maximum seconds between the first and last required completion.
+    pub max_span_secs: u64,
+}
+
+/// Mirror of `skill_rating::Tier`, lowest first.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum SkillTier {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Diamond,
+    Master,
+    Grandmaster,
+}
+
+/// Mirror of `achievement_nft::Achievement`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Achievement {
+    pub owner: Address,
+    pub puzzle_id: u32,
+    pub metadata: String,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Synergy {
@@ -92,6 +130,11 @@ pub enum DataKey {
 
     Set(u32),     // AchievementSet
     Synergy(u32), // Synergy
+    SetRules(u32), // SetRules
+
+    // Skill gating
+    SkillRating,           // Address of the skill_rating contract
+    TierMinSkill(SetTier), // SkillTier required to claim sets of a tier
 
     // Player progress is derived from achievement NFTs but cached here.
     PlayerProgress(Address, u32), // Vec<u32> completed puzzle ids for (player, set_id)
@@ -132,6 +175,14 @@ const EVT_ED_XFER: Symbol = symbol_short!("edxfer");
 #[soroban_sdk::contractclient(name = "AchievementNFTClient")]
 pub trait AchievementNFT {
     fn puzzle_ids_of(env: Env, owner: Address) -> Vec<u32>;
+    fn get_collection(env: Env, owner: Address) -> Vec<u32>;
+    fn get_achievement(env: Env, token_id: u32) -> Option<Achievement>;
+}
+
+// Tier lookup for skill-gated set tiers.
+#[soroban_sdk::contractclient(name = "SkillRatingClient")]
+pub trait SkillRating {
+    fn get_tier(env: Env, player: Address) -> (SkillTier, u32);
 }
 
 // We only need mint from reward_token (bonus distribution).
@@ -278,8 +329,56 @@ impl AchievementSets {
         id
     }
 
+    /// Restrict when and how a set can be claimed.
+    pub fn set_set_rules(env: Env, admin: Address, set_id: u32, rules: SetRules) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+        Self::load_set(&env, set_id);
+
+        if rules.claim_end != 0 && rules.claim_end < rules.claim_start {
+            panic!("invalid claim window");
+        }
+        env.storage().persistent().set(&DataKey::SetRules(set_id), &rules);
+    }
+
+    /// Link the `skill_rating` contract used for tier requirements.
+    pub fn set_skill_rating(env: Env, admin: Address, skill_rating: Option<Address>) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+
+        match skill_rating {
+            Some(addr) => env.storage().persistent().set(&DataKey::SkillRating, &addr),
+            None => env.storage().persistent().remove(&DataKey::SkillRating),
+        }
+    }
+
+    /// Require a minimum skill tier to claim any set of `tier`. `None` clears it.
+    pub fn set_tier_min_skill(env: Env, admin: Address, tier: SetTier, min_skill: Option<SkillTier>) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+
+        match min_skill {
+            Some(skill) => env
+                .storage()
+                .persistent()
+                .set(&DataKey::TierMinSkill(tier), &skill),
+            None => env.storage().persistent().remove(&DataKey::TierMinSkill(tier)),
+        }
+    }
+
     // ───────────── Views ─────────────
 
+    pub fn get_set_rules(env: Env, set_id: u32) -> SetRules {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SetRules(set_id))
+            .unwrap_or_default()
+    }
+
+    pub fn get_tier_min_skill(env: Env, tier: SetTier) -> Option<SkillTier> {
+        env.storage().persistent().get(&DataKey::TierMinSkill(tier))
+    }
+
     pub fn get_config(env: Env) -> Config {
         env.storage().persistent().get(&DataKey::Config).unwrap()
     }
@@ -424,6 +523,13 @@ impl AchievementSets {
             panic!("already claimed");
         }
 
+        let rules = Self::get_set_rules(env.clone(), set_id);
+        let now = env.ledger().timestamp();
+        if now < rules.claim_start || (rules.claim_end != 0 && now > rules.claim_end) {
+            panic!("claim window closed");
+        }
+        Self::assert_skill_tier(&env, &player, set.tier);
+
         // Enforce limited edition cap (cap is on claims, not on progress)
         if let Some(cap) = set.limited_edition_cap {
             let claimed: u32 = env
@@ -441,6 +547,9 @@ impl AchievementSets {
         if !view.is_completed {
             panic!("not completed");
         }
+        if rules.ordered || rules.max_span_secs > 0 {
+            Self::assert_completion_sequence(&env, &cfg, &player, &set, &rules);
+        }
 
         // Compute bonus
         let tier_bonus = Self::tier_bonus(set.tier);
@@ -450,7 +559,7 @@ impl AchievementSets {
         let token = RewardTokenClient::new(&env, &cfg.reward_token);
         let minter = env.current_contract_address();
 
-        env.authorize_as_current_contract(vec![]);
+        env.authorize_as_current_contract(vec![&env]);
         token.mint(&minter, &player, &bonus);
 
         // Mark claim
@@ -545,7 +654,7 @@ impl AchievementSets {
         let token = RewardTokenClient::new(&env, &cfg.reward_token);
         let minter = env.current_contract_address();
 
-        env.authorize_as_current_contract(vec![]);
+        env.authorize_as_current_contract(vec![&env]);
         token.mint(&minter, &player, &syn.bonus);
 
         env.storage()
@@ -646,6 +755,96 @@ impl AchievementSets {
         }
     }
 
+    fn assert_skill_tier(env: &Env, player: &Address, tier: SetTier) {
+        let Some(min_skill) = Self::get_tier_min_skill(env.clone(), tier) else {
+            return;
+        };
+        let skill_rating: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SkillRating)
+            .expect("skill rating not set");
+        // A player the rating contract cannot rate does not meet any minimum
+        let skill = match SkillRatingClient::new(env, &skill_rating).try_get_tier(player) {
+            Ok(Ok((skill, _))) => Some(skill),
+            _ => None,
+        };
+        if skill.is_none_or(|skill| skill < min_skill) {
+            panic!("skill tier too low");
+        }
+    }
+
+    /// Check ordering and span rules: any combination of one held
+    /// achievement NFT per required puzzle may satisfy them.
+    fn assert_completion_sequence(
+        env: &Env,
+        cfg: &Config,
+        player: &Address,
+        set: &AchievementSet,
+        rules: &SetRules,
+    ) {
+        let nft = AchievementNFTClient::new(env, &cfg.achievement_nft);
+        let mut held: Map<u32, Vec<u64>> = Map::new(env);
+        for token_id in nft.get_collection(player).iter() {
+            if let Some(achievement) = nft.get_achievement(&token_id) {
+                if !set.required_puzzle_ids.contains(achievement.puzzle_id) {
+                    continue;
+                }
+                let mut times = held.get(achievement.puzzle_id).unwrap_or(Vec::new(env));
+                times.push_back(achievement.timestamp);
+                held.set(achievement.puzzle_id, times);
+            }
+        }
+        for puzzle_id in set.required_puzzle_ids.iter() {
+            if !held.contains_key(puzzle_id) {
+                panic!("not completed");
+            }
+        }
+
+        if Self::earliest_sequence_end(set, rules, &held, 0).is_none() {
+            panic!("completed out of order");
+        }
+        if rules.max_span_secs == 0 {
+            return;
+        }
+        // The best combination starts at one of the held timestamps
+        for times in held.values().iter() {
+            for start in times.iter() {
+                if let Some(end) = Self::earliest_sequence_end(set, rules, &held, start) {
+                    if end - start <= rules.max_span_secs {
+                        return;
+                    }
+                }
+            }
+        }
+        panic!("completed too far apart");
+    }
+
+    /// Pick the earliest completion at or after `start` for each required
+    /// puzzle, no earlier than the previous one when the set is ordered, and
+    /// return the latest pick.
+    fn earliest_sequence_end(
+        set: &AchievementSet,
+        rules: &SetRules,
+        held: &Map<u32, Vec<u64>>,
+        start: u64,
+    ) -> Option<u64> {
+        let mut floor = start;
+        let mut end = start;
+        for puzzle_id in set.required_puzzle_ids.iter() {
+            let pick = held
+                .get(puzzle_id)?
+                .iter()
+                .filter(|ts| *ts >= floor)
+                .min()?;
+            if rules.ordered {
+                floor = pick;
+            }
+            end = end.max(pick);
+        }
+        Some(end)
+    }
+
     fn is_completed(set: &AchievementSet, completed_puzzle_ids: &Vec<u32>) -> bool {
         if completed_puzzle_ids.len() < set.required_puzzle_ids.len() {
             return false;
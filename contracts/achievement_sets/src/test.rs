@@ -3,14 +3,18 @@
 use super::*;
 use achievement_nft::AchievementNFTClient;
 use reward_token::RewardTokenClient;
-use soroban_sdk::{testutils::Address as _, symbol_short, Address, Env, String, Vec};
+use skill_rating::{SkillRating as SkillRatingContract, SkillRatingClient as RatingClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    symbol_short, Address, Env, String, Vec,
+};
 
 fn setup(
     env: &Env,
 ) -> (
-    AchievementSetsClient,
-    AchievementNFTClient,
-    RewardTokenClient,
+    AchievementSetsClient<'_>,
+    AchievementNFTClient<'_>,
+    RewardTokenClient<'_>,
     Address,
     Address,
 ) {
@@ -43,7 +47,7 @@ fn setup(
 #[test]
 fn test_set_creation_and_claim() {
     let env = Env::default();
-    let (sets, nft, token, admin, nft_id) = setup(&env);
+    let (sets, nft, token, admin, _nft_id) = setup(&env);
 
     let user = Address::generate(&env);
     let name = String::from_str(&env, "Starter Set");
@@ -64,17 +68,17 @@ fn test_set_creation_and_claim() {
     assert_eq!(set_id, 1);
 
     let progress = sets.progress(&user, &set_id);
-    assert_eq!(progress.is_completed, false);
+    assert!(!progress.is_completed);
     assert_eq!(progress.completed_count, 0);
     assert_eq!(progress.required_count, 3);
 
     // Mint achievement NFTs via craftmint (no puzzle completion required in test)
-    nft.craftmint(&user, &1, &String::from_str(&env, "A1"));
-    nft.craftmint(&user, &2, &String::from_str(&env, "A2"));
-    nft.craftmint(&user, &3, &String::from_str(&env, "A3"));
+    nft.craftmint(&admin, &user, &1, &String::from_str(&env, "A1"));
+    nft.craftmint(&admin, &user, &2, &String::from_str(&env, "A2"));
+    nft.craftmint(&admin, &user, &3, &String::from_str(&env, "A3"));
 
     let progress = sets.sync_player_set(&user, &set_id);
-    assert_eq!(progress.is_completed, true);
+    assert!(progress.is_completed);
     assert_eq!(progress.completed_count, 3);
 
     let bonus = sets.claim_set_bonus(&user, &set_id);
@@ -82,7 +86,7 @@ fn test_set_creation_and_claim() {
     assert_eq!(token.balance(&user), 100);
 
     let progress = sets.progress(&user, &set_id);
-    assert_eq!(progress.is_claimed, true);
+    assert!(progress.is_claimed);
 
     let unlocks = sets.get_unlocks(&user);
     assert_eq!(unlocks.len(), 1);
@@ -93,7 +97,7 @@ fn test_set_creation_and_claim() {
 #[should_panic(expected = "already claimed")]
 fn test_double_claim_panics() {
     let env = Env::default();
-    let (sets, nft, token, admin, _) = setup(&env);
+    let (sets, nft, _token, admin, _) = setup(&env);
 
     let user = Address::generate(&env);
     let name = String::from_str(&env, "Single Set");
@@ -111,8 +115,8 @@ fn test_double_claim_panics() {
         &symbol_short!("single"),
     );
 
-    nft.craftmint(&user, &10, &String::from_str(&env, "A"));
-    nft.craftmint(&user, &20, &String::from_str(&env, "B"));
+    nft.craftmint(&admin, &user, &10, &String::from_str(&env, "A"));
+    nft.craftmint(&admin, &user, &20, &String::from_str(&env, "B"));
 
     sets.claim_set_bonus(&user, &set_id);
     sets.claim_set_bonus(&user, &set_id); // panic
@@ -121,7 +125,7 @@ fn test_double_claim_panics() {
 #[test]
 fn test_limited_edition_and_edition_tokens() {
     let env = Env::default();
-    let (sets, nft, token, admin, _) = setup(&env);
+    let (sets, nft, _token, admin, _) = setup(&env);
 
     let user_a = Address::generate(&env);
     let user_b = Address::generate(&env);
@@ -141,8 +145,8 @@ fn test_limited_edition_and_edition_tokens() {
     );
 
     // User A completes and claims
-    nft.craftmint(&user_a, &100, &String::from_str(&env, "X"));
-    nft.craftmint(&user_a, &200, &String::from_str(&env, "Y"));
+    nft.craftmint(&admin, &user_a, &100, &String::from_str(&env, "X"));
+    nft.craftmint(&admin, &user_a, &200, &String::from_str(&env, "Y"));
     let bonus_a = sets.claim_set_bonus(&user_a, &set_id);
     assert_eq!(bonus_a, 250); // 200 base + 50 rare tier
 
@@ -154,8 +158,8 @@ fn test_limited_edition_and_edition_tokens() {
     assert_eq!(ed.owner, user_a);
 
     // User B completes and claims (2nd of 2)
-    nft.craftmint(&user_b, &100, &String::from_str(&env, "X2"));
-    nft.craftmint(&user_b, &200, &String::from_str(&env, "Y2"));
+    nft.craftmint(&admin, &user_b, &100, &String::from_str(&env, "X2"));
+    nft.craftmint(&admin, &user_b, &200, &String::from_str(&env, "Y2"));
     sets.claim_set_bonus(&user_b, &set_id);
 
     // Third player cannot claim (cap exhausted) - tested in test_limited_edition_cap_exhausted
@@ -164,7 +168,7 @@ fn test_limited_edition_and_edition_tokens() {
 #[test]
 fn test_edition_token_trading() {
     let env = Env::default();
-    let (sets, nft, token, admin, _) = setup(&env);
+    let (sets, nft, _token, admin, _) = setup(&env);
 
     let seller = Address::generate(&env);
     let buyer = Address::generate(&env);
@@ -183,8 +187,8 @@ fn test_edition_token_trading() {
         &symbol_short!("tradable"),
     );
 
-    nft.craftmint(&seller, &1, &String::from_str(&env, "M"));
-    nft.craftmint(&seller, &2, &String::from_str(&env, "N"));
+    nft.craftmint(&admin, &seller, &1, &String::from_str(&env, "M"));
+    nft.craftmint(&admin, &seller, &2, &String::from_str(&env, "N"));
     sets.claim_set_bonus(&seller, &set_id);
 
     let tokens = sets.edition_tokens_of(&seller);
@@ -242,10 +246,10 @@ fn test_synergy_bonus() {
         &symbol_short!("syn_ab"),
     );
 
-    nft.craftmint(&user, &1, &String::from_str(&env, "1"));
-    nft.craftmint(&user, &2, &String::from_str(&env, "2"));
-    nft.craftmint(&user, &3, &String::from_str(&env, "3"));
-    nft.craftmint(&user, &4, &String::from_str(&env, "4"));
+    nft.craftmint(&admin, &user, &1, &String::from_str(&env, "1"));
+    nft.craftmint(&admin, &user, &2, &String::from_str(&env, "2"));
+    nft.craftmint(&admin, &user, &3, &String::from_str(&env, "3"));
+    nft.craftmint(&admin, &user, &4, &String::from_str(&env, "4"));
 
     sets.claim_set_bonus(&user, &set1);
     sets.claim_set_bonus(&user, &set2);
@@ -279,23 +283,23 @@ fn test_limited_edition_cap_exhausted() {
         &symbol_short!("cap2"),
     );
 
-    nft.craftmint(&user_a, &100, &String::from_str(&env, "a1"));
-    nft.craftmint(&user_a, &200, &String::from_str(&env, "a2"));
+    nft.craftmint(&admin, &user_a, &100, &String::from_str(&env, "a1"));
+    nft.craftmint(&admin, &user_a, &200, &String::from_str(&env, "a2"));
     sets.claim_set_bonus(&user_a, &set_id);
 
-    nft.craftmint(&user_b, &100, &String::from_str(&env, "b1"));
-    nft.craftmint(&user_b, &200, &String::from_str(&env, "b2"));
+    nft.craftmint(&admin, &user_b, &100, &String::from_str(&env, "b1"));
+    nft.craftmint(&admin, &user_b, &200, &String::from_str(&env, "b2"));
     sets.claim_set_bonus(&user_b, &set_id);
 
-    nft.craftmint(&user_c, &100, &String::from_str(&env, "c1"));
-    nft.craftmint(&user_c, &200, &String::from_str(&env, "c2"));
+    nft.craftmint(&admin, &user_c, &100, &String::from_str(&env, "c1"));
+    nft.craftmint(&admin, &user_c, &200, &String::from_str(&env, "c2"));
     sets.claim_set_bonus(&user_c, &set_id); // panic: cap exhausted
 }
 
 #[test]
 fn test_leaderboards() {
     let env = Env::default();
-    let (sets, nft, token, admin, _) = setup(&env);
+    let (sets, nft, _token, admin, _) = setup(&env);
 
     let mut puzzles = Vec::new(&env);
     puzzles.push_back(1);
@@ -313,16 +317,199 @@ fn test_leaderboards() {
     let a = Address::generate(&env);
     let b = Address::generate(&env);
 
-    nft.craftmint(&a, &1, &String::from_str(&env, "a1"));
-    nft.craftmint(&a, &2, &String::from_str(&env, "a2"));
+    nft.craftmint(&admin, &a, &1, &String::from_str(&env, "a1"));
+    nft.craftmint(&admin, &a, &2, &String::from_str(&env, "a2"));
     sets.claim_set_bonus(&a, &set_id); // 100 + 300 = 400
 
-    nft.craftmint(&b, &1, &String::from_str(&env, "b1"));
-    nft.craftmint(&b, &2, &String::from_str(&env, "b2"));
+    nft.craftmint(&admin, &b, &1, &String::from_str(&env, "b1"));
+    nft.craftmint(&admin, &b, &2, &String::from_str(&env, "b2"));
     sets.claim_set_bonus(&b, &set_id); // 400
 
     let lb = sets.get_set_leaderboard(&set_id, &5);
-    assert!(lb.len() >= 1);
+    assert!(!lb.is_empty());
     let global = sets.get_global_leaderboard(&5);
-    assert!(global.len() >= 1);
+    assert!(!global.is_empty());
+}
+
+fn two_puzzle_set(env: &Env, sets: &AchievementSetsClient, admin: &Address, tier: SetTier) -> u32 {
+    let mut puzzles = Vec::new(env);
+    puzzles.push_back(1);
+    puzzles.push_back(2);
+    sets.create_set(
+        admin,
+        &String::from_str(env, "Rules"),
+        &puzzles,
+        &tier,
+        &100,
+        &None,
+        &symbol_short!("rules"),
+    )
+}
+
+fn mint_at(env: &Env, nft: &AchievementNFTClient, admin: &Address, user: &Address, puzzle_id: u32, timestamp: u64) {
+    env.ledger().set_timestamp(timestamp);
+    nft.craftmint(admin, user, &puzzle_id, &String::from_str(env, "A"));
+}
+
+#[test]
+fn test_claim_window() {
+    let env = Env::default();
+    let (sets, nft, _token, admin, _) = setup(&env);
+    let set_id = two_puzzle_set(&env, &sets, &admin, SetTier::Common);
+    let rules = SetRules {
+        claim_start: 100,
+        claim_end: 200,
+        ..Default::default()
+    };
+    sets.set_set_rules(&admin, &set_id, &rules);
+    assert_eq!(sets.get_set_rules(&set_id), rules);
+
+    let user = Address::generate(&env);
+    mint_at(&env, &nft, &admin, &user, 1, 50);
+    mint_at(&env, &nft, &admin, &user, 2, 60);
+    assert!(sets.try_claim_set_bonus(&user, &set_id).is_err());
+
+    env.ledger().set_timestamp(201);
+    assert!(sets.try_claim_set_bonus(&user, &set_id).is_err());
+
+    env.ledger().set_timestamp(150);
+    assert_eq!(sets.claim_set_bonus(&user, &set_id), 100);
+}
+
+#[test]
+fn test_ordered_set_within_span() {
+    let env = Env::default();
+    let (sets, nft, _token, admin, _) = setup(&env);
+    let set_id = two_puzzle_set(&env, &sets, &admin, SetTier::Common);
+    sets.set_set_rules(
+        &admin,
+        &set_id,
+        &SetRules {
+            ordered: true,
+            max_span_secs: 3600,
+            ..Default::default()
+        },
+    );
+
+    let user = Address::generate(&env);
+    mint_at(&env, &nft, &admin, &user, 1, 1_000);
+    mint_at(&env, &nft, &admin, &user, 2, 2_000);
+    assert_eq!(sets.claim_set_bonus(&user, &set_id), 100);
+}
+
+#[test]
+#[should_panic(expected = "completed out of order")]
+fn test_ordered_set_rejects_wrong_order() {
+    let env = Env::default();
+    let (sets, nft, _token, admin, _) = setup(&env);
+    let set_id = two_puzzle_set(&env, &sets, &admin, SetTier::Common);
+    sets.set_set_rules(
+        &admin,
+        &set_id,
+        &SetRules {
+            ordered: true,
+            ..Default::default()
+        },
+    );
+
+    let user = Address::generate(&env);
+    mint_at(&env, &nft, &admin, &user, 2, 1_000);
+    mint_at(&env, &nft, &admin, &user, 1, 2_000);
+    sets.claim_set_bonus(&user, &set_id);
+}
+
+#[test]
+#[should_panic(expected = "completed too far apart")]
+fn test_span_rule_rejects_slow_completion() {
+    let env = Env::default();
+    let (sets, nft, _token, admin, _) = setup(&env);
+    let set_id = two_puzzle_set(&env, &sets, &admin, SetTier::Common);
+    sets.set_set_rules(
+        &admin,
+        &set_id,
+        &SetRules {
+            max_span_secs: 3600,
+            ..Default::default()
+        },
+    );
+
+    let user = Address::generate(&env);
+    mint_at(&env, &nft, &admin, &user, 1, 1_000);
+    mint_at(&env, &nft, &admin, &user, 2, 1_000 + 7_200);
+    sets.claim_set_bonus(&user, &set_id);
+}
+
+#[test]
+fn test_sequence_rules_consider_every_held_completion() {
+    let env = Env::default();
+    let (sets, nft, _token, admin, _) = setup(&env);
+    let set_id = two_puzzle_set(&env, &sets, &admin, SetTier::Common);
+    sets.set_set_rules(
+        &admin,
+        &set_id,
+        &SetRules {
+            ordered: true,
+            max_span_secs: 3600,
+            ..Default::default()
+        },
+    );
+
+    // The first completions are out of order and too far apart, but the
+    // later pair of puzzle 1 then puzzle 2 satisfies both rules
+    let user = Address::generate(&env);
+    mint_at(&env, &nft, &admin, &user, 2, 1_000);
+    mint_at(&env, &nft, &admin, &user, 1, 5_000);
+    assert!(sets.try_claim_set_bonus(&user, &set_id).is_err());
+    mint_at(&env, &nft, &admin, &user, 1, 20_000);
+    mint_at(&env, &nft, &admin, &user, 2, 21_000);
+    assert_eq!(sets.claim_set_bonus(&user, &set_id), 100);
+}
+
+#[test]
+fn test_tier_requires_skill_rating() {
+    let env = Env::default();
+    let (sets, nft, _token, admin, _) = setup(&env);
+    let set_id = two_puzzle_set(&env, &sets, &admin, SetTier::Epic);
+
+    let rating = RatingClient::new(&env, &env.register_contract(None, SkillRatingContract));
+    rating.initialize(
+        &admin,
+        &1000i32,
+        &32i32,
+        &604_800u64,
+        &5_000u32,
+        &7_776_000u64,
+        &100i32,
+        &10u32,
+        &1_000_000u32,
+    );
+    sets.set_skill_rating(&admin, &Some(rating.address.clone()));
+    sets.set_tier_min_skill(&admin, &SetTier::Epic, &Some(SkillTier::Gold));
+
+    // A fresh player starts at 1000 (Silver)
+    let user = Address::generate(&env);
+    mint_at(&env, &nft, &admin, &user, 1, 10);
+    mint_at(&env, &nft, &admin, &user, 2, 20);
+    assert!(sets.try_claim_set_bonus(&user, &set_id).is_err());
+
+    sets.set_tier_min_skill(&admin, &SetTier::Epic, &Some(SkillTier::Silver));
+    assert_eq!(sets.claim_set_bonus(&user, &set_id), 225);
+}
+
+#[test]
+#[should_panic(expected = "skill tier too low")]
+fn test_unrated_player_fails_skill_tier() {
+    let env = Env::default();
+    let (sets, nft, _token, admin, _) = setup(&env);
+    let set_id = two_puzzle_set(&env, &sets, &admin, SetTier::Epic);
+
+    // The rating contract is not initialized, so it cannot rate anyone
+    let rating = RatingClient::new(&env, &env.register_contract(None, SkillRatingContract));
+    sets.set_skill_rating(&admin, &Some(rating.address.clone()));
+    sets.set_tier_min_skill(&admin, &SetTier::Epic, &Some(SkillTier::Bronze));
+
+    let user = Address::generate(&env);
+    mint_at(&env, &nft, &admin, &user, 1, 10);
+    mint_at(&env, &nft, &admin, &user, 2, 20);
+    sets.claim_set_bonus(&user, &set_id);
 }
@@ -607,6 +607,59 @@ impl AntiBot {
     ) -> Result<(), AntiBotError> {
         player.require_auth();
 
+        Self::log_activity(&env, &player, puzzle_id, solve_time_ms, gas_used, success)
+    }
+
+    /// Entry point for game contracts registered as verifiers.
+    ///
+    /// Refuses blacklisted or penalized players, feeds the solve into the
+    /// player's profile and behavioral pattern, and returns whether the player
+    /// is currently flagged and should be kept off public rankings.
+    pub fn screen_submission(
+        env: Env,
+        caller: Address,
+        player: Address,
+        puzzle_id: u32,
+        solve_time_ms: u64,
+    ) -> Result<bool, AntiBotError> {
+        caller.require_auth();
+        if !Self::is_verifier(env.clone(), caller) {
+            return Err(AntiBotError::Unauthorized);
+        }
+
+        Self::check_penalty_status(env.clone(), player.clone())?;
+        Self::log_activity(&env, &player, puzzle_id, solve_time_ms, 0, true)?;
+
+        Ok(Self::is_flagged(env, player))
+    }
+
+    /// Whether a player is blacklisted, flagged or marked suspicious.
+    pub fn is_flagged(env: Env, player: Address) -> bool {
+        if Self::is_whitelisted(env.clone(), player.clone()) {
+            return false;
+        }
+        if Self::is_blacklisted(env.clone(), player.clone()) {
+            return true;
+        }
+
+        let profile: Option<PlayerProfile> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PlayerProfile(player));
+        match profile {
+            Some(profile) => profile.status == 2 || profile.status == 3,
+            None => false,
+        }
+    }
+
+    fn log_activity(
+        env: &Env,
+        player: &Address,
+        puzzle_id: u32,
+        solve_time_ms: u64,
+        gas_used: u64,
+        success: bool,
+    ) -> Result<(), AntiBotError> {
         let config: Config = env
             .storage()
             .instance()
@@ -616,7 +669,7 @@ impl AntiBot {
         let now = env.ledger().timestamp();
         
         // Get or create profile
-        let mut profile = Self::get_or_create_profile(&env, &player);
+        let mut profile = Self::get_or_create_profile(env, player);
         
         // Update basic stats
         profile.total_attempts += 1;
@@ -634,8 +687,8 @@ impl AntiBot {
             
             if solve_time_ms < config.suspicious_solve_time_ms {
                 Self::record_suspicious_activity(
-                    &env,
-                    &player,
+                    env,
+                    player,
                     ActivityType::TooFastSolve,
                     symbol_short!("fast_slv"),
                     7,
@@ -655,8 +708,8 @@ impl AntiBot {
         // Check for bot patterns
         if profile.consecutive_fast_solves >= config.max_consecutive_fast_solves {
             Self::record_suspicious_activity(
-                &env,
-                &player,
+                env,
+                player,
                 ActivityType::PatternMatch,
                 symbol_short!("bot_patt"),
                 8,
@@ -686,15 +739,18 @@ impl AntiBot {
             .set(&DataKey::ActivityCount(player.clone()), &(count + 1));
 
         // Update behavioral pattern
-        Self::update_behavioral_pattern(&env, &player, &activity)?;
+        Self::update_behavioral_pattern(env, player, &activity)?;
+
+        // Suspicious-activity checks above may have flagged the player in storage
+        profile.status = Self::get_or_create_profile(env, player).status;
 
         // Update trust score based on activity
-        Self::update_trust_score(&env, &mut profile)?;
+        Self::update_trust_score(env, &mut profile)?;
         
-        Self::update_profile(&env, &player, &profile);
+        Self::update_profile(env, player, &profile);
 
         env.events().publish(
-            (symbol_short!("activity"), player),
+            (symbol_short!("activity"), player.clone()),
             (puzzle_id, solve_time_ms, success),
         );
 
@@ -1774,6 +1830,14 @@ impl AntiBot {
         env.storage().instance().set(&DataKey::Config, &config);
         Ok(())
     }
+
+    pub fn is_verifier(env: Env, address: Address) -> bool {
+        let config: Option<Config> = env.storage().instance().get(&DataKey::Config);
+        match config {
+            Some(config) => config.admin == address || config.verifiers.contains(&address),
+            None => false,
+        }
+    }
 }
 
 // ============================================================================
@@ -1006,11 +1006,14 @@ fn test_add_and_remove_verifier() {
 
     let config = client.get_config();
     assert!(config.verifiers.contains(&verifier));
+    assert!(client.is_verifier(&verifier));
+    assert!(client.is_verifier(&admin));
 
     client.remove_verifier(&verifier);
 
     let config = client.get_config();
     assert!(!config.verifiers.contains(&verifier));
+    assert!(!client.is_verifier(&verifier));
 }
 
 // ============================================================================
@@ -1074,3 +1077,62 @@ fn test_legitimate_player_not_blocked() {
     // Should be allowed or have low bot probability
     assert!(result.bot_probability < 50);
 }
+
+#[test]
+fn test_screen_submission_flags_fast_solver() {
+    let (env, admin, player) = setup_env();
+    let contract_id = env.register_contract(None, AntiBot);
+    let client = AntiBotClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    client.initialize(&admin);
+
+    let game = Address::generate(&env);
+    client.add_verifier(&game);
+
+    // A normal solve is recorded and not flagged
+    assert!(!client.screen_submission(&game, &player, &1, &30000));
+    assert_eq!(client.get_profile(&player).unwrap().total_attempts, 1);
+    assert!(client.get_behavioral_pattern(&player).is_some());
+
+    // Repeated impossible solves flag the player
+    for i in 0..3u64 {
+        env.ledger().set_timestamp(2000 + i * 10);
+        client.screen_submission(&game, &player, &1, &500);
+    }
+    assert!(client.is_flagged(&player));
+    assert_eq!(client.get_profile(&player).unwrap().status, 3);
+}
+
+#[test]
+fn test_screen_submission_requires_verifier() {
+    let (env, admin, player) = setup_env();
+    let contract_id = env.register_contract(None, AntiBot);
+    let client = AntiBotClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    let stranger = Address::generate(&env);
+    let result = client.try_screen_submission(&stranger, &player, &1, &30000);
+    assert_eq!(result, Err(Ok(AntiBotError::Unauthorized)));
+}
+
+#[test]
+fn test_screen_submission_refuses_penalized_player() {
+    let (env, admin, player) = setup_env();
+    let contract_id = env.register_contract(None, AntiBot);
+    let client = AntiBotClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    client.initialize(&admin);
+
+    let game = Address::generate(&env);
+    client.add_verifier(&game);
+    client.apply_penalty(&player, &PenaltyType::TemporaryBan, &symbol_short!("bot"), &5);
+
+    let result = client.try_screen_submission(&game, &player, &1, &30000);
+    assert_eq!(result, Err(Ok(AntiBotError::PenaltyActive)));
+}
@@ -2,8 +2,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short,
-    Address, Env, Map, String, Symbol, Vec,
+    contract, contractclient, contractimpl, contracttype, symbol_short,
+    Address, BytesN, Env, String, Symbol, Vec,
     log, panic_with_error,
 };
 
@@ -12,6 +12,45 @@ use soroban_sdk::{
 const ADMIN_KEY: Symbol          = symbol_short!("ADMIN");
 const TOKEN_COUNT_KEY: Symbol    = symbol_short!("TOK_CNT");
 const PAUSED_KEY: Symbol         = symbol_short!("PAUSED");
+const VERIFIER_KEY: Symbol       = symbol_short!("VERIFIER");
+const TIME_ATTACK_KEY: Symbol    = symbol_short!("TIME_ATK");
+
+/// Recorded as `completion_time_secs` when no source timed the solve.
+pub const UNTIMED: u64 = u64::MAX;
+
+// ─── Completion Sources ──────────────────────────────────────────────────────
+
+/// Mirror of `puzzle_verification::CompletionRecord`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CompletionRecord {
+    pub solution_hash: BytesN<32>,
+    pub completed_at: u64,
+    pub elapsed_secs: Option<u64>,
+    pub rank: u32,
+}
+
+/// Subset of `puzzle_verification::PuzzleVerification` read by this contract.
+#[contractclient(name = "PuzzleVerificationClient")]
+pub trait PuzzleVerificationInterface {
+    fn get_completion(env: Env, player: Address, puzzle_id: u32) -> Option<CompletionRecord>;
+}
+
+/// Mirror of `time_attack::TimeRecord`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TimeRecord {
+    pub player: Address,
+    pub completion_time_ms: u64,
+    pub timestamp: u64,
+    pub replay_hash: BytesN<32>,
+}
+
+/// Subset of `time_attack::TimeAttackContract` read by this contract.
+#[contractclient(name = "TimeAttackClient")]
+pub trait TimeAttackInterface {
+    fn get_personal_best(env: Env, player: Address, puzzle_id: u32) -> Option<TimeRecord>;
+}
 
 // ─── Error Codes ─────────────────────────────────────────────────────────────
 
@@ -27,6 +66,11 @@ pub enum CertError {
     ContractPaused    = 6,
     InvalidInput      = 7,
     Unauthorized      = 8,
+    NotCompleted      = 9,
+    SourceNotSet      = 10,
+    VerificationRequired = 11,
+    ReissueNotFound   = 12,
+    ReissuePending    = 13,
 }
 
 impl From<CertError> for soroban_sdk::Error {
@@ -74,6 +118,29 @@ impl RarityTier {
     }
 }
 
+// ─── Transfer Policy ─────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransferPolicy {
+    /// Bound to the owner's wallet; only an approved reissue can move it.
+    Soulbound,
+    /// Moves freely between wallets.
+    Transferable,
+    /// Moves freely; every owner, starting with the solver, is kept in `get_provenance`.
+    Provenance,
+}
+
+impl TransferPolicy {
+    pub fn from_transferable(transferable: bool) -> Self {
+        if transferable {
+            TransferPolicy::Transferable
+        } else {
+            TransferPolicy::Soulbound
+        }
+    }
+}
+
 // ─── Certificate Metadata ────────────────────────────────────────────────────
 
 #[contracttype]
@@ -81,8 +148,10 @@ impl RarityTier {
 pub struct CertificateMetadata {
     /// Unique token id (auto-incremented).
     pub token_id: u64,
-    /// Wallet address of the puzzle solver.
+    /// Wallet that currently holds the certificate.
     pub owner: Address,
+    /// Wallet that solved the puzzle; kept across transfers and reissues.
+    pub solver: Address,
     /// Unique puzzle identifier.
     pub puzzle_id: String,
     /// Puzzle title for display purposes.
@@ -99,10 +168,14 @@ pub struct CertificateMetadata {
     pub solution_hash: String,
     /// Human-readable display URI (off-chain metadata JSON, IPFS encouraged).
     pub metadata_uri: String,
-    /// Whether this certificate can be transferred.
+    /// Whether this certificate can be transferred (`policy` is not `Soulbound`).
     pub transferable: bool,
+    /// How the certificate may change hands.
+    pub policy: TransferPolicy,
     /// Whether this certificate has been burned.
     pub burned: bool,
+    /// Whether time, rank and solution hash were read from on-chain sources.
+    pub verified: bool,
 }
 
 // ─── Verification Proof ──────────────────────────────────────────────────────
@@ -112,12 +185,34 @@ pub struct CertificateMetadata {
 pub struct VerificationProof {
     pub token_id: u64,
     pub owner: Address,
+    pub solver: Address,
+    pub policy: TransferPolicy,
     pub puzzle_id: String,
     pub solution_hash: String,
     pub rarity: RarityTier,
     pub completed_at: u64,
     pub rank: u64,
     pub authentic: bool,
+    /// The completion record the certificate was minted from still exists.
+    pub verified: bool,
+}
+
+/// A pending request to move a certificate to a recovery wallet.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReissueRequest {
+    pub new_owner: Address,
+    pub requested_at: u64,
+    /// Endorsed by the current owner rather than only by the recovery wallet
+    pub owner_signed: bool,
+}
+
+/// Where a verified certificate's completion can be re-checked.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CompletionSource {
+    pub puzzle_id: u32,
+    pub solver: Address,
 }
 
 // ─── Storage Helpers ─────────────────────────────────────────────────────────
@@ -134,6 +229,49 @@ fn puzzle_minted_key(puzzle_id: &String, owner: &Address) -> (Symbol, String, Ad
     (symbol_short!("P_MINTED"), puzzle_id.clone(), owner.clone())
 }
 
+fn cert_source_key(token_id: u64) -> (Symbol, u64) {
+    (symbol_short!("CERT_SRC"), token_id)
+}
+
+fn provenance_key(token_id: u64) -> (Symbol, u64) {
+    (symbol_short!("PROV"), token_id)
+}
+
+fn puzzle_policy_key(puzzle_id: &String) -> (Symbol, String) {
+    (symbol_short!("P_POLICY"), puzzle_id.clone())
+}
+
+/// One slot per recovery wallet, so no outside wallet can block the others.
+fn reissue_key(token_id: u64, new_owner: &Address) -> (Symbol, u64, Address) {
+    (symbol_short!("REISSUE"), token_id, new_owner.clone())
+}
+
+/// Decimal form of an on-chain puzzle id, used as the certificate `puzzle_id`.
+fn decimal_string(env: &Env, mut value: u32) -> String {
+    let mut buf = [0u8; 10];
+    let mut start = buf.len();
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    String::from_bytes(env, &buf[start..])
+}
+
+/// Lowercase hex form of a solution hash.
+fn hex_string(env: &Env, bytes: &BytesN<32>) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut buf = [0u8; 64];
+    for (i, byte) in bytes.to_array().iter().enumerate() {
+        buf[2 * i] = HEX[(byte >> 4) as usize];
+        buf[2 * i + 1] = HEX[(byte & 0x0f) as usize];
+    }
+    String::from_bytes(env, &buf)
+}
+
 // ─── Contract ────────────────────────────────────────────────────────────────
 
 #[contract]
@@ -167,8 +305,83 @@ impl CompletionCertificateContract {
         env.storage().instance().set(&PAUSED_KEY, &paused);
     }
 
+    /// Link the `puzzle_verification` and `time_attack` contracts completions
+    /// are read from. Once a verification source is set, certificates can only
+    /// be minted through `mint_verified_certificate`.
+    pub fn set_completion_sources(
+        env: Env,
+        verification: Option<Address>,
+        time_attack: Option<Address>,
+    ) {
+        Self::require_admin(&env);
+        match verification {
+            Some(addr) => env.storage().instance().set(&VERIFIER_KEY, &addr),
+            None => env.storage().instance().remove(&VERIFIER_KEY),
+        }
+        match time_attack {
+            Some(addr) => env.storage().instance().set(&TIME_ATTACK_KEY, &addr),
+            None => env.storage().instance().remove(&TIME_ATTACK_KEY),
+        }
+    }
+
+    pub fn get_verification_source(env: Env) -> Option<Address> {
+        env.storage().instance().get::<Symbol, Address>(&VERIFIER_KEY)
+    }
+
+    pub fn get_time_attack_source(env: Env) -> Option<Address> {
+        env.storage().instance().get::<Symbol, Address>(&TIME_ATTACK_KEY)
+    }
+
+    // ── Transfer Policy ───────────────────────────────────────────────────
+
+    /// Policy applied to every certificate minted for `puzzle_id`, overriding
+    /// the `transferable` flag passed at mint. `None` clears it.
+    pub fn set_puzzle_policy(env: Env, puzzle_id: String, policy: Option<TransferPolicy>) {
+        Self::require_admin(&env);
+        let key = puzzle_policy_key(&puzzle_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_puzzle_policy(env: Env, puzzle_id: String) -> Option<TransferPolicy> {
+        env.storage()
+            .persistent()
+            .get::<(Symbol, String), TransferPolicy>(&puzzle_policy_key(&puzzle_id))
+    }
+
+    /// Change the policy of a single certificate. Switching to `Provenance`
+    /// starts the history at the current owner.
+    pub fn set_certificate_policy(env: Env, token_id: u64, policy: TransferPolicy) {
+        Self::require_admin(&env);
+        let mut cert = Self::live_certificate(&env, token_id);
+        if policy == TransferPolicy::Provenance
+            && !env.storage().persistent().has(&provenance_key(token_id))
+        {
+            env.storage()
+                .persistent()
+                .set(&provenance_key(token_id), &Vec::from_array(&env, [cert.owner.clone()]));
+        }
+        cert.transferable = policy != TransferPolicy::Soulbound;
+        cert.policy = policy;
+        env.storage().persistent().set(&cert_key(token_id), &cert);
+    }
+
+    /// Owners of a `Provenance` certificate in order, starting with the solver.
+    /// Empty for other policies.
+    pub fn get_provenance(env: Env, token_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get::<(Symbol, u64), Vec<Address>>(&provenance_key(token_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
     // ── Minting ───────────────────────────────────────────────────────────
 
+    /// Mint from caller-supplied completion data (admin only). Disabled once a
+    /// verification source is linked.
+    #[allow(clippy::too_many_arguments)]
     pub fn mint_certificate(
         env: Env,
         owner: Address,
@@ -182,7 +395,94 @@ impl CompletionCertificateContract {
     ) -> u64 {
         Self::require_not_paused(&env);
         Self::require_admin(&env);
+        if env.storage().instance().has(&VERIFIER_KEY) {
+            panic_with_error!(&env, CertError::VerificationRequired);
+        }
+
+        let completed_at = env.ledger().timestamp();
+        Self::mint_internal(
+            env,
+            owner,
+            puzzle_id,
+            puzzle_title,
+            completed_at,
+            completion_time_secs,
+            rank,
+            solution_hash,
+            metadata_uri,
+            transferable,
+            false,
+        )
+    }
 
+    /// Mint a certificate for a puzzle `owner` solved in `puzzle_verification`.
+    /// The solution hash, `completed_at` and solver rank always come from the
+    /// completion record. When a `time_attack` source holds a personal best
+    /// for the puzzle, only its time is used; otherwise the time is the elapsed
+    /// time since `start_attempt`, or `UNTIMED` if the clock was never started.
+    pub fn mint_verified_certificate(
+        env: Env,
+        owner: Address,
+        puzzle_id: u32,
+        puzzle_title: String,
+        metadata_uri: String,
+        transferable: bool,
+    ) -> u64 {
+        Self::require_not_paused(&env);
+        owner.require_auth();
+
+        let verification: Address = env
+            .storage()
+            .instance()
+            .get::<Symbol, Address>(&VERIFIER_KEY)
+            .unwrap_or_else(|| panic_with_error!(&env, CertError::SourceNotSet));
+        let record = PuzzleVerificationClient::new(&env, &verification)
+            .get_completion(&owner, &puzzle_id)
+            .unwrap_or_else(|| panic_with_error!(&env, CertError::NotCompleted));
+
+        let mut completion_time_secs = record.elapsed_secs.unwrap_or(UNTIMED);
+        if let Some(time_attack) = Self::get_time_attack_source(env.clone()) {
+            if let Some(best) =
+                TimeAttackClient::new(&env, &time_attack).get_personal_best(&owner, &puzzle_id)
+            {
+                completion_time_secs = best.completion_time_ms.div_ceil(1000);
+            }
+        }
+
+        let token_id = Self::mint_internal(
+            env.clone(),
+            owner.clone(),
+            decimal_string(&env, puzzle_id),
+            puzzle_title,
+            record.completed_at,
+            completion_time_secs,
+            record.rank as u64,
+            hex_string(&env, &record.solution_hash),
+            metadata_uri,
+            transferable,
+            true,
+        );
+        env.storage().persistent().set(
+            &cert_source_key(token_id),
+            &CompletionSource { puzzle_id, solver: owner },
+        );
+        token_id
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn mint_internal(
+        env: Env,
+        owner: Address,
+        puzzle_id: String,
+        puzzle_title: String,
+        completed_at: u64,
+        completion_time_secs: u64,
+        rank: u64,
+        solution_hash: String,
+        metadata_uri: String,
+        transferable: bool,
+        verified: bool,
+    ) -> u64 {
         let mint_key = puzzle_minted_key(&puzzle_id, &owner);
         if env.storage().persistent().has(&mint_key) {
             panic_with_error!(&env, CertError::AlreadyMinted);
@@ -198,11 +498,13 @@ impl CompletionCertificateContract {
             + 1;
         env.storage().instance().set(&TOKEN_COUNT_KEY, &token_id);
 
-        let completed_at = env.ledger().timestamp();
+        let policy = Self::get_puzzle_policy(env.clone(), puzzle_id.clone())
+            .unwrap_or(TransferPolicy::from_transferable(transferable));
 
         let cert = CertificateMetadata {
             token_id,
             owner: owner.clone(),
+            solver: owner.clone(),
             puzzle_id: puzzle_id.clone(),
             puzzle_title,
             completed_at,
@@ -211,24 +513,23 @@ impl CompletionCertificateContract {
             rarity: rarity.clone(),
             solution_hash,
             metadata_uri,
-            transferable,
+            transferable: policy != TransferPolicy::Soulbound,
+            policy: policy.clone(),
             burned: false,
+            verified,
         };
 
         // Persist certificate.
         env.storage().persistent().set(&cert_key(token_id), &cert);
         // Mark as minted for this (puzzle, owner) pair.
         env.storage().persistent().set(&mint_key, &token_id);
+        if policy == TransferPolicy::Provenance {
+            env.storage()
+                .persistent()
+                .set(&provenance_key(token_id), &Vec::from_array(&env, [owner.clone()]));
+        }
 
-        // Append to owner's list.
-        let own_key = owner_certs_key(&owner);
-        let mut owner_list: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get::<(Symbol, Address), Vec<u64>>(&own_key)
-            .unwrap_or(Vec::new(&env));
-        owner_list.push_back(token_id);
-        env.storage().persistent().set(&own_key, &owner_list);
+        Self::add_owned(&env, &owner, token_id);
 
         log!(
             &env,
@@ -266,31 +567,17 @@ impl CompletionCertificateContract {
         if cert.owner != from {
             panic_with_error!(&env, CertError::NotOwner);
         }
-        if !cert.transferable {
+        if cert.policy == TransferPolicy::Soulbound {
             panic_with_error!(&env, CertError::TransferRestricted);
         }
 
-        // Remove from old owner's list.
-        let old_key = owner_certs_key(&from);
-        let mut old_list: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get::<(Symbol, Address), Vec<u64>>(&old_key)
-            .unwrap_or(Vec::new(&env));
-        if let Some(idx) = old_list.iter().position(|id| id == token_id) {
-            old_list.remove(idx as u32);
+        Self::remove_owned(&env, &from, token_id);
+        Self::add_owned(&env, &to, token_id);
+        if cert.policy == TransferPolicy::Provenance {
+            let mut provenance = Self::get_provenance(env.clone(), token_id);
+            provenance.push_back(to.clone());
+            env.storage().persistent().set(&provenance_key(token_id), &provenance);
         }
-        env.storage().persistent().set(&old_key, &old_list);
-
-        // Add to new owner's list.
-        let new_key = owner_certs_key(&to);
-        let mut new_list: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get::<(Symbol, Address), Vec<u64>>(&new_key)
-            .unwrap_or(Vec::new(&env));
-        new_list.push_back(token_id);
-        env.storage().persistent().set(&new_key, &new_list);
 
         cert.owner = to.clone();
         env.storage().persistent().set(&key, &cert);
@@ -323,22 +610,147 @@ impl CompletionCertificateContract {
         cert.burned = true;
         env.storage().persistent().set(&key, &cert);
 
-        // Remove from owner list.
-        let own_key = owner_certs_key(&owner);
-        let mut list: Vec<u64> = env
+        Self::remove_owned(&env, &owner, token_id);
+
+        env.events().publish(
+            (symbol_short!("burn"), symbol_short!("cert")),
+            (token_id, owner),
+        );
+    }
+
+    // ── Recovery ──────────────────────────────────────────────────────────
+
+    /// Ask to move a certificate to `new_owner`, e.g. after losing access to
+    /// the owning wallet. Signed by the recovery wallet; nothing moves until
+    /// the admin calls `approve_reissue`. Each recovery wallet has its own
+    /// request slot.
+    pub fn request_reissue(env: Env, token_id: u64, new_owner: Address) {
+        Self::require_not_paused(&env);
+        new_owner.require_auth();
+        Self::live_certificate(&env, token_id);
+
+        let key = reissue_key(token_id, &new_owner);
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(&env, CertError::ReissuePending);
+        }
+        Self::store_reissue_request(&env, token_id, new_owner, false);
+    }
+
+    /// Owner-signed request to move a certificate to `new_owner`, e.g. a
+    /// soulbound certificate moving to the owner's new wallet. Replaces any
+    /// request already pending for that wallet.
+    pub fn request_owner_reissue(env: Env, owner: Address, token_id: u64, new_owner: Address) {
+        Self::require_not_paused(&env);
+        owner.require_auth();
+        let cert = Self::live_certificate(&env, token_id);
+        if cert.owner != owner {
+            panic_with_error!(&env, CertError::NotOwner);
+        }
+        if new_owner == owner {
+            panic_with_error!(&env, CertError::InvalidInput);
+        }
+        Self::store_reissue_request(&env, token_id, new_owner, true);
+    }
+
+    /// Current owner: withdraw a pending reissue request to `new_owner`.
+    pub fn cancel_reissue(env: Env, owner: Address, token_id: u64, new_owner: Address) {
+        owner.require_auth();
+        let cert = Self::live_certificate(&env, token_id);
+        if cert.owner != owner {
+            panic_with_error!(&env, CertError::NotOwner);
+        }
+        Self::remove_reissue_request(&env, token_id, &new_owner);
+
+        env.events().publish(
+            (symbol_short!("reissue"), symbol_short!("cancel")),
+            (token_id, new_owner),
+        );
+    }
+
+    pub fn get_reissue_request(env: Env, token_id: u64, new_owner: Address) -> Option<ReissueRequest> {
+        env.storage()
+            .persistent()
+            .get::<(Symbol, u64, Address), ReissueRequest>(&reissue_key(token_id, &new_owner))
+    }
+
+    /// Revoke the certificate and mint a copy to `new_owner` (admin only),
+    /// settling that wallet's pending request. The copy keeps the solver,
+    /// completion data, policy and provenance of the original. Returns the
+    /// new token id.
+    pub fn approve_reissue(env: Env, token_id: u64, new_owner: Address) -> u64 {
+        Self::require_admin(&env);
+        let request = Self::get_reissue_request(env.clone(), token_id, new_owner.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, CertError::ReissueNotFound));
+        env.storage().persistent().remove(&reissue_key(token_id, &new_owner));
+
+        let mut old = Self::live_certificate(&env, token_id);
+        let mint_key = puzzle_minted_key(&old.puzzle_id, &request.new_owner);
+        if env.storage().persistent().has(&mint_key) {
+            panic_with_error!(&env, CertError::AlreadyMinted);
+        }
+
+        old.burned = true;
+        env.storage().persistent().set(&cert_key(token_id), &old);
+        Self::remove_owned(&env, &old.owner, token_id);
+
+        let new_id = Self::total_supply(env.clone()) + 1;
+        env.storage().instance().set(&TOKEN_COUNT_KEY, &new_id);
+        let mut cert = old.clone();
+        cert.token_id = new_id;
+        cert.owner = request.new_owner.clone();
+        cert.burned = false;
+        env.storage().persistent().set(&cert_key(new_id), &cert);
+        env.storage().persistent().set(&mint_key, &new_id);
+        Self::add_owned(&env, &cert.owner, new_id);
+
+        if let Some(source) = env
             .storage()
             .persistent()
-            .get::<(Symbol, Address), Vec<u64>>(&own_key)
-            .unwrap_or(Vec::new(&env));
-        if let Some(idx) = list.iter().position(|id| id == token_id) {
-            list.remove(idx as u32);
+            .get::<(Symbol, u64), CompletionSource>(&cert_source_key(token_id))
+        {
+            env.storage().persistent().set(&cert_source_key(new_id), &source);
+        }
+        if cert.policy == TransferPolicy::Provenance {
+            let mut provenance = Self::get_provenance(env.clone(), token_id);
+            provenance.push_back(cert.owner.clone());
+            env.storage().persistent().set(&provenance_key(new_id), &provenance);
         }
-        env.storage().persistent().set(&own_key, &list);
 
         env.events().publish(
-            (symbol_short!("burn"), symbol_short!("cert")),
-            (token_id, owner),
+            (symbol_short!("reissue"), symbol_short!("cert")),
+            (token_id, new_id, request.new_owner),
         );
+        new_id
+    }
+
+    /// Drop a pending reissue request (admin only).
+    pub fn reject_reissue(env: Env, token_id: u64, new_owner: Address) {
+        Self::require_admin(&env);
+        Self::remove_reissue_request(&env, token_id, &new_owner);
+    }
+
+    fn store_reissue_request(env: &Env, token_id: u64, new_owner: Address, owner_signed: bool) {
+        let request = ReissueRequest {
+            new_owner: new_owner.clone(),
+            requested_at: env.ledger().timestamp(),
+            owner_signed,
+        };
+        env.storage()
+            .persistent()
+            .set(&reissue_key(token_id, &new_owner), &request);
+
+        env.events().publish(
+            (symbol_short!("reissue"), symbol_short!("request")),
+            (token_id, new_owner, owner_signed),
+        );
+    }
+
+    fn remove_reissue_request(env: &Env, token_id: u64, new_owner: &Address) {
+        let key = reissue_key(token_id, new_owner);
+        if !env.storage().persistent().has(&key) {
+            panic_with_error!(env, CertError::ReissueNotFound);
+        }
+        env.storage().persistent().remove(&key);
     }
 
     // ── Verification ──────────────────────────────────────────────────────
@@ -350,28 +762,39 @@ impl CompletionCertificateContract {
             .persistent()
             .get::<(Symbol, u64), CertificateMetadata>(&key)
         {
-            Some(cert) if !cert.burned => VerificationProof {
-                token_id:      cert.token_id,
-                owner:         cert.owner,
-                puzzle_id:     cert.puzzle_id,
-                solution_hash: cert.solution_hash,
-                rarity:        cert.rarity,
-                completed_at:  cert.completed_at,
-                rank:          cert.rank,
-                authentic:     true,
-            },
+            Some(cert) if !cert.burned => {
+                // Only verified certificates are authentic, and only while
+                // the completion they were minted from still matches them.
+                let verified = cert.verified && Self::source_confirms(&env, &cert);
+                VerificationProof {
+                    token_id:      cert.token_id,
+                    owner:         cert.owner,
+                    solver:        cert.solver,
+                    policy:        cert.policy,
+                    puzzle_id:     cert.puzzle_id,
+                    solution_hash: cert.solution_hash,
+                    rarity:        cert.rarity,
+                    completed_at:  cert.completed_at,
+                    rank:          cert.rank,
+                    authentic:     verified,
+                    verified,
+                }
+            }
             _ => {
 
                 let contract_addr = env.current_contract_address();
                 VerificationProof {
                     token_id,
-                    owner:         contract_addr,
+                    owner:         contract_addr.clone(),
+                    solver:        contract_addr,
+                    policy:        TransferPolicy::Soulbound,
                     puzzle_id:     String::from_str(&env, ""),
                     solution_hash: String::from_str(&env, ""),
                     rarity:        RarityTier::Common,
                     completed_at:  0,
                     rank:          0,
                     authentic:     false,
+                    verified:      false,
                 }
             }
         }
@@ -478,6 +901,62 @@ impl CompletionCertificateContract {
         admin.require_auth();
     }
 
+    fn live_certificate(env: &Env, token_id: u64) -> CertificateMetadata {
+        match env
+            .storage()
+            .persistent()
+            .get::<(Symbol, u64), CertificateMetadata>(&cert_key(token_id))
+        {
+            Some(cert) if !cert.burned => cert,
+            _ => panic_with_error!(env, CertError::CertNotFound),
+        }
+    }
+
+    fn add_owned(env: &Env, owner: &Address, token_id: u64) {
+        let key = owner_certs_key(owner);
+        let mut list: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get::<(Symbol, Address), Vec<u64>>(&key)
+            .unwrap_or(Vec::new(env));
+        list.push_back(token_id);
+        env.storage().persistent().set(&key, &list);
+    }
+
+    fn remove_owned(env: &Env, owner: &Address, token_id: u64) {
+        let key = owner_certs_key(owner);
+        let mut list: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get::<(Symbol, Address), Vec<u64>>(&key)
+            .unwrap_or(Vec::new(env));
+        if let Some(idx) = list.iter().position(|id| id == token_id) {
+            list.remove(idx as u32);
+        }
+        env.storage().persistent().set(&key, &list);
+    }
+
+    fn source_confirms(env: &Env, cert: &CertificateMetadata) -> bool {
+        let source = match env
+            .storage()
+            .persistent()
+            .get::<(Symbol, u64), CompletionSource>(&cert_source_key(cert.token_id))
+        {
+            Some(source) => source,
+            None => return false,
+        };
+        let record = match Self::get_verification_source(env.clone()) {
+            Some(verification) => PuzzleVerificationClient::new(env, &verification)
+                .get_completion(&source.solver, &source.puzzle_id),
+            None => None,
+        };
+        record.is_some_and(|record| {
+            hex_string(env, &record.solution_hash) == cert.solution_hash
+                && record.completed_at == cert.completed_at
+                && record.rank as u64 == cert.rank
+        })
+    }
+
     fn require_not_paused(env: &Env) {
         let paused: bool = env
             .storage()
@@ -496,9 +975,11 @@ impl CompletionCertificateContract {
 mod tests {
     use super::*;
     use soroban_sdk::{
-        testutils::{Address as _, Ledger, LedgerInfo},
-        Env, String,
+        testutils::{Address as _, Ledger},
+        Bytes, Env, String,
     };
+    use puzzle_verification::{PuzzleVerification, PuzzleVerificationClient as VerificationClient};
+    use time_attack::{TimeAttack, TimeAttackClient as TimeClient};
 
     /// Helper: deploy and initialise the contract, return (env, contract_id, admin).
     fn setup() -> (Env, Address, Address) {
@@ -512,6 +993,54 @@ mod tests {
         (env, contract_id, admin)
     }
 
+    /// Helper: link a `puzzle_verification` with puzzle 7 (solution `[9; 4]`)
+    /// and optionally a `time_attack` contract as completion sources.
+    fn setup_sources<'a>(
+        env: &'a Env,
+        client: &CompletionCertificateContractClient,
+        admin: &Address,
+        with_time_attack: bool,
+    ) -> (VerificationClient<'a>, Option<TimeClient<'a>>) {
+        env.ledger().set_timestamp(1_000);
+        let verification = VerificationClient::new(env, &env.register_contract(None, PuzzleVerification));
+        verification.initialize(admin);
+        let solution = Bytes::from_array(env, &[9u8; 4]);
+        verification.set_puzzle(&7, &env.crypto().sha256(&solution).to_bytes(), &0, &100_000, &1, &5);
+
+        let time_attack = if with_time_attack {
+            let time_attack = TimeClient::new(env, &env.register_contract(None, TimeAttack));
+            time_attack.initialize(admin);
+            Some(time_attack)
+        } else {
+            None
+        };
+        client.set_completion_sources(
+            &Some(verification.address.clone()),
+            &time_attack.as_ref().map(|t| t.address.clone()),
+        );
+        (verification, time_attack)
+    }
+
+    /// Helper: commit and reveal the correct solution for puzzle 7.
+    fn solve(env: &Env, verification: &VerificationClient, player: &Address) {
+        commit(env, verification, player);
+        env.ledger().with_mut(|li| li.timestamp += verification.get_reveal_delay());
+        reveal(env, verification, player);
+    }
+
+    fn commit(env: &Env, verification: &VerificationClient, player: &Address) {
+        let solution = Bytes::from_array(env, &[9u8; 4]);
+        let salt = BytesN::from_array(env, &[1u8; 32]);
+        let commitment = verification.compute_commitment(player, &7, &solution, &salt);
+        verification.commit_solution(player, &7, &commitment);
+    }
+
+    fn reveal(env: &Env, verification: &VerificationClient, player: &Address) {
+        let solution = Bytes::from_array(env, &[9u8; 4]);
+        let salt = BytesN::from_array(env, &[1u8; 32]);
+        assert!(verification.verify_solution(player, &7, &solution, &salt));
+    }
+
     // ── Rarity Tier Tests ─────────────────────────────────────────────────
 
     #[test]
@@ -810,8 +1339,10 @@ mod tests {
             &true,
         );
 
+        // Manually minted certificates have no on-chain completion behind them
         let proof = client.verify_certificate(&token_id);
-        assert!(proof.authentic);
+        assert!(!proof.authentic);
+        assert!(!proof.verified);
         assert_eq!(proof.owner,         owner);
         assert_eq!(proof.puzzle_id,     p_id);
         assert_eq!(proof.solution_hash, sol_hash);
@@ -945,4 +1476,306 @@ mod tests {
 
         assert_eq!(client.get_owner_certificates(&owner).len(), 3);
     }
-}
\ No newline at end of file
+
+    // ── On-chain Verification Tests ───────────────────────────────────────
+
+    #[test]
+    fn test_mint_verified_from_completion_record() {
+        let (env, contract_id, admin) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let (verification, _) = setup_sources(&env, &client, &admin, false);
+
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        // The second player commits before the first solve and reveals later
+        verification.start_attempt(&second, &7);
+        commit(&env, &verification, &second);
+        solve(&env, &verification, &first);
+        let first_completed_at = env.ledger().timestamp();
+        env.ledger().with_mut(|li| li.timestamp += 200);
+        reveal(&env, &verification, &second);
+
+        let untimed = client.mint_verified_certificate(
+            &first, &7,
+            &String::from_str(&env, "Seven"),
+            &String::from_str(&env, "uri"),
+            &true,
+        );
+        let cert = client.get_certificate(&untimed);
+        assert!(cert.verified);
+        assert_eq!(cert.puzzle_id, String::from_str(&env, "7"));
+        assert_eq!(cert.rank, 1);
+        // The solve time, not the mint time
+        assert_eq!(cert.completed_at, first_completed_at);
+        assert_eq!(cert.completion_time_secs, UNTIMED);
+        assert_eq!(cert.rarity, RarityTier::Common);
+
+        let timed = client.mint_verified_certificate(
+            &second, &7,
+            &String::from_str(&env, "Seven"),
+            &String::from_str(&env, "uri"),
+            &true,
+        );
+        let proof = client.verify_certificate(&timed);
+        assert!(proof.authentic);
+        assert!(proof.verified);
+        assert_eq!(proof.rank, 2);
+        // 200 s of play plus the 10 s reveal delay
+        assert_eq!(client.get_certificate(&timed).completion_time_secs, 210);
+        assert_eq!(proof.rarity, RarityTier::Epic);
+        // sha256([9; 4]) as lowercase hex
+        assert_eq!(
+            proof.solution_hash,
+            String::from_str(&env, "8493100b11a2fe625bcf97fc313f83b580ba4fd2c016221009db93bfe184ee45")
+        );
+    }
+
+    #[test]
+    fn test_mint_verified_uses_time_attack_best() {
+        let (env, contract_id, admin) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let (verification, time_attack) = setup_sources(&env, &client, &admin, true);
+        let time_attack = time_attack.unwrap();
+
+        let fast = Address::generate(&env);
+        let player = Address::generate(&env);
+        solve(&env, &verification, &player);
+        time_attack.submit_time(&fast, &7, &30_000, &BytesN::from_array(&env, &[1u8; 32]));
+        time_attack.submit_time(&player, &7, &45_500, &BytesN::from_array(&env, &[2u8; 32]));
+
+        let token_id = client.mint_verified_certificate(
+            &player, &7,
+            &String::from_str(&env, "Seven"),
+            &String::from_str(&env, "uri"),
+            &false,
+        );
+        let cert = client.get_certificate(&token_id);
+        // Rounded up to whole seconds; rank stays the solver order
+        assert_eq!(cert.completion_time_secs, 46);
+        assert_eq!(cert.rank, 1);
+        assert_eq!(cert.rarity, RarityTier::Legendary);
+    }
+
+    #[test]
+    fn test_mint_verified_without_completion_rejected() {
+        let (env, contract_id, admin) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        setup_sources(&env, &client, &admin, false);
+
+        let result = client.try_mint_verified_certificate(
+            &Address::generate(&env), &7,
+            &String::from_str(&env, "Seven"),
+            &String::from_str(&env, "uri"),
+            &true,
+        );
+        assert_eq!(result, Err(Ok(CertError::NotCompleted.into())));
+    }
+
+    #[test]
+    fn test_manual_mint_disabled_with_verification_source() {
+        let (env, contract_id, admin) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        setup_sources(&env, &client, &admin, false);
+
+        let result = client.try_mint_certificate(
+            &Address::generate(&env),
+            &String::from_str(&env, "7"),
+            &String::from_str(&env, "Seven"),
+            &1u64, &1u64,
+            &String::from_str(&env, "h"),
+            &String::from_str(&env, "u"),
+            &true,
+        );
+        assert_eq!(result, Err(Ok(CertError::VerificationRequired.into())));
+    }
+
+    #[test]
+    fn test_verified_cert_inauthentic_when_source_unlinked() {
+        let (env, contract_id, admin) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let (verification, _) = setup_sources(&env, &client, &admin, false);
+
+        let owner = Address::generate(&env);
+        solve(&env, &verification, &owner);
+        let token_id = client.mint_verified_certificate(
+            &owner, &7,
+            &String::from_str(&env, "Seven"),
+            &String::from_str(&env, "uri"),
+            &true,
+        );
+
+        client.set_completion_sources(&None, &None);
+        let proof = client.verify_certificate(&token_id);
+        assert!(!proof.verified);
+        assert!(!proof.authentic);
+    }
+
+    // ── Transfer Policy & Reissue Tests ───────────────────────────────────
+
+    fn mint_simple(env: &Env, client: &CompletionCertificateContractClient, owner: &Address, p_id: &str, transferable: bool) -> u64 {
+        client.mint_certificate(
+            owner,
+            &String::from_str(env, p_id),
+            &String::from_str(env, "Policy"),
+            &50u64, &1u64,
+            &String::from_str(env, "h"),
+            &String::from_str(env, "u"),
+            &transferable,
+        )
+    }
+
+    #[test]
+    fn test_puzzle_policy_overrides_mint_flag() {
+        let (env, contract_id, _) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        client.set_puzzle_policy(&String::from_str(&env, "P-SB"), &Some(TransferPolicy::Soulbound));
+        let token_id = mint_simple(&env, &client, &owner, "P-SB", true);
+
+        let cert = client.get_certificate(&token_id);
+        assert_eq!(cert.policy, TransferPolicy::Soulbound);
+        assert!(!cert.transferable);
+        let result = client.try_transfer(&owner, &Address::generate(&env), &token_id);
+        assert_eq!(result, Err(Ok(CertError::TransferRestricted.into())));
+
+        client.set_puzzle_policy(&String::from_str(&env, "P-SB"), &None);
+        assert_eq!(client.get_puzzle_policy(&String::from_str(&env, "P-SB")), None);
+    }
+
+    #[test]
+    fn test_provenance_keeps_solver_and_owner_history() {
+        let (env, contract_id, _) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let solver = Address::generate(&env);
+        let second = Address::generate(&env);
+        let third = Address::generate(&env);
+
+        client.set_puzzle_policy(&String::from_str(&env, "P-PV"), &Some(TransferPolicy::Provenance));
+        let token_id = mint_simple(&env, &client, &solver, "P-PV", false);
+        client.transfer(&solver, &second, &token_id);
+        client.transfer(&second, &third, &token_id);
+
+        let cert = client.get_certificate(&token_id);
+        assert_eq!(cert.owner, third);
+        assert_eq!(cert.solver, solver);
+        assert_eq!(
+            client.get_provenance(&token_id),
+            Vec::from_array(&env, [solver.clone(), second, third.clone()])
+        );
+        let proof = client.verify_certificate(&token_id);
+        assert_eq!(proof.solver, solver);
+        assert_eq!(proof.owner, third);
+    }
+
+    #[test]
+    fn test_set_certificate_policy() {
+        let (env, contract_id, _) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let token_id = mint_simple(&env, &client, &owner, "P-CP", false);
+        assert_eq!(client.get_provenance(&token_id).len(), 0);
+
+        client.set_certificate_policy(&token_id, &TransferPolicy::Provenance);
+        assert!(client.get_certificate(&token_id).transferable);
+        assert_eq!(client.get_provenance(&token_id), Vec::from_array(&env, [owner]));
+    }
+
+    #[test]
+    fn test_reissue_soulbound_to_recovery_wallet() {
+        let (env, contract_id, _) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let lost = Address::generate(&env);
+        let recovery = Address::generate(&env);
+        let token_id = mint_simple(&env, &client, &lost, "P-RE", false);
+
+        client.request_reissue(&token_id, &recovery);
+        let request = client.get_reissue_request(&token_id, &recovery).unwrap();
+        assert_eq!(request.new_owner, recovery);
+        assert!(!request.owner_signed);
+        let result = client.try_request_reissue(&token_id, &recovery);
+        assert_eq!(result, Err(Ok(CertError::ReissuePending.into())));
+
+        let new_id = client.approve_reissue(&token_id, &recovery);
+        assert_ne!(new_id, token_id);
+        assert!(client.get_certificate(&token_id).burned);
+        assert!(!client.verify_certificate(&token_id).authentic);
+        assert_eq!(client.get_owner_certificates(&lost).len(), 0);
+
+        let cert = client.get_certificate(&new_id);
+        assert_eq!(cert.owner, recovery);
+        assert_eq!(cert.solver, lost);
+        assert_eq!(cert.policy, TransferPolicy::Soulbound);
+        assert_eq!(client.get_owner_certificates(&recovery), Vec::from_array(&env, [new_id]));
+        assert!(client.is_minted(&String::from_str(&env, "P-RE"), &recovery));
+        assert!(client.get_reissue_request(&token_id, &recovery).is_none());
+    }
+
+    #[test]
+    fn test_reissue_verified_keeps_source_and_provenance() {
+        let (env, contract_id, admin) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let (verification, _) = setup_sources(&env, &client, &admin, false);
+        let lost = Address::generate(&env);
+        let recovery = Address::generate(&env);
+
+        client.set_puzzle_policy(&String::from_str(&env, "7"), &Some(TransferPolicy::Provenance));
+        solve(&env, &verification, &lost);
+        let token_id = client.mint_verified_certificate(
+            &lost, &7,
+            &String::from_str(&env, "Seven"),
+            &String::from_str(&env, "uri"),
+            &false,
+        );
+
+        client.request_reissue(&token_id, &recovery);
+        let new_id = client.approve_reissue(&token_id, &recovery);
+
+        let proof = client.verify_certificate(&new_id);
+        assert!(proof.verified);
+        assert!(proof.authentic);
+        assert_eq!(proof.solver, lost);
+        assert_eq!(client.get_provenance(&new_id), Vec::from_array(&env, [lost, recovery]));
+    }
+
+    #[test]
+    fn test_reject_reissue() {
+        let (env, contract_id, _) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let token_id = mint_simple(&env, &client, &owner, "P-RJ", false);
+
+        let recovery = Address::generate(&env);
+        client.request_reissue(&token_id, &recovery);
+        client.reject_reissue(&token_id, &recovery);
+        assert!(client.get_reissue_request(&token_id, &recovery).is_none());
+        assert_eq!(client.get_certificate(&token_id).owner, owner);
+
+        assert_eq!(client.try_approve_reissue(&token_id, &recovery), Err(Ok(CertError::ReissueNotFound.into())));
+        assert_eq!(client.try_reject_reissue(&token_id, &recovery), Err(Ok(CertError::ReissueNotFound.into())));
+    }
+
+    #[test]
+    fn test_outside_request_does_not_block_owner() {
+        let (env, contract_id, _) = setup();
+        let client = CompletionCertificateContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let attacker = Address::generate(&env);
+        let new_wallet = Address::generate(&env);
+        let token_id = mint_simple(&env, &client, &owner, "P-GR", false);
+
+        // An outside wallet files first; it only holds its own slot
+        client.request_reissue(&token_id, &attacker);
+        client.request_owner_reissue(&owner, &token_id, &new_wallet);
+        assert!(client.get_reissue_request(&token_id, &new_wallet).unwrap().owner_signed);
+
+        // The owner clears the outside request
+        client.cancel_reissue(&owner, &token_id, &attacker);
+        assert!(client.get_reissue_request(&token_id, &attacker).is_none());
+        let result = client.try_cancel_reissue(&attacker, &token_id, &new_wallet);
+        assert_eq!(result, Err(Ok(CertError::NotOwner.into())));
+
+        let new_id = client.approve_reissue(&token_id, &new_wallet);
+        assert_eq!(client.get_certificate(&new_id).owner, new_wallet);
+    }
+}
@@ -1,14 +1,49 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec, IntoVal,
+    contract, contractclient, contractimpl, contracttype, symbol_short, token, Address, Env,
+    Map, String, Symbol, TryFromVal, Val, Vec, IntoVal,
 };
 
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IngredientKind {
+    Nft = 0,      // Achievement NFT `token_id`, burned on use
+    Fungible = 1, // SEP-41 token at `token_address`, `amount` units burned on use
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Ingredient {
     pub token_address: Address,
     pub token_id: u32,
     pub amount: u32, // For fungible tokens, 1 for NFTs
+    pub kind: IngredientKind,
+}
+
+/// One possible output of a loot-table recipe.
+#[contracttype]
+#[derive(Clone)]
+pub struct LootEntry {
+    pub token_address: Address,
+    pub token_id: u32,
+    pub rarity: Rarity,
+    pub weight: u32, // Relative chance against the other entries
+}
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LevelSource {
+    BattlePass = 0, // Level of the player's pass in a `battle_pass` contract
+    DynamicNft = 1, // Level of a `dynamic_nft` token owned by the player
+}
+
+/// Minimum player level needed to craft a recipe.
+#[contracttype]
+#[derive(Clone)]
+pub struct LevelRequirement {
+    pub source: LevelSource,
+    pub contract: Address,
+    pub min_level: u32,
 }
 
 #[contracttype]
@@ -24,10 +59,51 @@ pub struct Recipe {
     pub rarity: Rarity,
     pub cooldown_seconds: u64,
     pub enabled: bool,
+    pub pity_threshold: u32, // Consecutive failures after which success is guaranteed, 0 = off
+    pub refund_bps: u32,     // Share of each ingredient returned on failure
+    pub loot_table: Vec<LootEntry>, // Weighted outputs; empty = the single output above
+    pub loot_rolls: u32,            // Outputs drawn from the loot table per success
 }
 
+// Shapes stored before ingredient kinds, failure rules and loot tables were
+// added, upgraded with defaults whenever a recipe is read.
 #[contracttype]
-#[derive(Clone, Copy)]
+#[derive(Clone)]
+pub struct LegacyIngredient {
+    pub token_address: Address,
+    pub token_id: u32,
+    pub amount: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyRecipe {
+    pub id: u32,
+    pub name: String,
+    pub description: String,
+    pub ingredients: Vec<LegacyIngredient>,
+    pub output_token_address: Address,
+    pub output_token_id: u32,
+    pub success_rate: u32,
+    pub rarity: Rarity,
+    pub cooldown_seconds: u64,
+    pub enabled: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct CraftAttempt {
+    pub recipe_id: u32,
+    pub roll: u32,           // 0-99, success when below success_rate
+    pub success_rate: u32,
+    pub pity_applied: bool,  // Success was forced by the pity counter
+    pub success: bool,
+    pub output_ids: Vec<u32>, // Minted tokens, empty on failure
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Rarity {
     Common = 0,
     Uncommon = 1,
@@ -44,6 +120,41 @@ pub enum DataKey {
     PlayerCooldown(Address),  // Persistent: Last crafting time per player
     RecipeCount,              // Instance: Total recipes
     NftContract,              // Instance: Address of the NFT contract to use
+    Pity(Address, u32),       // Persistent: Consecutive failures per player and recipe
+    CraftLog(Address),        // Persistent: Recent CraftAttempts per player
+    LevelRequirement(u32),    // Persistent: Minimum player level per recipe
+}
+
+const MAX_LOG_ENTRIES: u32 = 50;
+const MAX_LOOT_ROLLS: u32 = 10;
+
+// Mirrors of the level sources' types, matched field for field.
+#[contracttype]
+#[derive(Clone, Copy)]
+pub enum PassTier {
+    Free = 0,
+    Premium = 1,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct BattlePass {
+    pub owner: Address,
+    pub season: u32,
+    pub tier: PassTier,
+    pub current_level: u32,
+    pub is_active: bool,
+    pub purchase_time: u64,
+}
+
+#[contractclient(name = "BattlePassClient")]
+pub trait BattlePassInterface {
+    fn get_player_pass(env: Env, player: Address) -> Option<BattlePass>;
+}
+
+#[contractclient(name = "DynamicNftClient")]
+pub trait DynamicNftInterface {
+    fn get_level(env: Env, token_id: u32) -> (Address, u32);
 }
 
 #[contract]
@@ -63,6 +174,7 @@ impl CraftingContract {
     }
 
     /// Register a new crafting recipe (admin only).
+    #[allow(clippy::too_many_arguments)]
     pub fn register_recipe(
         env: Env,
         name: String,
@@ -103,6 +215,10 @@ impl CraftingContract {
             rarity: rarity_enum,
             cooldown_seconds,
             enabled: true,
+            pity_threshold: 0,
+            refund_bps: 0,
+            loot_table: Vec::new(&env),
+            loot_rolls: 1,
         };
 
         env.storage().persistent().set(&DataKey::Recipe(recipe_id), &recipe);
@@ -117,12 +233,54 @@ impl CraftingContract {
         recipe_id
     }
 
-    /// Get recipe details by ID.
+    /// Get recipe details by ID. Recipes stored in the legacy shape are
+    /// returned with default failure rules and no loot table; the next admin
+    /// update stores them in the current shape.
     pub fn get_recipe(env: Env, recipe_id: u32) -> Recipe {
-        env.storage()
+        let raw: Val = env
+            .storage()
             .persistent()
             .get(&DataKey::Recipe(recipe_id))
-            .unwrap_or_else(|| panic!("Recipe not found"))
+            .unwrap_or_else(|| panic!("Recipe not found"));
+        // Decoding a struct with the wrong field count traps, so check the
+        // stored field names first
+        let fields = Map::<Symbol, Val>::try_from_val(&env, &raw)
+            .unwrap_or_else(|_| panic!("Recipe not decodable"));
+        if fields.contains_key(Symbol::new(&env, "loot_rolls")) {
+            return Recipe::try_from_val(&env, &raw).unwrap();
+        }
+        let legacy = LegacyRecipe::try_from_val(&env, &raw).unwrap();
+        let mut ingredients = Vec::new(&env);
+        for ingredient in legacy.ingredients.iter() {
+            // The legacy contract treated an amount of 1 as an NFT
+            let kind = if ingredient.amount == 1 {
+                IngredientKind::Nft
+            } else {
+                IngredientKind::Fungible
+            };
+            ingredients.push_back(Ingredient {
+                token_address: ingredient.token_address,
+                token_id: ingredient.token_id,
+                amount: ingredient.amount,
+                kind,
+            });
+        }
+        Recipe {
+            id: legacy.id,
+            name: legacy.name,
+            description: legacy.description,
+            ingredients,
+            output_token_address: legacy.output_token_address,
+            output_token_id: legacy.output_token_id,
+            success_rate: legacy.success_rate,
+            rarity: legacy.rarity,
+            cooldown_seconds: legacy.cooldown_seconds,
+            enabled: legacy.enabled,
+            pity_threshold: 0,
+            refund_bps: 0,
+            loot_table: Vec::new(&env),
+            loot_rolls: 1,
+        }
     }
 
     /// Get all recipe IDs.
@@ -140,8 +298,13 @@ impl CraftingContract {
         let nft_contract: Address = env.storage().instance().get(&DataKey::NftContract).unwrap();
 
         for ingredient in ingredients.iter() {
-            // For NFTs, check ownership via cross-contract call
-            if ingredient.amount == 1 {
+            if ingredient.kind == IngredientKind::Fungible {
+                let balance = token::Client::new(&env, &ingredient.token_address).balance(&player);
+                if balance < ingredient.amount as i128 {
+                    return false;
+                }
+            } else {
+                // For NFTs, check ownership via cross-contract call
                 // Check if player owns the NFT
                 let owner: Result<Address, soroban_sdk::Error> = env.invoke_contract(
                     &nft_contract,
@@ -157,17 +320,35 @@ impl CraftingContract {
                     }
                     Err(_) => return false, // Token doesn't exist or other error
                 }
-            } else {
-                // For fungible tokens, would need balance checking
-                // TODO: Implement fungible token balance checking
-                return false; // Not implemented yet
             }
         }
         true
     }
 
-    /// Attempt to craft using a recipe.
-    pub fn craft(env: Env, player: Address, recipe_id: u32) -> u32 {
+    /// Attempt to craft using a recipe. Returns the minted token ids: one for
+    /// a fixed output, `loot_rolls` for a loot table, empty when the roll
+    /// fails. Every attempt is appended to the player's craft log.
+    pub fn craft(env: Env, player: Address, recipe_id: u32) -> Vec<u32> {
+        Self::craft_internal(env, player, recipe_id, None)
+    }
+
+    /// Craft a recipe gated on a `dynamic_nft` level, proving it with one of
+    /// the player's tokens. Returns the minted token ids like `craft`.
+    pub fn craft_with_level_token(
+        env: Env,
+        player: Address,
+        recipe_id: u32,
+        level_token_id: u32,
+    ) -> Vec<u32> {
+        Self::craft_internal(env, player, recipe_id, Some(level_token_id))
+    }
+
+    fn craft_internal(
+        env: Env,
+        player: Address,
+        recipe_id: u32,
+        level_token_id: Option<u32>,
+    ) -> Vec<u32> {
         player.require_auth();
 
         let recipe = Self::get_recipe(env.clone(), recipe_id);
@@ -175,6 +356,8 @@ impl CraftingContract {
             panic!("recipe_disabled");
         }
 
+        Self::check_level(&env, &player, recipe_id, level_token_id);
+
         // Check cooldown
         let last_craft: u64 = env
             .storage()
@@ -195,297 +378,258 @@ impl CraftingContract {
         env.storage().persistent().set(&DataKey::PlayerCooldown(player.clone()), &current_time);
         env.storage().persistent().extend_ttl(&DataKey::PlayerCooldown(player.clone()), 100_000, 500_000);
 
-        // Determine success
-        let random_seed: u64 = env.prng().gen_range(0..100);
-        let success = random_seed < recipe.success_rate as u64;
+        // Determine success; the pity counter forces it after enough failures
+        let failures = Self::get_pity_counter(env.clone(), player.clone(), recipe_id);
+        let roll = env.prng().gen_range::<u64>(0..100) as u32;
+        let pity_applied = recipe.pity_threshold > 0 && failures >= recipe.pity_threshold;
+        let success = roll < recipe.success_rate || pity_applied;
 
+        let pity_key = DataKey::Pity(player.clone(), recipe_id);
+        let mut output_ids = Vec::new(&env);
         if success {
-            // Burn ingredients
-            for ingredient in recipe.ingredients.iter() {
-                if ingredient.amount == 1 {
-                    // Burn NFT
-                    let _: Result<(), soroban_sdk::Error> = env.invoke_contract(
-                        &ingredient.token_address,
-                        &symbol_short!("burn"),
-                        Vec::from_array(&env, [ingredient.token_id.into()]),
+            Self::consume_ingredients(&env, &player, &recipe, 0);
+
+            if recipe.loot_table.is_empty() {
+                output_ids.push_back(Self::mint_output(
+                    &env,
+                    &player,
+                    &recipe.output_token_address,
+                    recipe.output_token_id,
+                ));
+            } else {
+                for _ in 0..recipe.loot_rolls {
+                    let entry = Self::draw_loot(&env, &recipe.loot_table);
+                    let output_id =
+                        Self::mint_output(&env, &player, &entry.token_address, entry.token_id);
+                    env.events().publish(
+                        (symbol_short!("loot"), &player, recipe_id),
+                        (output_id, entry.rarity),
                     );
-                    // Note: We ignore errors here - in production, you'd want proper error handling
+                    output_ids.push_back(output_id);
                 }
-                // TODO: Handle fungible tokens
             }
+            env.storage().persistent().remove(&pity_key);
 
-            // Mint output NFT
-            let output_id: u32 = env.invoke_contract(
-                &recipe.output_token_address,
-                &symbol_short!("craftmint"),
-                Vec::from_array(&env, [
-                    player.into_val(&env),
-                    recipe.output_token_id.into(),
-                    String::from_str(&env, "Crafted achievement").into_val(&env),
-                ]),
-            );
-
-            env.events().publish((symbol_short!("success"), &player, recipe_id), output_id);
-            output_id
+            env.events().publish((symbol_short!("success"), &player, recipe_id), output_ids.clone());
         } else {
-            // Handle failure - emit failure event
-            env.events().publish((symbol_short!("failure"), &player, recipe_id), 0u32);
-            panic!("craft_failed")
+            Self::consume_ingredients(&env, &player, &recipe, recipe.refund_bps);
+            env.storage().persistent().set(&pity_key, &(failures + 1));
+            env.storage().persistent().extend_ttl(&pity_key, 100_000, 500_000);
+
+            env.events().publish((symbol_short!("failure"), &player, recipe_id), roll);
         }
-    }
 
-    /// Get player's last crafting time.
-    pub fn get_player_cooldown(env: Env, player: Address) -> u64 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlayerCooldown(player))
-            .unwrap_or(0)
+        Self::append_log(
+            &env,
+            &player,
+            CraftAttempt {
+                recipe_id,
+                roll,
+                success_rate: recipe.success_rate,
+                pity_applied,
+                success,
+                output_ids: output_ids.clone(),
+                timestamp: current_time,
+            },
+        );
+
+        output_ids
     }
 
-    /// Enable/disable a recipe (admin only).
-    pub fn set_recipe_enabled(env: Env, recipe_id: u32, enabled: bool) {
+    /// Replace the weighted loot table of a recipe (admin only). Each success
+    /// draws `rolls` outputs; an empty table restores the single fixed output.
+    pub fn set_loot_table(env: Env, recipe_id: u32, entries: Vec<LootEntry>, rolls: u32) {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
+        if rolls == 0 || rolls > MAX_LOOT_ROLLS {
+            panic!("Loot rolls must be between 1 and 10");
+        }
+        let mut total_weight = 0u32;
+        for entry in entries.iter() {
+            total_weight = total_weight
+                .checked_add(entry.weight)
+                .unwrap_or_else(|| panic!("Loot weight overflow"));
+        }
+        if !entries.is_empty() && total_weight == 0 {
+            panic!("Loot table needs a positive weight");
+        }
+
         let mut recipe = Self::get_recipe(env.clone(), recipe_id);
-        recipe.enabled = enabled;
+        recipe.loot_table = entries;
+        recipe.loot_rolls = rolls;
         env.storage().persistent().set(&DataKey::Recipe(recipe_id), &recipe);
     }
-}
-
-#[cfg(test)]
-mod test {
-    use crate::{CraftingContract, Ingredient};
-    use soroban_sdk::{testutils::Address as AddressTestUtils, vec, Address, Env, String};
-
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let admin = Address::generate(&env);
-        let nft_contract = Address::generate(&env);
-
-        let contract_id = env.register_contract(None, CraftingContract);
-        let client = crate::CraftingContractClient::new(&env, &contract_id);
 
-        client.initialize(&admin, &nft_contract);
+    /// Require a minimum player level for a recipe, or clear it with `None` (admin only).
+    pub fn set_level_requirement(env: Env, recipe_id: u32, requirement: Option<LevelRequirement>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
 
-        assert_eq!(client.get_all_recipes().len(), 0);
+        Self::get_recipe(env.clone(), recipe_id);
+        let key = DataKey::LevelRequirement(recipe_id);
+        match requirement {
+            Some(requirement) => {
+                env.storage().persistent().set(&key, &requirement);
+                env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
     }
 
-    #[test]
-    fn test_register_recipe() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let admin = Address::generate(&env);
-        let nft_contract = Address::generate(&env);
-
-        let contract_id = env.register_contract(None, CraftingContract);
-        let client = crate::CraftingContractClient::new(&env, &contract_id);
-
-        client.initialize(&admin, &nft_contract);
-
-        let ingredients = vec![
-            &env,
-            Ingredient {
-                token_address: nft_contract.clone(),
-                token_id: 1,
-                amount: 1,
-            },
-            Ingredient {
-                token_address: nft_contract.clone(),
-                token_id: 2,
-                amount: 1,
-            },
-        ];
-
-        let recipe_id = client.register_recipe(
-            &String::from_str(&env, "Epic Sword"),
-            &String::from_str(&env, "A powerful sword crafted from rare materials"),
-            &ingredients,
-            &nft_contract,
-            &100,
-            &80,
-            &3, // Epic
-            &3600, // 1 hour cooldown
-        );
-
-        assert_eq!(recipe_id, 1);
-
-        let recipe = client.get_recipe(&recipe_id);
-        assert_eq!(recipe.name, String::from_str(&env, "Epic Sword"));
-        assert_eq!(recipe.success_rate, 80);
-        assert_eq!(recipe.ingredients.len(), 2);
+    pub fn get_level_requirement(env: Env, recipe_id: u32) -> Option<LevelRequirement> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LevelRequirement(recipe_id))
     }
 
-    #[test]
-    fn test_craft_success() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        let admin = Address::generate(&env);
-        let _player = Address::generate(&env);
-        let nft_contract = Address::generate(&env); // Mock NFT contract address
-
-        let contract_id = env.register_contract(None, CraftingContract);
-        let client = crate::CraftingContractClient::new(&env, &contract_id);
-        client.initialize(&admin, &nft_contract);
+    fn check_level(env: &Env, player: &Address, recipe_id: u32, level_token_id: Option<u32>) {
+        let requirement = match Self::get_level_requirement(env.clone(), recipe_id) {
+            Some(requirement) => requirement,
+            None => return,
+        };
 
-        // Register recipe
-        let ingredients = vec![
-            &env,
-            Ingredient {
-                token_address: nft_contract.clone(),
-                token_id: 1,
-                amount: 1,
-            },
-            Ingredient {
-                token_address: nft_contract.clone(),
-                token_id: 2,
-                amount: 1,
-            },
-        ];
-
-        let recipe_id = client.register_recipe(
-            &String::from_str(&env, "Epic Sword"),
-            &String::from_str(&env, "A powerful sword"),
-            &ingredients,
-            &nft_contract,
-            &100,
-            &100, // 100% success rate
-            &3,
-            &0, // No cooldown
-        );
+        let level = match requirement.source {
+            LevelSource::BattlePass => BattlePassClient::new(env, &requirement.contract)
+                .get_player_pass(player)
+                .filter(|pass| pass.is_active)
+                .map(|pass| pass.current_level)
+                .unwrap_or(0),
+            LevelSource::DynamicNft => {
+                let token_id =
+                    level_token_id.unwrap_or_else(|| panic!("level_token_required"));
+                let (owner, level) = match DynamicNftClient::new(env, &requirement.contract)
+                    .try_get_level(&token_id)
+                {
+                    Ok(Ok(owner_level)) => owner_level,
+                    _ => panic!("level_token_not_found"),
+                };
+                if owner != *player {
+                    panic!("level_token_not_owned");
+                }
+                level
+            }
+        };
 
-        // Note: Full crafting test requires NFT contract setup
-        // For testnet validation, we verify the recipe registration works
-        assert_eq!(recipe_id, 1);
-        let recipe = client.get_recipe(&recipe_id);
-        assert_eq!(recipe.success_rate, 100);
+        if level < requirement.min_level {
+            panic!("level_too_low");
+        }
     }
 
-    #[test]
-    fn test_recipe_discovery() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let admin = Address::generate(&env);
-        let nft_contract = Address::generate(&env);
-
-        let contract_id = env.register_contract(None, CraftingContract);
-        let client = crate::CraftingContractClient::new(&env, &contract_id);
-
-        client.initialize(&admin, &nft_contract);
-
-        // Register multiple recipes
-        for i in 0..3 {
-            let recipe_name = match i {
-                0 => String::from_str(&env, "Recipe 0"),
-                1 => String::from_str(&env, "Recipe 1"),
-                2 => String::from_str(&env, "Recipe 2"),
-                _ => String::from_str(&env, "Recipe"),
-            };
+    fn mint_output(env: &Env, player: &Address, token_address: &Address, token_id: u32) -> u32 {
+        env.invoke_contract(
+            token_address,
+            &symbol_short!("craftmint"),
+            Vec::from_array(env, [
+                env.current_contract_address().into_val(env),
+                player.into_val(env),
+                token_id.into(),
+                String::from_str(env, "Crafted achievement").into_val(env),
+            ]),
+        )
+    }
 
-            let ingredients = vec![
-                &env,
-                Ingredient {
-                    token_address: nft_contract.clone(),
-                    token_id: i + 1,
-                    amount: 1,
-                },
-            ];
-
-            client.register_recipe(
-                &recipe_name,
-                &String::from_str(&env, "Description"),
-                &ingredients,
-                &nft_contract,
-                &100,
-                &80,
-                &(i as u32 % 5),
-                &3600,
-            );
+    fn draw_loot(env: &Env, table: &Vec<LootEntry>) -> LootEntry {
+        let mut total_weight = 0u64;
+        for entry in table.iter() {
+            total_weight += entry.weight as u64;
         }
-
-        // Get all recipes
-        let all_recipes = client.get_all_recipes();
-        assert_eq!(all_recipes.len(), 3);
+        let mut roll = env.prng().gen_range::<u64>(0..total_weight);
+        for entry in table.iter() {
+            if roll < entry.weight as u64 {
+                return entry;
+            }
+            roll -= entry.weight as u64;
+        }
+        unreachable!()
     }
 
-    #[test]
-    fn test_craft_failure() {
-        let env = Env::default();
-        env.mock_all_auths();
+    /// Set the pity threshold and failure refund share of a recipe (admin only).
+    /// On failure each ingredient returns `amount * refund_bps / 10_000` units,
+    /// so an NFT ingredient is only kept with a full 10_000 bps refund.
+    pub fn set_failure_rules(env: Env, recipe_id: u32, pity_threshold: u32, refund_bps: u32) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
 
-        let admin = Address::generate(&env);
-        let _player = Address::generate(&env);
-        let nft_contract = Address::generate(&env);
+        if refund_bps > 10_000 {
+            panic!("Refund must be at most 10000 bps");
+        }
 
-        let contract_id = env.register_contract(None, CraftingContract);
-        let client = crate::CraftingContractClient::new(&env, &contract_id);
-        client.initialize(&admin, &nft_contract);
+        let mut recipe = Self::get_recipe(env.clone(), recipe_id);
+        recipe.pity_threshold = pity_threshold;
+        recipe.refund_bps = refund_bps;
+        env.storage().persistent().set(&DataKey::Recipe(recipe_id), &recipe);
+    }
 
-        // Register recipe with 0% success rate
-        let ingredients = vec![
-            &env,
-            Ingredient {
-                token_address: nft_contract.clone(),
-                token_id: 1,
-                amount: 1,
-            },
-        ];
-
-        let recipe_id = client.register_recipe(
-            &String::from_str(&env, "Impossible Item"),
-            &String::from_str(&env, "Cannot be crafted"),
-            &ingredients,
-            &nft_contract,
-            &200,
-            &0, // 0% success rate
-            &4,
-            &0,
-        );
+    /// Consecutive failed attempts of a recipe since the player's last success.
+    pub fn get_pity_counter(env: Env, player: Address, recipe_id: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Pity(player, recipe_id))
+            .unwrap_or(0)
+    }
 
-        // Verify recipe registration
-        assert_eq!(recipe_id, 1);
-        let recipe = client.get_recipe(&recipe_id);
-        assert_eq!(recipe.success_rate, 0);
+    /// The player's most recent craft attempts, oldest first.
+    pub fn get_craft_log(env: Env, player: Address) -> Vec<CraftAttempt> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CraftLog(player))
+            .unwrap_or(Vec::new(&env))
     }
 
-    #[test]
-    fn test_cooldown() {
-        let env = Env::default();
-        env.mock_all_auths();
+    /// Burn each ingredient except the refunded share.
+    fn consume_ingredients(env: &Env, player: &Address, recipe: &Recipe, refund_bps: u32) {
+        for ingredient in recipe.ingredients.iter() {
+            let refunded = (ingredient.amount as u64 * refund_bps as u64 / 10_000) as u32;
+            if ingredient.kind == IngredientKind::Fungible {
+                let burned = ingredient.amount - refunded;
+                if burned > 0 {
+                    token::Client::new(env, &ingredient.token_address)
+                        .burn(player, &(burned as i128));
+                }
+            } else if refunded == 0 {
+                // Burn NFT through the approval the player granted this contract
+                env.invoke_contract::<()>(
+                    &ingredient.token_address,
+                    &Symbol::new(env, "burn_from"),
+                    Vec::from_array(env, [
+                        env.current_contract_address().into_val(env),
+                        ingredient.token_id.into(),
+                    ]),
+                );
+            }
+        }
+    }
 
-        let admin = Address::generate(&env);
-        let _player = Address::generate(&env);
-        let nft_contract = Address::generate(&env);
+    fn append_log(env: &Env, player: &Address, attempt: CraftAttempt) {
+        let key = DataKey::CraftLog(player.clone());
+        let mut log = Self::get_craft_log(env.clone(), player.clone());
+        if log.len() >= MAX_LOG_ENTRIES {
+            log.pop_front();
+        }
+        log.push_back(attempt);
+        env.storage().persistent().set(&key, &log);
+        env.storage().persistent().extend_ttl(&key, 100_000, 500_000);
+    }
 
-        let contract_id = env.register_contract(None, CraftingContract);
-        let client = crate::CraftingContractClient::new(&env, &contract_id);
-        client.initialize(&admin, &nft_contract);
+    /// Get player's last crafting time.
+    pub fn get_player_cooldown(env: Env, player: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerCooldown(player))
+            .unwrap_or(0)
+    }
 
-        // Register recipe with cooldown
-        let ingredients = vec![
-            &env,
-            Ingredient {
-                token_address: nft_contract.clone(),
-                token_id: 1,
-                amount: 1,
-            },
-        ];
-
-        let recipe_id = client.register_recipe(
-            &String::from_str(&env, "Cooldown Item"),
-            &String::from_str(&env, "Has cooldown"),
-            &ingredients,
-            &nft_contract,
-            &200,
-            &100,
-            &1,
-            &60, // 60 seconds cooldown
-        );
+    /// Enable/disable a recipe (admin only).
+    pub fn set_recipe_enabled(env: Env, recipe_id: u32, enabled: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
 
-        // Verify cooldown is set in recipe
-        let recipe = client.get_recipe(&recipe_id);
-        assert_eq!(recipe.cooldown_seconds, 60);
+        let mut recipe = Self::get_recipe(env.clone(), recipe_id);
+        recipe.enabled = enabled;
+        env.storage().persistent().set(&DataKey::Recipe(recipe_id), &recipe);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test;
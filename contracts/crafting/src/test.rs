@@ -0,0 +1,660 @@
+#![cfg(test)]
+
+use crate::{
+    BattlePass, CraftingContract, DataKey, Ingredient, IngredientKind, LegacyIngredient,
+    LegacyRecipe, LevelRequirement, LevelSource, LootEntry, PassTier, Rarity,
+};
+use achievement_nft::{AchievementNFT, AchievementNFTClient};
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::{Address as AddressTestUtils, Ledger},
+    token, vec, Address, Env, String,
+};
+
+#[contracttype]
+enum MockKey {
+    Level(Address),
+    Nft(u32),
+}
+
+#[contract]
+pub struct MockBattlePass;
+
+#[contractimpl]
+impl MockBattlePass {
+    pub fn set_level(env: Env, player: Address, level: u32) {
+        let pass = BattlePass {
+            owner: player.clone(),
+            season: 1,
+            tier: PassTier::Premium,
+            current_level: level,
+            is_active: true,
+            purchase_time: 0,
+        };
+        env.storage().instance().set(&MockKey::Level(player), &pass);
+    }
+
+    pub fn get_player_pass(env: Env, player: Address) -> Option<BattlePass> {
+        env.storage().instance().get(&MockKey::Level(player))
+    }
+}
+
+#[contract]
+pub struct MockDynamicNft;
+
+#[contractimpl]
+impl MockDynamicNft {
+    pub fn set_nft(env: Env, token_id: u32, owner: Address, level: u32) {
+        env.storage().instance().set(&MockKey::Nft(token_id), &(owner, level));
+    }
+
+    pub fn get_level(env: Env, token_id: u32) -> (Address, u32) {
+        env.storage()
+            .instance()
+            .get(&MockKey::Nft(token_id))
+            .unwrap_or_else(|| panic!("Token not found"))
+    }
+}
+
+fn setup_craft(env: &Env, success_rate: u32, cooldown: u64) -> (crate::CraftingContractClient<'_>, AchievementNFTClient<'_>, Address, u32) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let player = Address::generate(env);
+    let nft_contract = env.register_contract(None, AchievementNFT);
+    let nft = AchievementNFTClient::new(env, &nft_contract);
+    nft.initialize(&admin);
+    nft.craftmint(&admin, &player, &1, &String::from_str(env, "Shard"));
+    nft.craftmint(&admin, &player, &2, &String::from_str(env, "Hilt"));
+
+    let contract_id = env.register_contract(None, CraftingContract);
+    let client = crate::CraftingContractClient::new(env, &contract_id);
+    client.initialize(&admin, &nft_contract);
+    // Crafting may only mint its recipe outputs
+    nft.authorize_minter(&contract_id, &vec![env, 100u32], &None);
+    // and burns ingredients as the player's operator
+    nft.approve_for_all(&player, &contract_id, &1_000);
+
+    let ingredients = vec![
+        env,
+        Ingredient {
+            token_address: nft_contract.clone(),
+            token_id: 1,
+            amount: 1,
+            kind: IngredientKind::Nft,
+        },
+        Ingredient {
+            token_address: nft_contract.clone(),
+            token_id: 2,
+            amount: 1,
+            kind: IngredientKind::Nft,
+        },
+    ];
+    let recipe_id = client.register_recipe(
+        &String::from_str(env, "Epic Sword"),
+        &String::from_str(env, "A powerful sword"),
+        &ingredients,
+        &nft_contract,
+        &100,
+        &success_rate,
+        &3,
+        &cooldown,
+    );
+    (client, nft, player, recipe_id)
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let nft_contract = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, CraftingContract);
+    let client = crate::CraftingContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &nft_contract);
+
+    assert_eq!(client.get_all_recipes().len(), 0);
+}
+
+#[test]
+fn test_register_recipe() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let nft_contract = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, CraftingContract);
+    let client = crate::CraftingContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &nft_contract);
+
+    let ingredients = vec![
+        &env,
+        Ingredient {
+            token_address: nft_contract.clone(),
+            token_id: 1,
+            amount: 1,
+            kind: IngredientKind::Nft,
+        },
+        Ingredient {
+            token_address: nft_contract.clone(),
+            token_id: 2,
+            amount: 1,
+            kind: IngredientKind::Nft,
+        },
+    ];
+
+    let recipe_id = client.register_recipe(
+        &String::from_str(&env, "Epic Sword"),
+        &String::from_str(&env, "A powerful sword crafted from rare materials"),
+        &ingredients,
+        &nft_contract,
+        &100,
+        &80,
+        &3, // Epic
+        &3600, // 1 hour cooldown
+    );
+
+    assert_eq!(recipe_id, 1);
+
+    let recipe = client.get_recipe(&recipe_id);
+    assert_eq!(recipe.name, String::from_str(&env, "Epic Sword"));
+    assert_eq!(recipe.success_rate, 80);
+    assert_eq!(recipe.ingredients.len(), 2);
+}
+
+#[test]
+fn test_craft_success() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let _player = Address::generate(&env);
+    let nft_contract = Address::generate(&env); // Mock NFT contract address
+
+    let contract_id = env.register_contract(None, CraftingContract);
+    let client = crate::CraftingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &nft_contract);
+
+    // Register recipe
+    let ingredients = vec![
+        &env,
+        Ingredient {
+            token_address: nft_contract.clone(),
+            token_id: 1,
+            amount: 1,
+            kind: IngredientKind::Nft,
+        },
+        Ingredient {
+            token_address: nft_contract.clone(),
+            token_id: 2,
+            amount: 1,
+            kind: IngredientKind::Nft,
+        },
+    ];
+
+    let recipe_id = client.register_recipe(
+        &String::from_str(&env, "Epic Sword"),
+        &String::from_str(&env, "A powerful sword"),
+        &ingredients,
+        &nft_contract,
+        &100,
+        &100, // 100% success rate
+        &3,
+        &0, // No cooldown
+    );
+
+    // Note: Full crafting test requires NFT contract setup
+    // For testnet validation, we verify the recipe registration works
+    assert_eq!(recipe_id, 1);
+    let recipe = client.get_recipe(&recipe_id);
+    assert_eq!(recipe.success_rate, 100);
+}
+
+#[test]
+fn test_recipe_discovery() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let nft_contract = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, CraftingContract);
+    let client = crate::CraftingContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &nft_contract);
+
+    // Register multiple recipes
+    for i in 0..3 {
+        let recipe_name = match i {
+            0 => String::from_str(&env, "Recipe 0"),
+            1 => String::from_str(&env, "Recipe 1"),
+            2 => String::from_str(&env, "Recipe 2"),
+            _ => String::from_str(&env, "Recipe"),
+        };
+
+        let ingredients = vec![
+            &env,
+            Ingredient {
+                token_address: nft_contract.clone(),
+                token_id: i + 1,
+                amount: 1,
+                kind: IngredientKind::Nft,
+            },
+        ];
+
+        client.register_recipe(
+            &recipe_name,
+            &String::from_str(&env, "Description"),
+            &ingredients,
+            &nft_contract,
+            &100,
+            &80,
+            &(i % 5),
+            &3600,
+        );
+    }
+
+    // Get all recipes
+    let all_recipes = client.get_all_recipes();
+    assert_eq!(all_recipes.len(), 3);
+}
+
+#[test]
+fn test_craft_failure() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let _player = Address::generate(&env);
+    let nft_contract = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, CraftingContract);
+    let client = crate::CraftingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &nft_contract);
+
+    // Register recipe with 0% success rate
+    let ingredients = vec![
+        &env,
+        Ingredient {
+            token_address: nft_contract.clone(),
+            token_id: 1,
+            amount: 1,
+            kind: IngredientKind::Nft,
+        },
+    ];
+
+    let recipe_id = client.register_recipe(
+        &String::from_str(&env, "Impossible Item"),
+        &String::from_str(&env, "Cannot be crafted"),
+        &ingredients,
+        &nft_contract,
+        &200,
+        &0, // 0% success rate
+        &4,
+        &0,
+    );
+
+    // Verify recipe registration
+    assert_eq!(recipe_id, 1);
+    let recipe = client.get_recipe(&recipe_id);
+    assert_eq!(recipe.success_rate, 0);
+}
+
+#[test]
+fn test_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let _player = Address::generate(&env);
+    let nft_contract = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, CraftingContract);
+    let client = crate::CraftingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &nft_contract);
+
+    // Register recipe with cooldown
+    let ingredients = vec![
+        &env,
+        Ingredient {
+            token_address: nft_contract.clone(),
+            token_id: 1,
+            amount: 1,
+            kind: IngredientKind::Nft,
+        },
+    ];
+
+    let recipe_id = client.register_recipe(
+        &String::from_str(&env, "Cooldown Item"),
+        &String::from_str(&env, "Has cooldown"),
+        &ingredients,
+        &nft_contract,
+        &200,
+        &100,
+        &1,
+        &60, // 60 seconds cooldown
+    );
+
+    // Verify cooldown is set in recipe
+    let recipe = client.get_recipe(&recipe_id);
+    assert_eq!(recipe.cooldown_seconds, 60);
+}
+
+#[test]
+fn test_craft_burns_ingredients_and_mints_output() {
+    let env = Env::default();
+    let (client, nft, player, recipe_id) = setup_craft(&env, 100, 0);
+
+    let outputs = client.craft(&player, &recipe_id);
+
+    assert_eq!(outputs, vec![&env, 3u32]);
+    let output_id = outputs.get(0).unwrap();
+    assert_eq!(nft.owner_of(&output_id), player);
+    assert_eq!(nft.get_achievement(&output_id).unwrap().puzzle_id, 100);
+    assert!(nft.get_achievement(&1).is_none());
+    assert!(nft.get_achievement(&2).is_none());
+}
+
+#[test]
+fn test_craft_zero_success_rate_fails() {
+    let env = Env::default();
+    let (client, nft, player, recipe_id) = setup_craft(&env, 0, 0);
+
+    assert!(client.craft(&player, &recipe_id).is_empty());
+
+    // Without a refund the ingredients are consumed
+    assert!(nft.get_achievement(&1).is_none());
+    assert!(nft.get_achievement(&2).is_none());
+    assert_eq!(client.get_pity_counter(&player, &recipe_id), 1);
+
+    let log = client.get_craft_log(&player);
+    assert_eq!(log.len(), 1);
+    let attempt = log.get(0).unwrap();
+    assert!(!attempt.success);
+    assert!(!attempt.pity_applied);
+    assert_eq!(attempt.success_rate, 0);
+    assert!(attempt.output_ids.is_empty());
+    assert!(attempt.roll < 100);
+}
+
+#[test]
+fn test_pity_guarantees_success_with_refunds() {
+    let env = Env::default();
+    let (client, nft, player, recipe_id) = setup_craft(&env, 0, 0);
+    client.set_failure_rules(&recipe_id, &2, &10_000);
+
+    assert!(client.craft(&player, &recipe_id).is_empty());
+    assert!(client.craft(&player, &recipe_id).is_empty());
+    // Fully refunded failures keep the ingredients
+    assert_eq!(nft.owner_of(&1), player);
+    assert_eq!(client.get_pity_counter(&player, &recipe_id), 2);
+
+    let output_id = client.craft(&player, &recipe_id).get(0).unwrap();
+    assert_eq!(nft.owner_of(&output_id), player);
+    assert!(nft.get_achievement(&1).is_none());
+    assert_eq!(client.get_pity_counter(&player, &recipe_id), 0);
+
+    let log = client.get_craft_log(&player);
+    assert_eq!(log.len(), 3);
+    let last = log.get(2).unwrap();
+    assert!(last.success);
+    assert!(last.pity_applied);
+    assert_eq!(last.output_ids, vec![&env, output_id]);
+}
+
+#[test]
+#[should_panic(expected = "Refund must be at most 10000 bps")]
+fn test_failure_refund_capped() {
+    let env = Env::default();
+    let (client, _nft, _player, recipe_id) = setup_craft(&env, 50, 0);
+
+    client.set_failure_rules(&recipe_id, &0, &10_001);
+}
+
+#[test]
+#[should_panic(expected = "invalid_ingredients")]
+fn test_craft_requires_owned_ingredients() {
+    let env = Env::default();
+    let (client, _nft, _player, recipe_id) = setup_craft(&env, 100, 0);
+
+    client.craft(&Address::generate(&env), &recipe_id);
+}
+
+#[test]
+#[should_panic(expected = "cooldown_active")]
+fn test_craft_respects_cooldown() {
+    let env = Env::default();
+    let (client, _nft, player, recipe_id) = setup_craft(&env, 100, 60);
+    env.ledger().set_timestamp(1000);
+
+    client.craft(&player, &recipe_id);
+    assert_eq!(client.get_player_cooldown(&player), 1000);
+
+    client.craft(&player, &recipe_id);
+}
+
+#[test]
+#[should_panic(expected = "recipe_disabled")]
+fn test_craft_disabled_recipe() {
+    let env = Env::default();
+    let (client, _nft, player, recipe_id) = setup_craft(&env, 100, 0);
+
+    client.set_recipe_enabled(&recipe_id, &false);
+    client.craft(&player, &recipe_id);
+}
+
+#[test]
+fn test_craft_consumes_fungible_ingredient() {
+    let env = Env::default();
+    let (client, nft, player, _) = setup_craft(&env, 0, 0);
+    let energy = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    token::StellarAssetClient::new(&env, &energy).mint(&player, &100);
+
+    let ingredients = vec![
+        &env,
+        Ingredient {
+            token_address: energy.clone(),
+            token_id: 0,
+            amount: 40,
+            kind: IngredientKind::Fungible,
+        },
+    ];
+    let recipe_id = client.register_recipe(
+        &String::from_str(&env, "Charged Shard"),
+        &String::from_str(&env, "Costs energy"),
+        &ingredients,
+        &nft.address,
+        &100,
+        &0,
+        &1,
+        &0,
+    );
+    client.set_failure_rules(&recipe_id, &1, &5_000);
+
+    // The failure refunds half the energy
+    assert!(client.craft(&player, &recipe_id).is_empty());
+    let energy_balance = token::Client::new(&env, &energy);
+    assert_eq!(energy_balance.balance(&player), 80);
+
+    // Pity forces the success, which burns the full amount
+    let outputs = client.craft(&player, &recipe_id);
+    assert_eq!(nft.owner_of(&outputs.get(0).unwrap()), player);
+    assert_eq!(energy_balance.balance(&player), 40);
+}
+
+#[test]
+#[should_panic(expected = "invalid_ingredients")]
+fn test_craft_requires_fungible_balance() {
+    let env = Env::default();
+    let (client, nft, player, _) = setup_craft(&env, 100, 0);
+    let energy = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    token::StellarAssetClient::new(&env, &energy).mint(&player, &10);
+
+    let ingredients = vec![
+        &env,
+        Ingredient {
+            token_address: energy,
+            token_id: 0,
+            amount: 40,
+            kind: IngredientKind::Fungible,
+        },
+    ];
+    let recipe_id = client.register_recipe(
+        &String::from_str(&env, "Charged Shard"),
+        &String::from_str(&env, "Costs energy"),
+        &ingredients,
+        &nft.address,
+        &100,
+        &100,
+        &1,
+        &0,
+    );
+
+    client.craft(&player, &recipe_id);
+}
+
+#[test]
+fn test_craft_needs_burn_approval() {
+    let env = Env::default();
+    let (client, nft, player, recipe_id) = setup_craft(&env, 100, 0);
+    nft.approve_for_all(&player, &client.address, &0);
+
+    assert!(client.try_craft(&player, &recipe_id).is_err());
+    assert_eq!(nft.owner_of(&1), player);
+}
+
+#[test]
+fn test_loot_table_mints_weighted_outputs() {
+    let env = Env::default();
+    let (client, nft, player, recipe_id) = setup_craft(&env, 100, 0);
+    nft.authorize_minter(&client.address, &vec![&env, 100u32, 200u32], &None);
+
+    let table = vec![
+        &env,
+        LootEntry {
+            token_address: nft.address.clone(),
+            token_id: 100,
+            rarity: Rarity::Common,
+            weight: 0,
+        },
+        LootEntry {
+            token_address: nft.address.clone(),
+            token_id: 200,
+            rarity: Rarity::Legendary,
+            weight: 5,
+        },
+    ];
+    client.set_loot_table(&recipe_id, &table, &3);
+
+    let outputs = client.craft(&player, &recipe_id);
+    assert_eq!(outputs.len(), 3);
+    for output_id in outputs.iter() {
+        assert_eq!(nft.owner_of(&output_id), player);
+        // A zero-weight entry is never drawn
+        assert_eq!(nft.get_achievement(&output_id).unwrap().puzzle_id, 200);
+    }
+    assert_eq!(client.get_craft_log(&player).get(0).unwrap().output_ids, outputs);
+}
+
+#[test]
+#[should_panic(expected = "Loot table needs a positive weight")]
+fn test_loot_table_rejects_zero_weights() {
+    let env = Env::default();
+    let (client, nft, _player, recipe_id) = setup_craft(&env, 100, 0);
+
+    let table = vec![
+        &env,
+        LootEntry {
+            token_address: nft.address.clone(),
+            token_id: 100,
+            rarity: Rarity::Common,
+            weight: 0,
+        },
+    ];
+    client.set_loot_table(&recipe_id, &table, &1);
+}
+
+#[test]
+fn test_battle_pass_level_gate() {
+    let env = Env::default();
+    let (client, _nft, player, recipe_id) = setup_craft(&env, 100, 0);
+    let pass = MockBattlePassClient::new(&env, &env.register_contract(None, MockBattlePass));
+    client.set_level_requirement(
+        &recipe_id,
+        &Some(LevelRequirement {
+            source: LevelSource::BattlePass,
+            contract: pass.address.clone(),
+            min_level: 5,
+        }),
+    );
+
+    // No pass at all counts as level 0
+    assert!(client.try_craft(&player, &recipe_id).is_err());
+    pass.set_level(&player, &4);
+    assert!(client.try_craft(&player, &recipe_id).is_err());
+
+    pass.set_level(&player, &5);
+    assert_eq!(client.craft(&player, &recipe_id).len(), 1);
+}
+
+#[test]
+fn test_dynamic_nft_level_gate() {
+    let env = Env::default();
+    let (client, _nft, player, recipe_id) = setup_craft(&env, 100, 0);
+    let levels = MockDynamicNftClient::new(&env, &env.register_contract(None, MockDynamicNft));
+    levels.set_nft(&1, &player, &7);
+    levels.set_nft(&2, &Address::generate(&env), &9);
+    client.set_level_requirement(
+        &recipe_id,
+        &Some(LevelRequirement {
+            source: LevelSource::DynamicNft,
+            contract: levels.address.clone(),
+            min_level: 7,
+        }),
+    );
+
+    // The level token must be supplied and owned by the player
+    assert!(client.try_craft(&player, &recipe_id).is_err());
+    assert!(client.try_craft_with_level_token(&player, &recipe_id, &2).is_err());
+
+    assert_eq!(client.craft_with_level_token(&player, &recipe_id, &1).len(), 1);
+
+    client.set_level_requirement(&recipe_id, &None);
+    assert!(client.get_level_requirement(&recipe_id).is_none());
+}
+
+#[test]
+fn test_legacy_recipe_upgraded_on_read() {
+    let env = Env::default();
+    let (client, nft, player, _) = setup_craft(&env, 100, 0);
+    let legacy = LegacyRecipe {
+        id: 7,
+        name: String::from_str(&env, "Old Shield"),
+        description: String::from_str(&env, "Stored before ingredient kinds"),
+        ingredients: vec![
+            &env,
+            LegacyIngredient { token_address: nft.address.clone(), token_id: 1, amount: 1 },
+        ],
+        output_token_address: nft.address.clone(),
+        output_token_id: 100,
+        success_rate: 100,
+        rarity: Rarity::Rare,
+        cooldown_seconds: 0,
+        enabled: true,
+    };
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&DataKey::Recipe(7), &legacy);
+    });
+
+    let recipe = client.get_recipe(&7);
+    assert_eq!(recipe.ingredients.get(0).unwrap().kind, IngredientKind::Nft);
+    assert_eq!(recipe.pity_threshold, 0);
+    assert_eq!(recipe.refund_bps, 0);
+    assert_eq!(recipe.loot_table.len(), 0);
+    assert_eq!(recipe.loot_rolls, 1);
+
+    // Admin updates store the current shape; crafting works either way
+    client.set_failure_rules(&7, &3, &0);
+    assert_eq!(client.get_recipe(&7).pity_threshold, 3);
+    assert_eq!(client.craft(&player, &7).len(), 1);
+}
@@ -1,10 +1,26 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, contractevent, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractclient, contractevent, contractimpl, contracttype, Address, Env, Map,
+    String, Symbol, TryFromVal, Val, Vec,
+};
 
 #[contracttype]
 #[derive(Clone)]
 pub struct DynamicNft {
+    pub owner: Address,
+    pub level: u32,
+    pub rarity: u32,
+    pub traits: Map<Symbol, TraitValue>,
+    pub metadata: String,
+    pub history_len: u32,    // entries in the token's History log
+    pub minted_at: u64,
+}
+
+/// Token layout written before typed traits and the external history log.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyDynamicNft {
     pub owner: Address,
     pub level: u32,
     pub rarity: u32,
@@ -14,14 +30,165 @@ pub struct DynamicNft {
     pub minted_at: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraitValue {
+    Int(i128),
+    Text(String),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttributeKind {
+    Int = 0,
+    Text = 1,
+}
+
+/// Admin-defined attribute that traits are validated against.
+#[contracttype]
+#[derive(Clone)]
+pub struct AttributeSpec {
+    pub kind: AttributeKind,
+    pub min: i128,       // Int bounds, inclusive; ignored for Text
+    pub max: i128,
+    pub required: bool,  // must be present on mint
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AttributeDelta {
+    pub name: Symbol,
+    pub delta: i128,     // clamped to the attribute's bounds
+}
+
+/// Named evolution applied by verifiers, e.g. "speed +1".
+#[contracttype]
+#[derive(Clone)]
+pub struct EvolutionRule {
+    pub level_inc: u32,
+    pub rarity_inc: u32,
+    pub deltas: Vec<AttributeDelta>,
+}
+
+/// Which parent's traits a fused token inherits.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TraitInheritance {
+    First = 0,    // traits of token_a
+    Second = 1,   // traits of token_b
+    Dominant = 2, // traits of the higher rarity parent, then the higher level, then token_a
+    Merge = 3,    // union of both; the dominant parent wins Text clashes, Int clashes keep the max
+}
+
+/// Admin-registered fusion outcome for inputs of a given rarity.
+#[contracttype]
+#[derive(Clone)]
+pub struct FusionRule {
+    pub min_level: u32,              // both inputs must reach this level
+    pub success_bps: u32,            // chance of success out of 10_000
+    pub rarity_bonus: u32,           // added to the higher input rarity
+    pub rarity_cap: u32,             // 0 = uncapped
+    pub level_cap: u32,              // cap on the summed level, 0 = uncapped
+    pub traits: TraitInheritance,
+    pub burn_on_failure: bool,       // failed fusions destroy both inputs; required below 10_000 bps
+}
+
+/// Condition read from a linked game contract for the token's owner.
+#[contracttype]
+#[derive(Clone)]
+pub enum AutoTrigger {
+    SkillTier(Address, SkillTier), // skill_rating tier reached
+    ChainCompleted(Address, u32),  // quest_chain chain completed
+    DailyInactive(Address, u32),   // no daily_rewards claim for this many ledgers
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum AutoAction {
+    Evolve(Symbol),   // apply a registered EvolutionRule
+    Downgrade(u32),   // level decrease
+}
+
+/// Subscription rule applied by `sync_token`. Tier and chain triggers fire once
+/// per token; inactivity fires again after every further inactive period.
+#[contracttype]
+#[derive(Clone)]
+pub struct AutoRule {
+    pub trigger: AutoTrigger,
+    pub action: AutoAction,
+}
+
+// Mirrors of the linked contracts' types, matched field for field.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SkillTier {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Diamond,
+    Master,
+    Grandmaster,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PlayerProgress {
+    pub player: Address,
+    pub chain_id: u32,
+    pub completed_quests: Vec<u32>,
+    pub current_quest: Option<u32>,
+    pub checkpoint_quest: Option<u32>,
+    pub start_time: u64,
+    pub completion_time: Option<u64>,
+    pub total_reward_earned: i128,
+    pub path_taken: Vec<u32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct UserStreak {
+    pub current_streak: u32,
+    pub last_claim_ledger: u32,
+    pub total_logins: u32,
+    pub last_claim_hash: u64,
+}
+
+#[contractclient(name = "SkillRatingClient")]
+pub trait SkillRatingInterface {
+    fn get_tier(env: Env, player: Address) -> (SkillTier, u32);
+}
+
+#[contractclient(name = "QuestChainClient")]
+pub trait QuestChainInterface {
+    fn get_player_progress(env: Env, player: Address, chain_id: u32) -> Option<PlayerProgress>;
+}
+
+#[contractclient(name = "DailyRewardsClient")]
+pub trait DailyRewardsInterface {
+    fn get_streak(env: Env, user: Address) -> UserStreak;
+}
+
 #[contracttype]
 pub enum DataKey {
     Admin(Address),
     Verifier(Address),
     DynamicNft(u32),
     NextNftId,
+    FusionRule(u32),   // keyed on the higher input rarity
+    Parents(u32),      // (token_a, token_b) of a fused token, kept after the parents burn
+    Schema,            // Map<Symbol, AttributeSpec>
+    EvolutionRule(Symbol),
+    History(u32, u32), // (token_id, index) -> entry, kept after the token burns
+    AutoRules,         // Map<u32, AutoRule>
+    AutoApplied(u32, u32), // (token_id, rule_id) -> ledger the rule last fired
+    SyncSince(u32),    // ledger the token's inactivity clock starts from
+    RetiredAutoRule(u32), // removed rule ids, never reused so AutoApplied entries stay valid
 }
 
+const MAX_HISTORY_PAGE: u32 = 50;
+const MAX_AUTO_RULES: u32 = 20;
+
 #[contract]
 pub struct DynamicNftContract;
 
@@ -57,6 +224,22 @@ pub struct FuseEvent {
     pub token_id: u32,
 }
 
+#[contractevent]
+#[derive(Clone)]
+pub struct AutoSyncEvent {
+    pub token_id: u32,
+    pub rule_id: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct FuseFailedEvent {
+    pub owner: Address,
+    pub token_a: u32,
+    pub token_b: u32,
+    pub burned: bool,
+}
+
 #[contractimpl]
 impl DynamicNftContract {
     pub fn initialize(env: Env, admin: Address) {
@@ -82,8 +265,9 @@ impl DynamicNftContract {
         env.storage().persistent().remove(&DataKey::Verifier(verifier));
     }
 
-    pub fn mint(env: Env, minter: Address, owner: Address, metadata: String, traits: String) -> u32 {
+    pub fn mint(env: Env, minter: Address, owner: Address, metadata: String, traits: Map<Symbol, TraitValue>) -> u32 {
         minter.require_auth();
+        Self::validate_traits(&env, &traits);
 
         let next: u32 = env.storage().persistent().get(&DataKey::NextNftId).unwrap();
         let nft = DynamicNft {
@@ -92,29 +276,29 @@ impl DynamicNftContract {
             rarity: 1,
             traits: traits.clone(),
             metadata: metadata.clone(),
-            history: Vec::new(&env),
+            history_len: 0,
             minted_at: env.ledger().timestamp(),
         };
         env.storage().persistent().set(&DataKey::DynamicNft(next), &nft);
+        env.storage().persistent().set(&DataKey::SyncSince(next), &env.ledger().sequence());
         env.storage().persistent().set(&DataKey::NextNftId, &(next + 1));
         env.events().publish_event(&MintEvent { owner: owner.clone(), token_id: next });
         next
     }
 
     // evolve by milestone (admin or verifier in governance)
-    pub fn evolve_milestone(env: Env, submitter: Address, token_id: u32, level_inc: u32, rarity_inc: u32, new_traits: Option<String>) {
+    pub fn evolve_milestone(env: Env, submitter: Address, token_id: u32, level_inc: u32, rarity_inc: u32, new_traits: Option<Map<Symbol, TraitValue>>) {
         submitter.require_auth();
         Self::assert_admin_or_verifier(&env, &submitter);
         let mut nft: DynamicNft = env.storage().persistent().get(&DataKey::DynamicNft(token_id)).unwrap();
         nft.level = nft.level.saturating_add(level_inc);
         nft.rarity = nft.rarity.saturating_add(rarity_inc);
         if let Some(t) = new_traits {
+            Self::validate_traits(&env, &t);
             nft.traits = t;
         }
         // record evolution event in history
-        let mut hist = nft.history.clone();
-        hist.push_back(String::from_str(&env, "evolved_milestone"));
-        nft.history = hist;
+        Self::push_history(&env, token_id, &mut nft, String::from_str(&env, "evolved_milestone"));
         env.storage().persistent().set(&DataKey::DynamicNft(token_id), &nft);
         env.events().publish_event(&EvolveMilestoneEvent { token_id });
     }
@@ -129,9 +313,7 @@ impl DynamicNftContract {
         }
         nft.level = nft.level.saturating_add(1);
         // record time evolution in history
-        let mut hist = nft.history.clone();
-        hist.push_back(String::from_str(&env, "time_evolved"));
-        nft.history = hist;
+        Self::push_history(&env, token_id, &mut nft, String::from_str(&env, "time_evolved"));
         nft.minted_at = now; // reset timer for further evolutions
         env.storage().persistent().set(&DataKey::DynamicNft(token_id), &nft);
         env.events().publish_event(&EvolveTimeEvent { token_id });
@@ -142,16 +324,43 @@ impl DynamicNftContract {
         Self::assert_admin_or_verifier(&env, &submitter);
         let mut nft: DynamicNft = env.storage().persistent().get(&DataKey::DynamicNft(token_id)).unwrap();
         nft.level = nft.level.saturating_sub(level_dec);
-        let mut hist = nft.history.clone();
-        hist.push_back(String::from_str(&env, "downgraded"));
-        nft.history = hist;
+        Self::push_history(&env, token_id, &mut nft, String::from_str(&env, "downgraded"));
         env.storage().persistent().set(&DataKey::DynamicNft(token_id), &nft);
         env.events().publish_event(&DowngradeEvent { token_id });
     }
 
-    // fuse two NFTs into a new one; owner must be same for both
-    pub fn fuse(env: Env, submitter: Address, token_a: u32, token_b: u32) -> u32 {
+    pub fn set_fusion_rule(env: Env, admin: Address, rarity: u32, rule: FusionRule) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+        if rule.success_bps > 10_000 {
+            panic!("Invalid success chance");
+        }
+        // a failed roll must cost something, or owners retry until it succeeds
+        if rule.success_bps < 10_000 && !rule.burn_on_failure {
+            panic!("Failed fusions must burn inputs");
+        }
+        env.storage().persistent().set(&DataKey::FusionRule(rarity), &rule);
+    }
+
+    pub fn remove_fusion_rule(env: Env, admin: Address, rarity: u32) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+        env.storage().persistent().remove(&DataKey::FusionRule(rarity));
+    }
+
+    pub fn get_fusion_rule(env: Env, rarity: u32) -> Option<FusionRule> {
+        env.storage().persistent().get(&DataKey::FusionRule(rarity))
+    }
+
+    // fuse two NFTs into a new one; owner must be same for both.
+    // The rule registered for the higher input rarity decides the outcome;
+    // without one the level is summed, rarity is max + 1 and token_a's traits are kept.
+    // Returns None when the fusion roll fails.
+    pub fn fuse(env: Env, submitter: Address, token_a: u32, token_b: u32) -> Option<u32> {
         submitter.require_auth();
+        if token_a == token_b {
+            panic!("Cannot fuse a token with itself");
+        }
         let nft_a: DynamicNft = env.storage().persistent().get(&DataKey::DynamicNft(token_a)).unwrap();
         let nft_b: DynamicNft = env.storage().persistent().get(&DataKey::DynamicNft(token_b)).unwrap();
         if nft_a.owner != nft_b.owner {
@@ -161,11 +370,54 @@ impl DynamicNftContract {
         if submitter != nft_a.owner {
             panic!("Only owner can fuse tokens");
         }
-        // create fused NFT: summed level, higher rarity, combined traits
+
+        let top_rarity = nft_a.rarity.max(nft_b.rarity);
+        let rule: FusionRule = Self::get_fusion_rule(env.clone(), top_rarity).unwrap_or(FusionRule {
+            min_level: 0,
+            success_bps: 10_000,
+            rarity_bonus: 1,
+            rarity_cap: 0,
+            level_cap: 0,
+            traits: TraitInheritance::First,
+            burn_on_failure: false,
+        });
+        if nft_a.level < rule.min_level || nft_b.level < rule.min_level {
+            panic!("Level too low to fuse");
+        }
+
         let owner = nft_a.owner.clone();
-        let fused_level = nft_a.level.saturating_add(nft_b.level);
-        let fused_rarity = if nft_a.rarity > nft_b.rarity { nft_a.rarity } else { nft_b.rarity } + 1u32;
-        let combined_traits = nft_a.traits.clone();
+        if rule.success_bps < 10_000 && env.prng().gen_range::<u64>(0..10_000) >= rule.success_bps as u64 {
+            if rule.burn_on_failure {
+                env.storage().persistent().remove(&DataKey::DynamicNft(token_a));
+                env.storage().persistent().remove(&DataKey::DynamicNft(token_b));
+            }
+            env.events().publish_event(&FuseFailedEvent {
+                owner,
+                token_a,
+                token_b,
+                burned: rule.burn_on_failure,
+            });
+            return None;
+        }
+
+        let mut fused_level = nft_a.level.saturating_add(nft_b.level);
+        if rule.level_cap > 0 {
+            fused_level = fused_level.min(rule.level_cap);
+        }
+        let mut fused_rarity = top_rarity.saturating_add(rule.rarity_bonus);
+        if rule.rarity_cap > 0 {
+            fused_rarity = fused_rarity.min(rule.rarity_cap);
+        }
+        let a_dominates = nft_a.rarity > nft_b.rarity
+            || (nft_a.rarity == nft_b.rarity && nft_a.level >= nft_b.level);
+        let fused_traits = match rule.traits {
+            TraitInheritance::First => nft_a.traits.clone(),
+            TraitInheritance::Second => nft_b.traits.clone(),
+            TraitInheritance::Dominant if a_dominates => nft_a.traits.clone(),
+            TraitInheritance::Dominant => nft_b.traits.clone(),
+            TraitInheritance::Merge if a_dominates => Self::merge_traits(&nft_a.traits, &nft_b.traits),
+            TraitInheritance::Merge => Self::merge_traits(&nft_b.traits, &nft_a.traits),
+        };
         let combined_metadata = nft_a.metadata.clone();
 
         // simple burn: remove old entries
@@ -173,37 +425,364 @@ impl DynamicNftContract {
         env.storage().persistent().remove(&DataKey::DynamicNft(token_b));
 
         let next: u32 = env.storage().persistent().get(&DataKey::NextNftId).unwrap();
-        let mut new_hist = Vec::new(&env);
-        new_hist.push_back(String::from_str(&env, "fused"));
-
-        let nft = DynamicNft {
+        let mut nft = DynamicNft {
             owner: owner.clone(),
             level: fused_level,
             rarity: fused_rarity,
-            traits: combined_traits,
+            traits: fused_traits,
             metadata: combined_metadata,
-            history: new_hist,
+            history_len: 0,
             minted_at: env.ledger().timestamp(),
         };
+        Self::push_history(&env, next, &mut nft, Self::fused_entry(&env, token_a, token_b));
         env.storage().persistent().set(&DataKey::DynamicNft(next), &nft);
+        env.storage().persistent().set(&DataKey::Parents(next), &(token_a, token_b));
+        env.storage().persistent().set(&DataKey::SyncSince(next), &env.ledger().sequence());
         env.storage().persistent().set(&DataKey::NextNftId, &(next + 1));
         env.events().publish_event(&FuseEvent { owner: owner.clone(), token_id: next });
-        next
+        Some(next)
+    }
+
+    // parents of a fused token; follow them recursively to trace the full lineage
+    pub fn get_parents(env: Env, token_id: u32) -> Option<(u32, u32)> {
+        env.storage().persistent().get(&DataKey::Parents(token_id))
     }
 
     pub fn get_nft(env: Env, token_id: u32) -> Option<DynamicNft> {
         env.storage().persistent().get(&DataKey::DynamicNft(token_id))
     }
 
-    pub fn get_history(env: Env, token_id: u32) -> Option<Vec<String>> {
+    // rewrite a token stored in the legacy layout (admin only): `traits` replaces the
+    // free-form trait string and the inline history moves into the paginated log
+    pub fn migrate_token(env: Env, admin: Address, token_id: u32, traits: Map<Symbol, TraitValue>) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+        let key = DataKey::DynamicNft(token_id);
+        let raw: Val = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Token not found"));
+        let legacy = LegacyDynamicNft::try_from_val(&env, &raw)
+            .unwrap_or_else(|_| panic!("Token already migrated"));
+        Self::validate_traits(&env, &traits);
+
+        let mut nft = DynamicNft {
+            owner: legacy.owner,
+            level: legacy.level,
+            rarity: legacy.rarity,
+            traits,
+            metadata: legacy.metadata,
+            history_len: 0,
+            minted_at: legacy.minted_at,
+        };
+        for entry in legacy.history.iter() {
+            Self::push_history(&env, token_id, &mut nft, entry);
+        }
+        env.storage().persistent().set(&key, &nft);
+    }
+
+    // owner and level only, for contracts gating on level without mirroring DynamicNft
+    pub fn get_level(env: Env, token_id: u32) -> (Address, u32) {
+        let nft: DynamicNft = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DynamicNft(token_id))
+            .unwrap_or_else(|| panic!("Token not found"));
+        (nft.owner, nft.level)
+    }
+
+    // up to `limit` (max 50) history entries starting at `start`, oldest first
+    pub fn get_history(env: Env, token_id: u32, start: u32, limit: u32) -> Vec<String> {
+        let mut page = Vec::new(&env);
+        let end = start.saturating_add(limit.min(MAX_HISTORY_PAGE));
+        for index in start..end {
+            match env.storage().persistent().get(&DataKey::History(token_id, index)) {
+                Some(entry) => page.push_back(entry),
+                None => break,
+            }
+        }
+        page
+    }
+
+    pub fn get_history_len(env: Env, token_id: u32) -> u32 {
         let nft: Option<DynamicNft> = env.storage().persistent().get(&DataKey::DynamicNft(token_id));
-        match nft {
-            Some(n) => Some(n.history),
-            None => None,
+        nft.map(|n| n.history_len).unwrap_or(0)
+    }
+
+    pub fn set_attribute(env: Env, admin: Address, name: Symbol, spec: AttributeSpec) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+        if spec.kind == AttributeKind::Int && spec.min > spec.max {
+            panic!("Invalid attribute bounds");
+        }
+        let mut schema = Self::get_schema(env.clone());
+        schema.set(name, spec);
+        env.storage().persistent().set(&DataKey::Schema, &schema);
+    }
+
+    pub fn remove_attribute(env: Env, admin: Address, name: Symbol) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+        let mut schema = Self::get_schema(env.clone());
+        schema.remove(name);
+        env.storage().persistent().set(&DataKey::Schema, &schema);
+    }
+
+    pub fn get_schema(env: Env) -> Map<Symbol, AttributeSpec> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Schema)
+            .unwrap_or(Map::new(&env))
+    }
+
+    pub fn set_evolution_rule(env: Env, admin: Address, rule_id: Symbol, rule: EvolutionRule) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+        let schema = Self::get_schema(env.clone());
+        for delta in rule.deltas.iter() {
+            match schema.get(delta.name) {
+                Some(spec) if spec.kind == AttributeKind::Int => {}
+                Some(_) => panic!("Attribute is not numeric"),
+                None => panic!("Unknown attribute"),
+            }
+        }
+        env.storage().persistent().set(&DataKey::EvolutionRule(rule_id), &rule);
+    }
+
+    pub fn get_evolution_rule(env: Env, rule_id: Symbol) -> Option<EvolutionRule> {
+        env.storage().persistent().get(&DataKey::EvolutionRule(rule_id))
+    }
+
+    // apply a registered evolution rule (admin or verifier)
+    pub fn apply_evolution(env: Env, submitter: Address, token_id: u32, rule_id: Symbol) {
+        submitter.require_auth();
+        Self::assert_admin_or_verifier(&env, &submitter);
+        let mut nft: DynamicNft = env.storage().persistent().get(&DataKey::DynamicNft(token_id)).unwrap();
+        Self::evolve_with_rule(&env, token_id, &mut nft, rule_id);
+        env.storage().persistent().set(&DataKey::DynamicNft(token_id), &nft);
+        env.events().publish_event(&EvolveMilestoneEvent { token_id });
+    }
+
+    pub fn set_auto_rule(env: Env, admin: Address, rule_id: u32, rule: AutoRule) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+        if let AutoAction::Evolve(evolution) = rule.action.clone() {
+            if Self::get_evolution_rule(env.clone(), evolution).is_none() {
+                panic!("Unknown evolution rule");
+            }
+        }
+        if env.storage().persistent().has(&DataKey::RetiredAutoRule(rule_id)) {
+            panic!("Auto rule id retired");
+        }
+        let mut rules = Self::get_auto_rules(env.clone());
+        if !rules.contains_key(rule_id) && rules.len() >= MAX_AUTO_RULES {
+            panic!("Too many auto rules");
+        }
+        rules.set(rule_id, rule);
+        env.storage().persistent().set(&DataKey::AutoRules, &rules);
+    }
+
+    pub fn remove_auto_rule(env: Env, admin: Address, rule_id: u32) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+        let mut rules = Self::get_auto_rules(env.clone());
+        if rules.remove(rule_id).is_none() {
+            panic!("Unknown auto rule");
+        }
+        env.storage().persistent().set(&DataKey::AutoRules, &rules);
+        // tokens keep their AutoApplied entries, so a new rule needs a new id
+        env.storage().persistent().set(&DataKey::RetiredAutoRule(rule_id), &true);
+    }
+
+    pub fn get_auto_rules(env: Env) -> Map<u32, AutoRule> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AutoRules)
+            .unwrap_or(Map::new(&env))
+    }
+
+    // callable by anyone: reads the linked contracts for the owner and applies
+    // every due auto rule in rule id order; returns how many were applied
+    pub fn sync_token(env: Env, token_id: u32) -> u32 {
+        let mut nft: DynamicNft = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DynamicNft(token_id))
+            .unwrap_or_else(|| panic!("Token not found"));
+        let now = env.ledger().sequence();
+        let since_key = DataKey::SyncSince(token_id);
+        // tokens minted before auto rules existed start their inactivity clock here
+        let since: u32 = match env.storage().persistent().get(&since_key) {
+            Some(since) => since,
+            None => {
+                env.storage().persistent().set(&since_key, &now);
+                now
+            }
+        };
+
+        let mut applied = 0u32;
+        for (rule_id, rule) in Self::get_auto_rules(env.clone()).iter() {
+            let applied_key = DataKey::AutoApplied(token_id, rule_id);
+            let last_applied: Option<u32> = env.storage().persistent().get(&applied_key);
+            let due = match rule.trigger {
+                AutoTrigger::SkillTier(contract, min_tier) => {
+                    last_applied.is_none()
+                        && matches!(
+                            SkillRatingClient::new(&env, &contract).try_get_tier(&nft.owner),
+                            Ok(Ok((tier, _))) if tier >= min_tier
+                        )
+                }
+                AutoTrigger::ChainCompleted(contract, chain_id) => {
+                    last_applied.is_none()
+                        && matches!(
+                            QuestChainClient::new(&env, &contract)
+                                .try_get_player_progress(&nft.owner, &chain_id),
+                            Ok(Ok(Some(progress))) if progress.completion_time.is_some()
+                        )
+                }
+                AutoTrigger::DailyInactive(contract, ledgers) => {
+                    // a failing or unreadable contract counts as not due
+                    match DailyRewardsClient::new(&env, &contract).try_get_streak(&nft.owner) {
+                        Ok(Ok(streak)) => {
+                            let active_at = since
+                                .max(streak.last_claim_ledger)
+                                .max(last_applied.unwrap_or(0));
+                            now.saturating_sub(active_at) >= ledgers
+                        }
+                        _ => false,
+                    }
+                }
+            };
+            if !due {
+                continue;
+            }
+
+            match rule.action {
+                AutoAction::Evolve(evolution) => {
+                    Self::evolve_with_rule(&env, token_id, &mut nft, evolution)
+                }
+                AutoAction::Downgrade(level_dec) => {
+                    nft.level = nft.level.saturating_sub(level_dec);
+                    Self::push_history(&env, token_id, &mut nft, String::from_str(&env, "downgraded"));
+                }
+            }
+            env.storage().persistent().set(&applied_key, &now);
+            env.events().publish_event(&AutoSyncEvent { token_id, rule_id });
+            applied += 1;
+        }
+
+        if applied > 0 {
+            env.storage().persistent().set(&DataKey::DynamicNft(token_id), &nft);
         }
+        applied
     }
 
     // helpers
+    fn evolve_with_rule(env: &Env, token_id: u32, nft: &mut DynamicNft, rule_id: Symbol) {
+        let rule = Self::get_evolution_rule(env.clone(), rule_id)
+            .unwrap_or_else(|| panic!("Unknown evolution rule"));
+        nft.level = nft.level.saturating_add(rule.level_inc);
+        nft.rarity = nft.rarity.saturating_add(rule.rarity_inc);
+
+        let schema = Self::get_schema(env.clone());
+        for delta in rule.deltas.iter() {
+            // attributes dropped from the schema since the rule was registered are skipped
+            let spec = match schema.get(delta.name.clone()) {
+                Some(spec) => spec,
+                None => continue,
+            };
+            let current = match nft.traits.get(delta.name.clone()) {
+                Some(TraitValue::Int(v)) => v,
+                Some(TraitValue::Text(_)) => panic!("Attribute is not numeric"),
+                None => 0,
+            };
+            let updated = current.saturating_add(delta.delta).clamp(spec.min, spec.max);
+            nft.traits.set(delta.name, TraitValue::Int(updated));
+        }
+
+        Self::push_history(env, token_id, nft, String::from_str(env, "evolved_rule"));
+    }
+
+    fn validate_traits(env: &Env, traits: &Map<Symbol, TraitValue>) {
+        let schema = Self::get_schema(env.clone());
+        for (name, value) in traits.iter() {
+            let spec = schema.get(name).unwrap_or_else(|| panic!("Unknown attribute"));
+            match value {
+                TraitValue::Int(v) => {
+                    if spec.kind != AttributeKind::Int {
+                        panic!("Attribute type mismatch");
+                    }
+                    if v < spec.min || v > spec.max {
+                        panic!("Attribute out of range");
+                    }
+                }
+                TraitValue::Text(_) => {
+                    if spec.kind != AttributeKind::Text {
+                        panic!("Attribute type mismatch");
+                    }
+                }
+            }
+        }
+        for (name, spec) in schema.iter() {
+            if spec.required && !traits.contains_key(name) {
+                panic!("Missing required attribute");
+            }
+        }
+    }
+
+    // `primary` wins Text clashes; Int clashes keep the larger value
+    fn merge_traits(primary: &Map<Symbol, TraitValue>, secondary: &Map<Symbol, TraitValue>) -> Map<Symbol, TraitValue> {
+        let mut merged = primary.clone();
+        for (name, value) in secondary.iter() {
+            match (merged.get(name.clone()), value) {
+                (None, value) => merged.set(name, value),
+                (Some(TraitValue::Int(a)), TraitValue::Int(b)) if b > a => {
+                    merged.set(name, TraitValue::Int(b))
+                }
+                _ => {}
+            }
+        }
+        merged
+    }
+
+    fn push_history(env: &Env, token_id: u32, nft: &mut DynamicNft, entry: String) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::History(token_id, nft.history_len), &entry);
+        nft.history_len += 1;
+    }
+
+    // "fused:<token_a>:<token_b>"
+    fn fused_entry(env: &Env, token_a: u32, token_b: u32) -> String {
+        let mut buf = [0u8; 32];
+        let prefix = b"fused:";
+        buf[..prefix.len()].copy_from_slice(prefix);
+        let mut len = prefix.len();
+        len = Self::write_decimal(&mut buf, len, token_a);
+        buf[len] = b':';
+        len = Self::write_decimal(&mut buf, len + 1, token_b);
+        String::from_bytes(env, &buf[..len])
+    }
+
+    fn write_decimal(buf: &mut [u8], start: usize, value: u32) -> usize {
+        let mut digits = [0u8; 10];
+        let mut n = value;
+        let mut count = 0;
+        loop {
+            digits[count] = b'0' + (n % 10) as u8;
+            count += 1;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        for i in 0..count {
+            buf[start + i] = digits[count - 1 - i];
+        }
+        start + count
+    }
+
     fn assert_admin(env: &Env, admin: &Address) {
         let is_admin: bool = env.storage().persistent().get(&DataKey::Admin(admin.clone())).unwrap_or(false);
         if !is_admin {
@@ -223,3 +802,6 @@ impl DynamicNftContract {
         }
     }
 }
+
+#[cfg(test)]
+mod test;
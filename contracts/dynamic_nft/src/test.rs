@@ -1,66 +1,216 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+};
+
+#[contracttype]
+enum MockKey {
+    Tier(Address),
+    Completed(Address, u32),
+    LastClaim(Address),
+}
+
+#[contract]
+pub struct MockSkillRating;
+
+#[contractimpl]
+impl MockSkillRating {
+    pub fn set_tier(env: Env, player: Address, tier: SkillTier) {
+        env.storage().instance().set(&MockKey::Tier(player), &tier);
+    }
+
+    pub fn get_tier(env: Env, player: Address) -> (SkillTier, u32) {
+        let tier: SkillTier = env
+            .storage()
+            .instance()
+            .get(&MockKey::Tier(player))
+            .unwrap_or_else(|| panic!("no rating"));
+        (tier, 0)
+    }
+}
+
+#[contract]
+pub struct MockQuestChain;
+
+#[contractimpl]
+impl MockQuestChain {
+    pub fn complete(env: Env, player: Address, chain_id: u32) {
+        env.storage().instance().set(&MockKey::Completed(player, chain_id), &true);
+    }
+
+    pub fn get_player_progress(env: Env, player: Address, chain_id: u32) -> Option<PlayerProgress> {
+        let completed: bool = env
+            .storage()
+            .instance()
+            .get(&MockKey::Completed(player.clone(), chain_id))
+            .unwrap_or(false);
+        Some(PlayerProgress {
+            player,
+            chain_id,
+            completed_quests: Vec::new(&env),
+            current_quest: None,
+            checkpoint_quest: None,
+            start_time: 0,
+            completion_time: if completed { Some(1) } else { None },
+            total_reward_earned: 0,
+            path_taken: Vec::new(&env),
+        })
+    }
+}
+
+#[contract]
+pub struct MockDailyRewards;
+
+#[contractimpl]
+impl MockDailyRewards {
+    pub fn claim(env: Env, user: Address) {
+        let ledger = env.ledger().sequence();
+        env.storage().instance().set(&MockKey::LastClaim(user), &ledger);
+    }
+
+    pub fn get_streak(env: Env, user: Address) -> UserStreak {
+        let last: u32 = env.storage().instance().get(&MockKey::LastClaim(user)).unwrap_or(0);
+        UserStreak {
+            current_streak: 0,
+            last_claim_ledger: last,
+            total_logins: 0,
+            last_claim_hash: 0,
+        }
+    }
+}
 
 fn setup() -> (Env, Address, Address, DynamicNftContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register_contract(None, DynamicNftContract);
+    let contract_id = env.register(DynamicNftContract, ());
     let client = DynamicNftContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
     let user = Address::generate(&env);
 
     client.initialize(&admin);
+    client.set_attribute(
+        &admin,
+        &symbol_short!("name"),
+        &AttributeSpec { kind: AttributeKind::Text, min: 0, max: 0, required: true },
+    );
+    client.set_attribute(
+        &admin,
+        &symbol_short!("speed"),
+        &AttributeSpec { kind: AttributeKind::Int, min: 0, max: 5, required: false },
+    );
 
     (env, admin, user, client)
 }
 
+fn named(env: &Env, name: &str) -> Map<Symbol, TraitValue> {
+    let mut traits = Map::new(env);
+    traits.set(symbol_short!("name"), TraitValue::Text(String::from_str(env, name)));
+    traits
+}
+
 #[test]
 fn mint_and_get() {
     let (env, _admin, user, client) = setup();
     env.ledger().set_timestamp(100);
-    let token = client.mint(&user, &user, &String::from_str(&env, "meta_v1"), &String::from_str(&env, "traitA"));
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta_v1"), &named(&env, "traitA"));
     assert_eq!(token, 1);
 
     let nft = client.get_nft(&token).unwrap();
     assert_eq!(nft.owner, user);
     assert_eq!(nft.level, 1);
     // no history on fresh mint
-    let history = client.get_history(&token).unwrap();
+    let history = client.get_history(&token, &0, &10);
     assert_eq!(history.len(), 0);
 }
 
+#[test]
+fn migrate_legacy_token() {
+    let (env, admin, user, client) = setup();
+    let history = Vec::from_array(
+        &env,
+        [String::from_str(&env, "evolved_milestone"), String::from_str(&env, "time_evolved")],
+    );
+    let legacy = LegacyDynamicNft {
+        owner: user.clone(),
+        level: 3,
+        rarity: 2,
+        traits: String::from_str(&env, "color:red"),
+        metadata: String::from_str(&env, "meta"),
+        history: history.clone(),
+        minted_at: 50,
+    };
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&DataKey::DynamicNft(7), &legacy);
+    });
+
+    // the typed traits still have to match the schema
+    assert!(client
+        .try_migrate_token(&admin, &7, &Map::new(&env))
+        .is_err());
+    assert!(client
+        .try_migrate_token(&user, &7, &named(&env, "red"))
+        .is_err());
+
+    client.migrate_token(&admin, &7, &named(&env, "red"));
+    let nft = client.get_nft(&7).unwrap();
+    assert_eq!(nft.owner, user);
+    assert_eq!(nft.level, 3);
+    assert_eq!(nft.rarity, 2);
+    assert_eq!(nft.traits, named(&env, "red"));
+    assert_eq!(nft.minted_at, 50);
+    assert_eq!(client.get_history_len(&7), 2);
+    assert_eq!(client.get_history(&7, &0, &10), history);
+
+    // migrated tokens evolve normally and cannot be migrated twice
+    client.evolve_milestone(&admin, &7, &1u32, &0u32, &None);
+    assert_eq!(client.get_history_len(&7), 3);
+    assert!(client
+        .try_migrate_token(&admin, &7, &named(&env, "red"))
+        .is_err());
+}
+
+#[test]
+fn get_level_view() {
+    let (env, admin, user, client) = setup();
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "traitA"));
+    client.evolve_milestone(&admin, &token, &4u32, &0u32, &None);
+
+    assert_eq!(client.get_level(&token), (user, 5));
+    assert!(client.try_get_level(&99).is_err());
+}
+
 #[test]
 fn time_evolution_changes_level() {
     let (env, _admin, user, client) = setup();
     env.ledger().set_timestamp(100);
 
-    let token = client.mint(&user, &user, &String::from_str(&env, "meta_v1"), &String::from_str(&env, "traitA"));
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta_v1"), &named(&env, "traitA"));
 
     // not ready yet
-    let res = std::panic::catch_unwind(|| client.evolve_time(&user, &token, &10u64));
-    assert!(res.is_err());
+    assert!(client.try_evolve_time(&user, &token, &10u64).is_err());
 
     env.ledger().set_timestamp(200);
     client.evolve_time(&user, &token, &50u64);
     let nft = client.get_nft(&token).unwrap();
     assert_eq!(nft.level, 2);
-    let history = client.get_history(&token).unwrap();
+    let history = client.get_history(&token, &0, &10);
     assert_eq!(history.len(), 1);
-    assert_eq!(history.get(0).unwrap(), &String::from_str(&env, "time_evolved"));
+    assert_eq!(history.get(0).unwrap(), String::from_str(&env, "time_evolved"));
 }
 
 #[test]
 fn fuse_two_tokens() {
     let (env, _admin, user, client) = setup();
     env.ledger().set_timestamp(100);
-    let a = client.mint(&user, &user, &String::from_str(&env, "a"), &String::from_str(&env, "A"));
-    let b = client.mint(&user, &user, &String::from_str(&env, "b"), &String::from_str(&env, "B"));
+    let a = client.mint(&user, &user, &String::from_str(&env, "a"), &named(&env, "A"));
+    let b = client.mint(&user, &user, &String::from_str(&env, "b"), &named(&env, "B"));
 
-    let fused = client.fuse(&user, &a, &b);
+    let fused = client.fuse(&user, &a, &b).unwrap();
     assert_eq!(fused, 3);
     // originals should be removed
     let orig_a = client.get_nft(&a);
@@ -70,9 +220,10 @@ fn fuse_two_tokens() {
 
     let nft = client.get_nft(&fused).unwrap();
     assert_eq!(nft.level, 2);
-    let history = client.get_history(&fused).unwrap();
+    let history = client.get_history(&fused, &0, &10);
     assert_eq!(history.len(), 1);
-    assert_eq!(history.get(0).unwrap(), &String::from_str(&env, "fused"));
+    assert_eq!(history.get(0).unwrap(), String::from_str(&env, "fused:1:2"));
+    assert_eq!(client.get_parents(&fused), Some((a, b)));
 }
 
 #[test]
@@ -80,7 +231,7 @@ fn evolve_milestone_by_admin() {
     let (env, admin, user, client) = setup();
     env.ledger().set_timestamp(100);
 
-    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &String::from_str(&env, "trait1"));
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "trait1"));
     let nft_before = client.get_nft(&token).unwrap();
     assert_eq!(nft_before.level, 1);
     assert_eq!(nft_before.rarity, 1);
@@ -90,9 +241,9 @@ fn evolve_milestone_by_admin() {
     let nft_after = client.get_nft(&token).unwrap();
     assert_eq!(nft_after.level, 3);
     assert_eq!(nft_after.rarity, 2);
-    let history = client.get_history(&token).unwrap();
+    let history = client.get_history(&token, &0, &10);
     assert_eq!(history.len(), 1);
-    assert_eq!(history.get(0).unwrap(), &String::from_str(&env, "evolved_milestone"));
+    assert_eq!(history.get(0).unwrap(), String::from_str(&env, "evolved_milestone"));
 }
 
 #[test]
@@ -103,7 +254,7 @@ fn downgrade_by_verifier() {
     let verifier = Address::generate(&env);
     client.add_verifier(&admin, &verifier);
 
-    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &String::from_str(&env, "trait1"));
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "trait1"));
     // first evolve it
     client.evolve_milestone(&admin, &token, &3u32, &0u32, &None);
     let nft_evolved = client.get_nft(&token).unwrap();
@@ -113,8 +264,8 @@ fn downgrade_by_verifier() {
     client.downgrade(&verifier, &token, &2u32);
     let nft_downgraded = client.get_nft(&token).unwrap();
     assert_eq!(nft_downgraded.level, 2);
-    let history = client.get_history(&token).unwrap();
-    assert!(history.iter().any(|e| e == &String::from_str(&env, "downgraded")));
+    let history = client.get_history(&token, &0, &10);
+    assert!(history.iter().any(|e| e == String::from_str(&env, "downgraded")));
 }
 
 #[test]
@@ -123,7 +274,7 @@ fn evolve_milestone_requires_admin_or_verifier() {
     let (env, _admin, user, client) = setup();
     env.ledger().set_timestamp(100);
 
-    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &String::from_str(&env, "trait1"));
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "trait1"));
     // random user cannot evolve
     let random = Address::generate(&env);
     client.evolve_milestone(&random, &token, &1u32, &0u32, &None);
@@ -135,8 +286,8 @@ fn fuse_requires_owner() {
     let (env, _admin, user, client) = setup();
     env.ledger().set_timestamp(100);
 
-    let a = client.mint(&user, &user, &String::from_str(&env, "a"), &String::from_str(&env, "A"));
-    let b = client.mint(&user, &user, &String::from_str(&env, "b"), &String::from_str(&env, "B"));
+    let a = client.mint(&user, &user, &String::from_str(&env, "a"), &named(&env, "A"));
+    let b = client.mint(&user, &user, &String::from_str(&env, "b"), &named(&env, "B"));
 
     let attacker = Address::generate(&env);
     // attacker cannot fuse user's tokens
@@ -152,8 +303,323 @@ fn remove_verifier() {
     client.add_verifier(&admin, &verifier);
     client.remove_verifier(&admin, &verifier);
 
-    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &String::from_str(&env, "trait1"));
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "trait1"));
     // removed verifier cannot evolve
-    let res = std::panic::catch_unwind(|| client.evolve_milestone(&verifier, &token, &1u32, &0u32, &None));
-    assert!(res.is_err());
+    assert!(client
+        .try_evolve_milestone(&verifier, &token, &1u32, &0u32, &None)
+        .is_err());
+}
+
+fn fusion_rule(success_bps: u32, traits: TraitInheritance, burn_on_failure: bool) -> FusionRule {
+    FusionRule {
+        min_level: 2,
+        success_bps,
+        rarity_bonus: 2,
+        rarity_cap: 4,
+        level_cap: 5,
+        traits,
+        burn_on_failure,
+    }
+}
+
+#[test]
+fn fusion_rule_sets_caps_and_traits() {
+    let (env, admin, user, client) = setup();
+    client.set_fusion_rule(&admin, &3, &fusion_rule(10_000, TraitInheritance::Dominant, false));
+
+    let a = client.mint(&user, &user, &String::from_str(&env, "a"), &named(&env, "A"));
+    let b = client.mint(&user, &user, &String::from_str(&env, "b"), &named(&env, "B"));
+    client.evolve_milestone(&admin, &a, &2u32, &1u32, &None);
+    client.evolve_milestone(&admin, &b, &1u32, &2u32, &None);
+
+    // a: level 3 rarity 2, b: level 2 rarity 3 -> the rarity 3 rule applies
+    let fused = client.fuse(&user, &a, &b).unwrap();
+    let nft = client.get_nft(&fused).unwrap();
+    assert_eq!(nft.level, 5);
+    assert_eq!(nft.rarity, 4);
+    assert_eq!(nft.traits, named(&env, "B"));
+
+    // lineage survives further fusions
+    let c = client.mint(&user, &user, &String::from_str(&env, "c"), &named(&env, "C"));
+    let again = client.fuse(&user, &fused, &c).unwrap();
+    assert_eq!(client.get_parents(&again), Some((fused, c)));
+    assert_eq!(client.get_parents(&fused), Some((a, b)));
+    assert_eq!(
+        client.get_history(&again, &0, &10).get(0).unwrap(),
+        String::from_str(&env, "fused:3:4")
+    );
+}
+
+#[test]
+fn fusion_failure_can_burn_inputs() {
+    let (env, admin, user, client) = setup();
+    client.set_fusion_rule(&admin, &1, &fusion_rule(0, TraitInheritance::First, true));
+
+    let a = client.mint(&user, &user, &String::from_str(&env, "a"), &named(&env, "A"));
+    let b = client.mint(&user, &user, &String::from_str(&env, "b"), &named(&env, "B"));
+    client.evolve_milestone(&admin, &a, &1u32, &0u32, &None);
+    client.evolve_milestone(&admin, &b, &1u32, &0u32, &None);
+    assert_eq!(client.fuse(&user, &a, &b), None);
+    assert!(client.get_nft(&a).is_none());
+    assert!(client.get_nft(&b).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Failed fusions must burn inputs")]
+fn fusion_rule_with_free_failure_rejected() {
+    let (_env, admin, _user, client) = setup();
+    client.set_fusion_rule(&admin, &1, &fusion_rule(5_000, TraitInheritance::First, false));
+}
+
+#[test]
+#[should_panic(expected = "Level too low to fuse")]
+fn fusion_rule_requires_min_level() {
+    let (env, admin, user, client) = setup();
+    client.set_fusion_rule(&admin, &1, &fusion_rule(10_000, TraitInheritance::First, false));
+
+    let a = client.mint(&user, &user, &String::from_str(&env, "a"), &named(&env, "A"));
+    let b = client.mint(&user, &user, &String::from_str(&env, "b"), &named(&env, "B"));
+    client.fuse(&user, &a, &b);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn fusion_rule_requires_admin() {
+    let (_env, _admin, user, client) = setup();
+    client.set_fusion_rule(&user, &1, &fusion_rule(10_000, TraitInheritance::First, false));
+}
+
+#[test]
+fn merge_fusion_combines_attributes() {
+    let (env, admin, user, client) = setup();
+    let mut rule = fusion_rule(10_000, TraitInheritance::Merge, false);
+    rule.min_level = 0;
+    client.set_fusion_rule(&admin, &1, &rule);
+
+    let mut fast = named(&env, "A");
+    fast.set(symbol_short!("speed"), TraitValue::Int(4));
+    let a = client.mint(&user, &user, &String::from_str(&env, "a"), &named(&env, "A"));
+    let b = client.mint(&user, &user, &String::from_str(&env, "b"), &fast);
+    client.evolve_milestone(&admin, &a, &1u32, &0u32, &None);
+
+    // a dominates on level and keeps its name; b contributes its speed
+    let fused = client.fuse(&user, &a, &b).unwrap();
+    let traits = client.get_nft(&fused).unwrap().traits;
+    assert_eq!(traits.get(symbol_short!("name")), Some(TraitValue::Text(String::from_str(&env, "A"))));
+    assert_eq!(traits.get(symbol_short!("speed")), Some(TraitValue::Int(4)));
+}
+
+#[test]
+fn traits_are_validated_against_schema() {
+    let (env, admin, user, client) = setup();
+    let meta = String::from_str(&env, "meta");
+
+    // missing the required name
+    assert!(client.try_mint(&user, &user, &meta, &Map::new(&env)).is_err());
+
+    let mut unknown = named(&env, "A");
+    unknown.set(symbol_short!("power"), TraitValue::Int(1));
+    assert!(client.try_mint(&user, &user, &meta, &unknown).is_err());
+
+    let mut wrong_type = named(&env, "A");
+    wrong_type.set(symbol_short!("speed"), TraitValue::Text(String::from_str(&env, "fast")));
+    assert!(client.try_mint(&user, &user, &meta, &wrong_type).is_err());
+
+    let mut too_fast = named(&env, "A");
+    too_fast.set(symbol_short!("speed"), TraitValue::Int(6));
+    assert!(client.try_mint(&user, &user, &meta, &too_fast).is_err());
+
+    let token = client.mint(&user, &user, &meta, &named(&env, "A"));
+    assert!(client
+        .try_evolve_milestone(&admin, &token, &1u32, &0u32, &Some(too_fast))
+        .is_err());
+}
+
+#[test]
+fn evolution_rule_targets_attributes() {
+    let (env, admin, user, client) = setup();
+    let verifier = Address::generate(&env);
+    client.add_verifier(&admin, &verifier);
+    let mut deltas = Vec::new(&env);
+    deltas.push_back(AttributeDelta { name: symbol_short!("speed"), delta: 2 });
+    client.set_evolution_rule(
+        &admin,
+        &symbol_short!("train"),
+        &EvolutionRule { level_inc: 1, rarity_inc: 0, deltas },
+    );
+
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "A"));
+    for _ in 0..3 {
+        client.apply_evolution(&verifier, &token, &symbol_short!("train"));
+    }
+
+    // speed starts at 0 and is clamped to the schema maximum
+    let nft = client.get_nft(&token).unwrap();
+    assert_eq!(nft.level, 4);
+    assert_eq!(nft.traits.get(symbol_short!("speed")), Some(TraitValue::Int(5)));
+    assert_eq!(client.get_history_len(&token), 3);
+
+    // rules may only target numeric attributes
+    let mut bad = Vec::new(&env);
+    bad.push_back(AttributeDelta { name: symbol_short!("name"), delta: 1 });
+    assert!(client
+        .try_set_evolution_rule(&admin, &symbol_short!("bad"), &EvolutionRule { level_inc: 0, rarity_inc: 0, deltas: bad })
+        .is_err());
+}
+
+#[test]
+fn history_is_paginated() {
+    let (env, admin, user, client) = setup();
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "A"));
+    for _ in 0..4 {
+        client.evolve_milestone(&admin, &token, &1u32, &0u32, &None);
+    }
+    client.downgrade(&admin, &token, &1u32);
+
+    assert_eq!(client.get_history_len(&token), 5);
+    let first = client.get_history(&token, &0, &2);
+    assert_eq!(first.len(), 2);
+    let rest = client.get_history(&token, &2, &10);
+    assert_eq!(rest.len(), 3);
+    assert_eq!(rest.get(2).unwrap(), String::from_str(&env, "downgraded"));
+    assert_eq!(client.get_history(&token, &5, &10).len(), 0);
+}
+
+fn register_train_rule(env: &Env, client: &DynamicNftContractClient, admin: &Address) {
+    let mut deltas = Vec::new(env);
+    deltas.push_back(AttributeDelta { name: symbol_short!("speed"), delta: 1 });
+    client.set_evolution_rule(
+        admin,
+        &symbol_short!("train"),
+        &EvolutionRule { level_inc: 1, rarity_inc: 0, deltas },
+    );
+}
+
+#[test]
+fn sync_applies_linked_evolutions_once() {
+    let (env, admin, user, client) = setup();
+    register_train_rule(&env, &client, &admin);
+    let rating = MockSkillRatingClient::new(&env, &env.register(MockSkillRating, ()));
+    let quests = MockQuestChainClient::new(&env, &env.register(MockQuestChain, ()));
+    client.set_auto_rule(
+        &admin,
+        &1,
+        &AutoRule {
+            trigger: AutoTrigger::SkillTier(rating.address.clone(), SkillTier::Gold),
+            action: AutoAction::Evolve(symbol_short!("train")),
+        },
+    );
+    client.set_auto_rule(
+        &admin,
+        &2,
+        &AutoRule {
+            trigger: AutoTrigger::ChainCompleted(quests.address.clone(), 7),
+            action: AutoAction::Evolve(symbol_short!("train")),
+        },
+    );
+
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "A"));
+    // unrated players and unfinished chains trigger nothing
+    assert_eq!(client.sync_token(&token), 0);
+
+    rating.set_tier(&user, &SkillTier::Silver);
+    assert_eq!(client.sync_token(&token), 0);
+
+    rating.set_tier(&user, &SkillTier::Platinum);
+    quests.complete(&user, &7);
+    assert_eq!(client.sync_token(&token), 2);
+    // both rules fired once; syncing again is a no-op
+    assert_eq!(client.sync_token(&token), 0);
+
+    let nft = client.get_nft(&token).unwrap();
+    assert_eq!(nft.level, 3);
+    assert_eq!(nft.traits.get(symbol_short!("speed")), Some(TraitValue::Int(2)));
+    assert_eq!(client.get_history_len(&token), 2);
+}
+
+#[test]
+fn sync_downgrades_after_daily_inactivity() {
+    let (env, admin, user, client) = setup();
+    let daily = MockDailyRewardsClient::new(&env, &env.register(MockDailyRewards, ()));
+    client.set_auto_rule(
+        &admin,
+        &1,
+        &AutoRule {
+            trigger: AutoTrigger::DailyInactive(daily.address.clone(), 100),
+            action: AutoAction::Downgrade(1),
+        },
+    );
+
+    env.ledger().set_sequence_number(1_000);
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "A"));
+    client.evolve_milestone(&admin, &token, &3u32, &0u32, &None);
+
+    // the clock starts at mint and restarts with every claim
+    env.ledger().set_sequence_number(1_090);
+    daily.claim(&user);
+    env.ledger().set_sequence_number(1_150);
+    assert_eq!(client.sync_token(&token), 0);
+
+    env.ledger().set_sequence_number(1_190);
+    assert_eq!(client.sync_token(&token), 1);
+    assert_eq!(client.get_nft(&token).unwrap().level, 3);
+
+    // another full inactive period is needed before the next downgrade
+    env.ledger().set_sequence_number(1_250);
+    assert_eq!(client.sync_token(&token), 0);
+    env.ledger().set_sequence_number(1_290);
+    assert_eq!(client.sync_token(&token), 1);
+    assert_eq!(client.get_nft(&token).unwrap().level, 2);
+}
+
+#[test]
+fn auto_rules_require_admin_and_known_evolution() {
+    let (env, admin, user, client) = setup();
+    let rating = Address::generate(&env);
+    let rule = AutoRule {
+        trigger: AutoTrigger::SkillTier(rating, SkillTier::Gold),
+        action: AutoAction::Evolve(symbol_short!("train")),
+    };
+
+    assert!(client.try_set_auto_rule(&admin, &1, &rule).is_err());
+    register_train_rule(&env, &client, &admin);
+    assert!(client.try_set_auto_rule(&user, &1, &rule).is_err());
+
+    client.set_auto_rule(&admin, &1, &rule);
+    assert_eq!(client.get_auto_rules().len(), 1);
+    client.remove_auto_rule(&admin, &1);
+    assert_eq!(client.get_auto_rules().len(), 0);
+    // tokens may still hold AutoApplied entries for the removed id
+    assert!(client.try_set_auto_rule(&admin, &1, &rule).is_err());
+    assert!(client.try_remove_auto_rule(&admin, &2).is_err());
+    client.set_auto_rule(&admin, &2, &rule);
+}
+
+#[test]
+fn sync_treats_failing_linked_contracts_as_not_due() {
+    let (env, admin, user, client) = setup();
+    register_train_rule(&env, &client, &admin);
+    client.set_auto_rule(
+        &admin,
+        &1,
+        &AutoRule {
+            trigger: AutoTrigger::ChainCompleted(Address::generate(&env), 7),
+            action: AutoAction::Evolve(symbol_short!("train")),
+        },
+    );
+    client.set_auto_rule(
+        &admin,
+        &2,
+        &AutoRule {
+            trigger: AutoTrigger::DailyInactive(Address::generate(&env), 100),
+            action: AutoAction::Downgrade(1),
+        },
+    );
+
+    env.ledger().set_sequence_number(1_000);
+    let token = client.mint(&user, &user, &String::from_str(&env, "meta"), &named(&env, "A"));
+    client.evolve_milestone(&admin, &token, &3u32, &0u32, &None);
+    env.ledger().set_sequence_number(1_200);
+    assert_eq!(client.sync_token(&token), 0);
+    assert_eq!(client.get_nft(&token).unwrap().level, 4);
 }
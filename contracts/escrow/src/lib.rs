@@ -1,4 +1,6 @@
 #![no_std]
+// `ReleaseCondition` variants are part of the public contract spec
+#![allow(clippy::enum_variant_names)]
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env,
     String, Symbol, Vec,
@@ -27,13 +29,6 @@ pub enum ReleaseCondition {
     AllPartiesApprove,
     MajorityApprove,
     ArbitratorApprove,
-pub enum EscrowStatus {
-    Created = 1,
-    Active = 2,
-    Disputed = 3,
-    Resolved = 4,
-    Refunded = 5,
-    Released = 6,
 }
 
 #[contracttype]
@@ -41,11 +36,6 @@ pub enum EscrowStatus {
 pub enum DisputeResolution {
     Release,
     Refund,
-pub struct ReleaseCondition {
-    pub condition_id: u64,
-    pub description: String,
-    pub fulfilled: bool,
-    pub evidence: Option<String>,
 }
 
 #[contracttype]
@@ -92,46 +82,13 @@ const DISPUTE_RESOLVED: Symbol = symbol_short!("resolved");
 const AUTO_RELEASE: Symbol = symbol_short!("auto");
 const TIMEOUT_REFUND: Symbol = symbol_short!("timeout");
 
-pub struct EscrowAgreement {
-    pub escrow_id: u64,
-    pub creator: Address,
-    pub parties: Vec<Party>,
-    pub arbitrator: Address,
-    pub status: EscrowStatus,
-    pub total_deposit: i128,
-    pub release_conditions: Vec<ReleaseCondition>,
-    pub dispute_reason: Option<String>,
-    pub resolution: Option<String>,
-    pub created_at: u64,
-    pub timeout_at: u64,
-    pub last_activity_at: u64,
-}
-
-#[contracttype]
-pub enum DataKey {
-    Escrow(u64),
-    EscrowCount,
-    NextConditionId,
-}
-
-// 2. CONTRACT LOGIC
 #[contract]
 pub struct EscrowContract;
 
 #[contractimpl]
 impl EscrowContract {
-    #[allow(clippy::too_many_arguments)]
-    /// Initialize the contract
-    pub fn init(env: Env, _admin: Address) {
-        if !env.storage().instance().has(&DataKey::EscrowCount) {
-            env.storage().instance().set(&DataKey::EscrowCount, &0u64);
-        }
-        if !env.storage().instance().has(&DataKey::NextConditionId) {
-            env.storage().instance().set(&DataKey::NextConditionId, &1u64);
-        }
-    }
-
     /// Create a new escrow agreement
+    #[allow(clippy::too_many_arguments)]
     pub fn create_escrow(
         env: Env,
         creator: Address,
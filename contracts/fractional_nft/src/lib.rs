@@ -155,10 +155,12 @@ impl FractionalNftContract {
             panic!("not_nft_owner");
         }
 
-        let transfer_args = (owner.clone(), env.current_contract_address(), nft_id).into_val(&env);
+        // The owner must have approved this contract on the NFT contract
+        let this = env.current_contract_address();
+        let transfer_args = (this.clone(), owner.clone(), this, nft_id).into_val(&env);
         env.invoke_contract::<()>(
             &nft_contract,
-            &Symbol::new(&env, "transfer"),
+            &Symbol::new(&env, "transfer_from"),
             transfer_args,
         );
 
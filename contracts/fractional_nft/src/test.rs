@@ -1,37 +1,9 @@
 #![cfg(test)]
+#![allow(clippy::bool_assert_comparison)]
 
 use super::*;
-use soroban_sdk::{contract, contractimpl, testutils::{Address as _, Ledger}, token, Address, Env, String};
-
-// Mock NFT contract for testing fractional_nft
-#[contract]
-pub struct MockNFT;
-
-#[contractimpl]
-impl MockNFT {
-    pub fn owner_of(env: Env, token_id: u32) -> Address {
-        env.storage()
-            .persistent()
-            .get(&token_id)
-            .unwrap_or_else(|| panic!("token_not_found"))
-    }
-
-    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
-        from.require_auth();
-        let current_owner: Address = env.storage()
-            .persistent()
-            .get(&token_id)
-            .unwrap_or_else(|| panic!("token_not_found"));
-        if current_owner != from {
-            panic!("not_owner");
-        }
-        env.storage().persistent().set(&token_id, &to);
-    }
-
-    pub fn set_owner(env: Env, token_id: u32, owner: Address) {
-        env.storage().persistent().set(&token_id, &owner);
-    }
-}
+use achievement_nft::{AchievementNFT, AchievementNFTClient};
+use soroban_sdk::{testutils::{Address as _, Ledger}, token, Address, Env, String};
 
 fn setup_token<'a>(
     env: &'a Env,
@@ -45,10 +17,21 @@ fn setup_token<'a>(
     (token_id, token_client, token_admin_client)
 }
 
-fn setup_mock_nft<'a>(env: &'a Env) -> (Address, MockNFTClient<'a>) {
-    let nft_id = env.register_contract(None, MockNFT);
-    let client = MockNFTClient::new(env, &nft_id);
-    (nft_id, client)
+fn setup_nft<'a>(env: &'a Env) -> (Address, AchievementNFTClient<'a>, Address) {
+    let nft_id = env.register_contract(None, AchievementNFT);
+    let client = AchievementNFTClient::new(env, &nft_id);
+    let nft_admin = Address::generate(env);
+    client.initialize(&nft_admin);
+    (nft_id, client, nft_admin)
+}
+
+fn mint_nft(env: &Env, nft: &AchievementNFTClient, nft_admin: &Address, owner: &Address) -> u32 {
+    nft.craftmint(nft_admin, owner, &1, &String::from_str(env, "Vaulted"))
+}
+
+// Let the vault contract escrow the owner's NFT
+fn approve_vault(env: &Env, nft: &AchievementNFTClient, owner: &Address, vault_contract: &Address, token_id: u32) {
+    nft.approve(owner, vault_contract, &token_id, &(env.ledger().sequence() + 1_000));
 }
 
 #[test]
@@ -56,17 +39,23 @@ fn test_fractionalize_and_transfer_shares() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (nft_contract_id, nft) = setup_mock_nft(&env);
+    let (nft_contract_id, nft, nft_admin) = setup_nft(&env);
 
     let admin = Address::generate(&env);
     let owner = Address::generate(&env);
     let token_id = 1u32;
-    nft.set_owner(&token_id, &owner);
+    assert_eq!(mint_nft(&env, &nft, &nft_admin, &owner), token_id);
 
     let contract_id = env.register_contract(None, FractionalNftContract);
     let client = FractionalNftContractClient::new(&env, &contract_id);
     client.initialize(&admin);
 
+    // Without an approval the NFT cannot be escrowed
+    let unapproved = client.try_fractionalize(&owner, &nft_contract_id, &token_id, &100i128, &0u32, &None);
+    assert!(unapproved.is_err());
+    assert_eq!(nft.owner_of(&token_id), owner);
+
+    approve_vault(&env, &nft, &owner, &contract_id, token_id);
     let vault_id = client.fractionalize(
         &owner,
         &nft_contract_id,
@@ -76,6 +65,7 @@ fn test_fractionalize_and_transfer_shares() {
         &None,
     );
 
+    assert_eq!(nft.owner_of(&token_id), contract_id);
     assert_eq!(client.get_vault(&vault_id).unwrap().total_shares, 100);
     assert_eq!(client.balance_of(&vault_id, &owner), 100);
 
@@ -92,18 +82,19 @@ fn test_minimum_ownership_threshold_enforced() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (nft_contract_id, nft) = setup_mock_nft(&env);
+    let (nft_contract_id, nft, nft_admin) = setup_nft(&env);
 
     let admin = Address::generate(&env);
     let owner = Address::generate(&env);
     let token_id = 1u32;
-    nft.set_owner(&token_id, &owner);
+    assert_eq!(mint_nft(&env, &nft, &nft_admin, &owner), token_id);
 
     let contract_id = env.register_contract(None, FractionalNftContract);
     let client = FractionalNftContractClient::new(&env, &contract_id);
     client.initialize(&admin);
 
     // total_shares=100, min_ownership_bps=1000 => min_shares=10
+    approve_vault(&env, &nft, &owner, &contract_id, token_id);
     let vault_id = client.fractionalize(
         &owner,
         &nft_contract_id,
@@ -130,14 +121,15 @@ fn test_share_trading_listing_buy_and_cancel() {
     let (payment_token_id, payment_token, payment_admin) = setup_token(&env, &admin);
     payment_admin.mint(&buyer, &1_000);
 
-    let (nft_contract_id, nft) = setup_mock_nft(&env);
+    let (nft_contract_id, nft, nft_admin) = setup_nft(&env);
     let token_id = 1u32;
-    nft.set_owner(&token_id, &seller);
+    assert_eq!(mint_nft(&env, &nft, &nft_admin, &seller), token_id);
 
     let contract_id = env.register_contract(None, FractionalNftContract);
     let client = FractionalNftContractClient::new(&env, &contract_id);
     client.initialize(&admin);
 
+    approve_vault(&env, &nft, &seller, &contract_id, token_id);
     let vault_id = client.fractionalize(
         &seller,
         &nft_contract_id,
@@ -196,16 +188,17 @@ fn test_buyout_tender_and_reclaim() {
     let (payment_token_id, payment_token, payment_admin) = setup_token(&env, &admin);
     payment_admin.mint(&buyer, &10_000);
 
-    let (nft_contract_id, nft) = setup_mock_nft(&env);
+    let (nft_contract_id, nft, nft_admin) = setup_nft(&env);
 
     let original_owner = Address::generate(&env);
     let token_id = 1u32;
-    nft.set_owner(&token_id, &original_owner);
+    assert_eq!(mint_nft(&env, &nft, &nft_admin, &original_owner), token_id);
 
     let contract_id = env.register_contract(None, FractionalNftContract);
     let client = FractionalNftContractClient::new(&env, &contract_id);
     client.initialize(&admin);
 
+    approve_vault(&env, &nft, &original_owner, &contract_id, token_id);
     let vault_id = client.fractionalize(
         &original_owner,
         &nft_contract_id,
@@ -247,14 +240,15 @@ fn test_profit_sharing_from_rentals_and_claim() {
     let (rental_token_id, rental_token, rental_admin) = setup_token(&env, &admin);
     rental_admin.mint(&payer, &1_000);
 
-    let (nft_contract_id, nft) = setup_mock_nft(&env);
+    let (nft_contract_id, nft, nft_admin) = setup_nft(&env);
     let token_id = 1u32;
-    nft.set_owner(&token_id, &owner);
+    assert_eq!(mint_nft(&env, &nft, &nft_admin, &owner), token_id);
 
     let contract_id = env.register_contract(None, FractionalNftContract);
     let client = FractionalNftContractClient::new(&env, &contract_id);
     client.initialize(&admin);
 
+    approve_vault(&env, &nft, &owner, &contract_id, token_id);
     let vault_id = client.fractionalize(
         &owner,
         &nft_contract_id,
@@ -288,14 +282,15 @@ fn test_voting_set_rental_token() {
 
     let (new_rental_token_id, _, _) = setup_token(&env, &admin);
 
-    let (nft_contract_id, nft) = setup_mock_nft(&env);
+    let (nft_contract_id, nft, nft_admin) = setup_nft(&env);
     let token_id = 1u32;
-    nft.set_owner(&token_id, &owner);
+    assert_eq!(mint_nft(&env, &nft, &nft_admin, &owner), token_id);
 
     let contract_id = env.register_contract(None, FractionalNftContract);
     let client = FractionalNftContractClient::new(&env, &contract_id);
     client.initialize(&admin);
 
+    approve_vault(&env, &nft, &owner, &contract_id, token_id);
     let vault_id = client.fractionalize(
         &owner,
         &nft_contract_id,
@@ -333,14 +328,15 @@ fn test_recombine_merge_fractions() {
     let owner = Address::generate(&env);
     let receiver = Address::generate(&env);
 
-    let (nft_contract_id, nft) = setup_mock_nft(&env);
+    let (nft_contract_id, nft, nft_admin) = setup_nft(&env);
     let token_id = 1u32;
-    nft.set_owner(&token_id, &owner);
+    assert_eq!(mint_nft(&env, &nft, &nft_admin, &owner), token_id);
 
     let contract_id = env.register_contract(None, FractionalNftContract);
     let client = FractionalNftContractClient::new(&env, &contract_id);
     client.initialize(&admin);
 
+    approve_vault(&env, &nft, &owner, &contract_id, token_id);
     let vault_id = client.fractionalize(
         &owner,
         &nft_contract_id,
@@ -0,0 +1,8 @@
+//! Cross-contract tests that deploy several contracts into one environment.
+//!
+//! Nothing here is deployed; the crate only exists so `cargo test` exercises
+//! the interfaces between the factory, verification, certificate, achievement
+//! NFT and marketplace contracts together.
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,222 @@
+use achievement_nft::{AchievementNFT, AchievementNFTClient};
+use completion_certificate::{
+    CompletionCertificateContract, CompletionCertificateContractClient, RarityTier,
+};
+use marketplace_contract::{
+    Asset, AssetType, ListingStatus, MarketplaceContract, MarketplaceContractClient,
+};
+use puzzle_factory::{
+    PuzzleCategory, PuzzleConfig, PuzzleContent, PuzzleFactory, PuzzleFactoryClient,
+};
+use puzzle_verification::{PuzzleVerification, PuzzleVerificationClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Bytes, BytesN, Env, String, Vec,
+};
+
+struct Deployment<'a> {
+    creator: Address,
+    factory: PuzzleFactoryClient<'a>,
+    verification: PuzzleVerificationClient<'a>,
+    certificates: CompletionCertificateContractClient<'a>,
+    achievements: AchievementNFTClient<'a>,
+    marketplace: MarketplaceContractClient<'a>,
+    fee_recipient: Address,
+}
+
+fn deploy(env: &Env) -> Deployment<'_> {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let fee_recipient = Address::generate(env);
+
+    let factory = PuzzleFactoryClient::new(env, &env.register_contract(None, PuzzleFactory));
+    factory.initialize(&admin);
+    factory.authorize_creator(&creator);
+
+    let verification =
+        PuzzleVerificationClient::new(env, &env.register_contract(None, PuzzleVerification));
+    verification.initialize(&admin);
+    verification.set_puzzle_factory(&Some(factory.address.clone()));
+    factory.set_verifier(&Some(verification.address.clone()));
+
+    let certificates = CompletionCertificateContractClient::new(
+        env,
+        &env.register_contract(None, CompletionCertificateContract),
+    );
+    certificates.initialize(&admin);
+    certificates.set_completion_sources(&Some(verification.address.clone()), &None);
+
+    let achievements = AchievementNFTClient::new(env, &env.register_contract(None, AchievementNFT));
+    achievements.initialize(&admin);
+    achievements.authorize_verifier(&verification.address);
+    verification.set_achievement_nft(&Some(achievements.address.clone()));
+
+    let marketplace =
+        MarketplaceContractClient::new(env, &env.register_contract(None, MarketplaceContract));
+    marketplace.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
+
+    Deployment {
+        creator,
+        factory,
+        verification,
+        certificates,
+        achievements,
+        marketplace,
+        fee_recipient,
+    }
+}
+
+fn create_puzzle(env: &Env, d: &Deployment, solution: &Bytes) -> u32 {
+    let config = PuzzleConfig {
+        solution_hash: env.crypto().sha256(solution).into(),
+        start_time: 900,
+        end_time: 50_000,
+        max_attempts: 3,
+        time_limit: None,
+        reward_points: 40,
+    };
+    d.factory.create_puzzle(
+        &d.creator,
+        &PuzzleCategory::Logic,
+        &3,
+        &PuzzleContent {
+            title: String::from_str(env, "Maze"),
+            description: String::from_str(env, "Find the exit"),
+            content_uri: String::from_str(env, "ipfs://maze"),
+            content_hash: BytesN::from_array(env, &[0u8; 32]),
+            tags: Vec::new(env),
+        },
+        &config,
+        &0,
+    )
+}
+
+fn solve(env: &Env, d: &Deployment, player: &Address, puzzle_id: u32, solution: &Bytes) -> bool {
+    let salt = BytesN::from_array(env, &[5u8; 32]);
+    let commitment = d
+        .verification
+        .compute_commitment(player, &puzzle_id, solution, &salt);
+    d.verification.commit_solution(player, &puzzle_id, &commitment);
+    env.ledger()
+        .with_mut(|li| li.timestamp += d.verification.get_reveal_delay());
+    d.verification.verify_solution(player, &puzzle_id, solution, &salt)
+}
+
+#[test]
+fn test_solve_certify_and_trade_achievement() {
+    let env = Env::default();
+    let d = deploy(&env);
+    let player = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    // Factory -> verification: the puzzle is loaded from the factory and plays are reported back
+    let solution = Bytes::from_array(&env, &[4u8; 6]);
+    let puzzle_id = create_puzzle(&env, &d, &solution);
+    assert!(solve(&env, &d, &player, puzzle_id, &solution));
+    assert!(d.verification.is_completed(&player, &puzzle_id));
+    assert_eq!(d.verification.rewards_of(&player), 40);
+    let puzzle = d.factory.get_puzzle(&puzzle_id);
+    assert_eq!(puzzle.metadata.total_plays, 1);
+    assert_eq!(puzzle.metadata.successful_plays, 1);
+
+    // Verification -> certificate: minted from the on-chain completion record
+    let cert_id = d.certificates.mint_verified_certificate(
+        &player,
+        &puzzle_id,
+        &String::from_str(&env, "Maze"),
+        &String::from_str(&env, "ipfs://maze-cert"),
+        &false,
+    );
+    let proof = d.certificates.verify_certificate(&cert_id);
+    assert!(proof.authentic);
+    assert!(proof.verified);
+    assert_eq!(proof.owner, player);
+    assert_eq!(proof.rank, 1);
+    // The player never started the clock, so the solve is untimed
+    assert_eq!(proof.rarity, RarityTier::Common);
+
+    // Verification -> achievement NFT: the solve was marked completed by the verifier
+    let token_id = d.achievements.mint(
+        &player,
+        &puzzle_id,
+        &String::from_str(&env, "Maze solver"),
+    );
+    assert!(d.achievements.has_puzzle(&player, &puzzle_id));
+
+    // Achievement NFT -> marketplace: escrowed on listing, delivered on purchase
+    let token_admin = Address::generate(&env);
+    let payment_token = env.register_stellar_asset_contract_v2(token_admin).address();
+    token::StellarAssetClient::new(&env, &payment_token).mint(&buyer, &10_000);
+
+    let asset = Asset {
+        asset_type: AssetType::NFT,
+        contract: d.achievements.address.clone(),
+        token_id,
+    };
+    d.achievements.approve(
+        &player,
+        &d.marketplace.address,
+        &token_id,
+        &(env.ledger().sequence() + 1_000),
+    );
+    let listing_id = d.marketplace.create_listing(
+        &player,
+        &asset,
+        &payment_token,
+        &1_000,
+        &Some(d.creator.clone()),
+        &500,
+    );
+    assert_eq!(d.achievements.owner_of(&token_id), d.marketplace.address);
+
+    d.marketplace.buy(&buyer, &listing_id);
+
+    let payments = token::Client::new(&env, &payment_token);
+    assert_eq!(d.achievements.owner_of(&token_id), buyer);
+    assert_eq!(
+        d.marketplace.get_listing(&listing_id).unwrap().status,
+        ListingStatus::Sold
+    );
+    assert_eq!(payments.balance(&buyer), 9_000);
+    assert_eq!(payments.balance(&player), 925);
+    assert_eq!(payments.balance(&d.fee_recipient), 25);
+    assert_eq!(payments.balance(&d.creator), 50);
+}
+
+#[test]
+fn test_failed_reveal_blocks_rewards() {
+    let env = Env::default();
+    let d = deploy(&env);
+    let player = Address::generate(&env);
+
+    let solution = Bytes::from_array(&env, &[4u8; 6]);
+    let puzzle_id = create_puzzle(&env, &d, &solution);
+
+    let wrong = Bytes::from_array(&env, &[9u8; 6]);
+    assert!(!solve(&env, &d, &player, puzzle_id, &wrong));
+    assert!(!d.verification.is_completed(&player, &puzzle_id));
+    assert_eq!(d.verification.rewards_of(&player), 0);
+
+    let puzzle = d.factory.get_puzzle(&puzzle_id);
+    assert_eq!(puzzle.metadata.total_plays, 1);
+    assert_eq!(puzzle.metadata.successful_plays, 0);
+
+    // Neither the certificate nor the achievement NFT can be minted
+    let certified = d.certificates.try_mint_verified_certificate(
+        &player,
+        &puzzle_id,
+        &String::from_str(&env, "Maze"),
+        &String::from_str(&env, "ipfs://maze-cert"),
+        &false,
+    );
+    assert!(certified.is_err());
+    let minted = d.achievements.try_mint(
+        &player,
+        &puzzle_id,
+        &String::from_str(&env, "Maze solver"),
+    );
+    assert!(minted.is_err());
+}
@@ -1,6 +1,9 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, Map, Symbol,
+    TryFromVal, Val, Vec,
+};
 
 //
 // ──────────────────────────────────────────────────────────
@@ -16,6 +19,43 @@ pub enum TimePeriod {
     AllTime = 2,
 }
 
+//
+// ──────────────────────────────────────────────────────────
+// BOARDS
+// ──────────────────────────────────────────────────────────
+//
+
+/// Mirrors `puzzle_factory::PuzzleCategory` so category boards share its encoding
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PuzzleCategory {
+    Logic,
+    Mathematics,
+    Pattern,
+    Cryptography,
+    Spatial,
+    Sequence,
+}
+
+/// Identifies an independent leaderboard
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BoardKey {
+    Global,
+    Puzzle(u32),
+    Category(PuzzleCategory),
+    Season(u32),
+}
+
+/// How repeated submissions from the same player combine within a period
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Aggregation {
+    Best = 0,
+    Sum = 1,
+    Latest = 2,
+}
+
 //
 // ──────────────────────────────────────────────────────────
 // DATA KEYS
@@ -24,13 +64,27 @@ pub enum TimePeriod {
 
 #[contracttype]
 pub enum DataKey {
-    Config,                                    // LeaderboardConfig
-    PlayerScore(Address, TimePeriod, u64),     // PlayerScore - (player, period, period_id)
-    TopScores(TimePeriod, u64),                // Vec<PlayerScore> - sorted top scores for period
-    PlayerAllTimeScore(Address),               // i128 - cumulative all-time score
-    TotalPlayers,                              // u32
-    HighScore(TimePeriod),                     // i128 - record high score per period type
-    Verifier(Address),                         // bool - authorized score verifiers
+    Config,                                          // LeaderboardConfig
+    Board(BoardKey),                                 // Board
+    PlayerScore(BoardKey, Address, TimePeriod, u64), // PlayerScore - (board, player, period, period_id)
+    TopScores(BoardKey, TimePeriod, u64),            // Vec<PlayerScore> - sorted top scores for period
+    PlayerAllTimeScore(Address),                     // i128 - cumulative all-time score
+    TotalPlayers,                                    // u32
+    HighScore(BoardKey, TimePeriod),                 // i128 - record high score per board and period type
+    Verifier(Address),                               // bool - authorized score verifiers
+    LastPeriod(BoardKey, TimePeriod),                // u64 - last period_id that received scores
+    Snapshot(BoardKey, TimePeriod, u64),             // PeriodSnapshot - frozen ranking of a closed period
+    SnapshotRewards(BoardKey, TimePeriod, u64),      // RewardCurve - token rewards attached to a snapshot
+    RewardClaimed(BoardKey, TimePeriod, u64, Address), // bool - winner already claimed
+}
+
+// Score keys stored before boards were added. The global board falls back to
+// them until the next write stores the entry under its board key.
+#[contracttype]
+pub enum LegacyDataKey {
+    PlayerScore(Address, TimePeriod, u64), // PlayerScore
+    TopScores(TimePeriod, u64),            // Vec<PlayerScore>
+    HighScore(TimePeriod),                 // i128
 }
 
 //
@@ -46,9 +100,31 @@ pub struct LeaderboardConfig {
     pub max_top_entries: u32,      // Maximum entries in top-N list (gas optimization)
     pub daily_period_length: u64,  // Seconds (86400 for 24 hours)
     pub weekly_period_length: u64, // Seconds (604800 for 7 days)
+    pub snapshot_size: u32,        // Entries frozen when a period closes
     pub paused: bool,
 }
 
+// Config shape stored before period snapshots were added, upgraded with the
+// default snapshot size whenever the config is read.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LegacyLeaderboardConfig {
+    pub admin: Address,
+    pub max_top_entries: u32,
+    pub daily_period_length: u64,
+    pub weekly_period_length: u64,
+    pub paused: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Board {
+    pub key: BoardKey,
+    pub aggregation: Aggregation,
+    pub creator: Address,
+    pub created_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct PlayerScore {
@@ -59,6 +135,27 @@ pub struct PlayerScore {
     pub period_id: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PeriodSnapshot {
+    pub board: BoardKey,
+    pub period: TimePeriod,
+    pub period_id: u64,
+    pub entries: Vec<PlayerScore>, // Ranked top N at close, index 0 is rank 1
+    pub closed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RewardCurve {
+    pub token: Address,
+    pub amounts: Vec<i128>, // amounts[i] is paid to rank i + 1
+    pub funded: i128,       // Total escrowed for the ranked winners
+    pub claimed: i128,
+    pub claim_deadline: u64, // Unclaimed escrow can be swept back after this
+    pub swept: i128,
+}
+
 //
 // ──────────────────────────────────────────────────────────
 // CONSTANTS
@@ -67,7 +164,9 @@ pub struct PlayerScore {
 
 const DEFAULT_DAILY_PERIOD: u64 = 86_400;     // 24 hours
 const DEFAULT_WEEKLY_PERIOD: u64 = 604_800;   // 7 days
+const REWARD_CLAIM_WINDOW: u64 = 2_592_000;   // 30 days to claim period rewards
 const DEFAULT_MAX_TOP_ENTRIES: u32 = 100;
+const DEFAULT_SNAPSHOT_SIZE: u32 = 10;
 
 //
 // ──────────────────────────────────────────────────────────
@@ -77,8 +176,14 @@ const DEFAULT_MAX_TOP_ENTRIES: u32 = 100;
 
 // Event symbols
 const NEW_HIGH_SCORE: Symbol = symbol_short!("hi_score");
+const BOARD_HIGH_SCORE: Symbol = symbol_short!("brd_hi");
 const SCORE_SUBMIT: Symbol = symbol_short!("submit");
 const RANK_CHANGE: Symbol = symbol_short!("rank_chg");
+const BOARD_CREATED: Symbol = symbol_short!("brd_new");
+const PERIOD_CLOSED: Symbol = symbol_short!("snapshot");
+const REWARD_ATTACHED: Symbol = symbol_short!("rwd_set");
+const REWARD_CLAIMED: Symbol = symbol_short!("rwd_claim");
+const REWARD_SWEPT: Symbol = symbol_short!("rwd_sweep");
 
 //
 // ──────────────────────────────────────────────────────────
@@ -111,16 +216,27 @@ impl LeaderboardContract {
             max_top_entries
         };
 
+        let global = Board {
+            key: BoardKey::Global,
+            aggregation: Aggregation::Sum,
+            creator: admin.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+
         let config = LeaderboardConfig {
             admin,
             max_top_entries: max_entries,
             daily_period_length: DEFAULT_DAILY_PERIOD,
             weekly_period_length: DEFAULT_WEEKLY_PERIOD,
+            snapshot_size: DEFAULT_SNAPSHOT_SIZE,
             paused: false,
         };
 
         env.storage().persistent().set(&DataKey::Config, &config);
         env.storage().persistent().set(&DataKey::TotalPlayers, &0u32);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Board(BoardKey::Global), &global);
     }
 
     // ───────────── ADMIN FUNCTIONS ─────────────
@@ -150,8 +266,7 @@ impl LeaderboardContract {
         admin.require_auth();
         Self::assert_admin(&env, &admin);
 
-        let mut config: LeaderboardConfig =
-            env.storage().persistent().get(&DataKey::Config).unwrap();
+        let mut config = Self::load_config(&env);
         config.paused = paused;
         env.storage().persistent().set(&DataKey::Config, &config);
     }
@@ -166,8 +281,7 @@ impl LeaderboardContract {
         admin.require_auth();
         Self::assert_admin(&env, &admin);
 
-        let mut config: LeaderboardConfig =
-            env.storage().persistent().get(&DataKey::Config).unwrap();
+        let mut config = Self::load_config(&env);
         config.daily_period_length = daily_period_length;
         config.weekly_period_length = weekly_period_length;
         env.storage().persistent().set(&DataKey::Config, &config);
@@ -182,12 +296,25 @@ impl LeaderboardContract {
             panic!("Max entries must be positive");
         }
 
-        let mut config: LeaderboardConfig =
-            env.storage().persistent().get(&DataKey::Config).unwrap();
+        let mut config = Self::load_config(&env);
         config.max_top_entries = max_top_entries;
         env.storage().persistent().set(&DataKey::Config, &config);
     }
 
+    /// Update how many entries are frozen when a period closes (admin only)
+    pub fn update_snapshot_size(env: Env, admin: Address, snapshot_size: u32) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+
+        if snapshot_size == 0 {
+            panic!("Snapshot size must be positive");
+        }
+
+        let mut config = Self::load_config(&env);
+        config.snapshot_size = snapshot_size;
+        env.storage().persistent().set(&DataKey::Config, &config);
+    }
+
     // ───────────── SCORE SUBMISSION ─────────────
 
     /// Submit a verified score for a player
@@ -206,18 +333,11 @@ impl LeaderboardContract {
             panic!("Score must be non-negative");
         }
 
-        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(&env);
         let current_time = env.ledger().timestamp();
+        let board = Self::load_board(&env, &BoardKey::Global);
 
-        // Calculate period IDs
-        let daily_period_id = current_time / config.daily_period_length;
-        let weekly_period_id = current_time / config.weekly_period_length;
-        let all_time_period_id = 0u64; // All-time uses 0 as period ID
-
-        // Update scores for each time period
-        Self::update_period_score(&env, &config, &player, score, TimePeriod::Daily, daily_period_id, current_time);
-        Self::update_period_score(&env, &config, &player, score, TimePeriod::Weekly, weekly_period_id, current_time);
-        Self::update_period_score(&env, &config, &player, score, TimePeriod::AllTime, all_time_period_id, current_time);
+        Self::update_board_scores(&env, &config, &board, &player, score, current_time);
 
         // Update cumulative all-time score
         let current_all_time: i128 = env
@@ -250,6 +370,74 @@ impl LeaderboardContract {
             .publish((SCORE_SUBMIT, player.clone()), (score, current_time));
     }
 
+    // ───────────── BOARDS ─────────────
+
+    /// Create a new board on demand
+    /// Can be called by admin or authorized verifier
+    ///
+    /// # Arguments
+    /// * `creator` - The admin or verifier creating the board
+    /// * `key` - Board identifier (per puzzle, per category or per season)
+    /// * `aggregation` - How repeated scores combine (best, sum or latest)
+    pub fn create_board(env: Env, creator: Address, key: BoardKey, aggregation: Aggregation) {
+        creator.require_auth();
+        Self::assert_not_paused(&env);
+        Self::assert_authorized_submitter(&env, &creator);
+
+        if env.storage().persistent().has(&DataKey::Board(key.clone())) {
+            panic!("Board already exists");
+        }
+
+        let board = Board {
+            key: key.clone(),
+            aggregation,
+            creator: creator.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Board(key.clone()), &board);
+
+        env.events()
+            .publish((BOARD_CREATED, creator), (key, aggregation));
+    }
+
+    /// Submit a verified score for a player on a specific board
+    /// Can be called by admin or authorized verifier
+    ///
+    /// # Arguments
+    /// * `submitter` - The admin or verifier submitting the score
+    /// * `key` - The board receiving the score
+    /// * `player` - The player who earned the score
+    /// * `score` - The score value
+    pub fn submit_board_score(
+        env: Env,
+        submitter: Address,
+        key: BoardKey,
+        player: Address,
+        score: i128,
+    ) {
+        submitter.require_auth();
+        Self::assert_not_paused(&env);
+        Self::assert_authorized_submitter(&env, &submitter);
+
+        if key == BoardKey::Global {
+            panic!("Use submit_score for the global board");
+        }
+        if score < 0 {
+            panic!("Score must be non-negative");
+        }
+
+        let config = Self::load_config(&env);
+        let current_time = env.ledger().timestamp();
+        let board = Self::load_board(&env, &key);
+
+        Self::update_board_scores(&env, &config, &board, &player, score, current_time);
+
+        env.events()
+            .publish((SCORE_SUBMIT, key, player), (score, current_time));
+    }
+
     /// Update a player's score directly (replace existing score)
     /// Admin only - useful for corrections
     pub fn update_score(
@@ -267,10 +455,11 @@ impl LeaderboardContract {
             panic!("Score must be non-negative");
         }
 
-        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(&env);
         let current_time = env.ledger().timestamp();
 
         let period_id = Self::get_current_period_id(&config, period, current_time);
+        Self::roll_period(&env, &config, &BoardKey::Global, period, period_id, current_time);
 
         // Create or update player score
         let player_score = PlayerScore {
@@ -282,12 +471,265 @@ impl LeaderboardContract {
         };
 
         env.storage().persistent().set(
-            &DataKey::PlayerScore(player.clone(), period, period_id),
+            &DataKey::PlayerScore(BoardKey::Global, player.clone(), period, period_id),
             &player_score,
         );
 
         // Update top scores list
-        Self::update_top_scores_list(&env, &config, &player_score, period, period_id);
+        Self::update_top_scores_list(&env, &config, &BoardKey::Global, &player_score, period, period_id);
+    }
+
+    // ───────────── PERIOD SNAPSHOTS ─────────────
+
+    /// Freeze the ranking of a finished period
+    /// Periods are also closed automatically by the first submission after rollover,
+    /// so this is only needed for boards that received no scores since.
+    /// Only existing boards with scores in the period can be closed.
+    ///
+    /// # Arguments
+    /// * `key` - The board to close
+    /// * `period` - Daily or Weekly
+    /// * `period_id` - A past id as returned by `get_current_period_id_view`
+    pub fn close_period(env: Env, key: BoardKey, period: TimePeriod, period_id: u64) {
+        if period == TimePeriod::AllTime {
+            panic!("All-time period never closes");
+        }
+
+        Self::load_board(&env, &key);
+        let config = Self::load_config(&env);
+        let current_time = env.ledger().timestamp();
+        if period_id >= Self::get_current_period_id(&config, period, current_time) {
+            panic!("Period still open");
+        }
+        if Self::read_top_scores(&env, &key, period, period_id).is_none() {
+            panic!("No scores for period");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Snapshot(key.clone(), period, period_id))
+        {
+            panic!("Period already closed");
+        }
+
+        Self::freeze_period(&env, &config, &key, period, period_id, current_time);
+    }
+
+    /// Attach a token reward curve to a closed period (admin only)
+    /// Escrows the amounts owed to the ranked winners from the admin.
+    /// Winners have 30 days to claim before the admin can sweep the rest.
+    ///
+    /// # Arguments
+    /// * `amounts` - Reward per rank, index 0 is paid to rank 1
+    pub fn attach_period_rewards(
+        env: Env,
+        admin: Address,
+        key: BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+        token: Address,
+        amounts: Vec<i128>,
+    ) {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+
+        let snapshot: PeriodSnapshot = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Snapshot(key.clone(), period, period_id))
+            .unwrap_or_else(|| panic!("Snapshot not found"));
+
+        let rewards_key = DataKey::SnapshotRewards(key.clone(), period, period_id);
+        if env.storage().persistent().has(&rewards_key) {
+            panic!("Rewards already attached");
+        }
+
+        // Only ranks present in the snapshot are funded
+        let mut funded: i128 = 0;
+        for i in 0..amounts.len() {
+            let amount = amounts.get(i).unwrap();
+            if amount < 0 {
+                panic!("Reward must be non-negative");
+            }
+            if i < snapshot.entries.len() {
+                funded += amount;
+            }
+        }
+
+        if funded > 0 {
+            token::Client::new(&env, &token).transfer(
+                &admin,
+                &env.current_contract_address(),
+                &funded,
+            );
+        }
+
+        let curve = RewardCurve {
+            token,
+            amounts,
+            funded,
+            claimed: 0,
+            claim_deadline: env.ledger().timestamp() + REWARD_CLAIM_WINDOW,
+            swept: 0,
+        };
+        env.storage().persistent().set(&rewards_key, &curve);
+
+        env.events()
+            .publish((REWARD_ATTACHED, key, period), (period_id, funded));
+    }
+
+    /// Claim the reward earned by a player's rank in a closed period
+    /// Returns the amount transferred
+    pub fn claim_period_reward(
+        env: Env,
+        player: Address,
+        key: BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+    ) -> i128 {
+        player.require_auth();
+
+        let snapshot: PeriodSnapshot = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Snapshot(key.clone(), period, period_id))
+            .unwrap_or_else(|| panic!("Snapshot not found"));
+
+        let rewards_key = DataKey::SnapshotRewards(key.clone(), period, period_id);
+        let mut curve: RewardCurve = env
+            .storage()
+            .persistent()
+            .get(&rewards_key)
+            .unwrap_or_else(|| panic!("No rewards attached"));
+        if env.ledger().timestamp() > curve.claim_deadline {
+            panic!("Claim window closed");
+        }
+
+        let claimed_key = DataKey::RewardClaimed(key.clone(), period, period_id, player.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            panic!("Reward already claimed");
+        }
+
+        let rank = Self::snapshot_rank(&snapshot, &player);
+        if rank == 0 {
+            panic!("Player not ranked");
+        }
+
+        let amount = curve.amounts.get(rank - 1).unwrap_or(0);
+        if amount == 0 {
+            panic!("No reward for rank");
+        }
+
+        env.storage().persistent().set(&claimed_key, &true);
+        curve.claimed += amount;
+        env.storage().persistent().set(&rewards_key, &curve);
+
+        token::Client::new(&env, &curve.token).transfer(
+            &env.current_contract_address(),
+            &player,
+            &amount,
+        );
+
+        env.events().publish(
+            (REWARD_CLAIMED, player),
+            (key, period, period_id, rank, amount),
+        );
+
+        amount
+    }
+
+    /// Return unclaimed period rewards to the admin once the claim window has closed
+    /// Returns the amount swept
+    pub fn sweep_period_rewards(
+        env: Env,
+        admin: Address,
+        key: BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+    ) -> i128 {
+        admin.require_auth();
+        Self::assert_admin(&env, &admin);
+
+        let rewards_key = DataKey::SnapshotRewards(key.clone(), period, period_id);
+        let mut curve: RewardCurve = env
+            .storage()
+            .persistent()
+            .get(&rewards_key)
+            .unwrap_or_else(|| panic!("No rewards attached"));
+        if env.ledger().timestamp() <= curve.claim_deadline {
+            panic!("Claim window still open");
+        }
+
+        let remaining = curve.funded - curve.claimed - curve.swept;
+        if remaining > 0 {
+            curve.swept += remaining;
+            env.storage().persistent().set(&rewards_key, &curve);
+            token::Client::new(&env, &curve.token).transfer(
+                &env.current_contract_address(),
+                &admin,
+                &remaining,
+            );
+            env.events()
+                .publish((REWARD_SWEPT, key, period), (period_id, remaining));
+        }
+
+        remaining
+    }
+
+    /// Get the frozen ranking of a closed period
+    pub fn get_period_snapshot(
+        env: Env,
+        key: BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+    ) -> Option<PeriodSnapshot> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Snapshot(key, period, period_id))
+    }
+
+    /// Get a player's rank in a closed period's snapshot
+    /// Returns 0 if the player was not ranked or the period is not closed
+    pub fn get_snapshot_rank(
+        env: Env,
+        key: BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+        player: Address,
+    ) -> u32 {
+        let snapshot: Option<PeriodSnapshot> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Snapshot(key, period, period_id));
+        match snapshot {
+            Some(snapshot) => Self::snapshot_rank(&snapshot, &player),
+            None => 0,
+        }
+    }
+
+    /// Get the reward curve attached to a closed period
+    pub fn get_period_rewards(
+        env: Env,
+        key: BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+    ) -> Option<RewardCurve> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SnapshotRewards(key, period, period_id))
+    }
+
+    /// Check whether a player has claimed their reward for a closed period
+    pub fn has_claimed_reward(
+        env: Env,
+        key: BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+        player: Address,
+    ) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::RewardClaimed(key, period, period_id, player))
     }
 
     // ───────────── VIEW FUNCTIONS ─────────────
@@ -298,14 +740,44 @@ impl LeaderboardContract {
     /// * `period` - The time period (Daily, Weekly, AllTime)
     /// * `limit` - Maximum number of players to return
     pub fn get_top_players(env: Env, period: TimePeriod, limit: u32) -> Vec<PlayerScore> {
-        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        Self::get_board_top_players(env, BoardKey::Global, period, limit)
+    }
+
+    /// Get a player's rank for a specific time period
+    /// Returns 0 if player not found in top rankings
+    ///
+    /// # Arguments
+    /// * `player` - Player address
+    /// * `period` - The time period (Daily, Weekly, AllTime)
+    pub fn get_player_rank(env: Env, player: Address, period: TimePeriod) -> u32 {
+        Self::get_board_player_rank(env, BoardKey::Global, player, period)
+    }
+
+    /// Get a player's score for a specific time period
+    pub fn get_player_score(env: Env, player: Address, period: TimePeriod) -> Option<PlayerScore> {
+        Self::get_board_player_score(env, BoardKey::Global, player, period)
+    }
+
+    /// Get a board's definition
+    pub fn get_board(env: Env, key: BoardKey) -> Option<Board> {
+        if key == BoardKey::Global {
+            return Some(Self::load_board(&env, &key));
+        }
+        env.storage().persistent().get(&DataKey::Board(key))
+    }
+
+    /// Get top N players on a board for a specific time period
+    pub fn get_board_top_players(
+        env: Env,
+        key: BoardKey,
+        period: TimePeriod,
+        limit: u32,
+    ) -> Vec<PlayerScore> {
+        let config = Self::load_config(&env);
         let current_time = env.ledger().timestamp();
         let period_id = Self::get_current_period_id(&config, period, current_time);
 
-        let top_scores: Vec<PlayerScore> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::TopScores(period, period_id))
+        let top_scores = Self::read_top_scores(&env, &key, period, period_id)
             .unwrap_or(Vec::new(&env));
 
         // Return limited results
@@ -323,42 +795,38 @@ impl LeaderboardContract {
         result
     }
 
-    /// Get a player's rank for a specific time period
+    /// Get a player's rank on a board for a specific time period
     /// Returns 0 if player not found in top rankings
-    ///
-    /// # Arguments
-    /// * `player` - Player address
-    /// * `period` - The time period (Daily, Weekly, AllTime)
-    pub fn get_player_rank(env: Env, player: Address, period: TimePeriod) -> u32 {
-        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+    pub fn get_board_player_rank(env: Env, key: BoardKey, player: Address, period: TimePeriod) -> u32 {
+        let config = Self::load_config(&env);
         let current_time = env.ledger().timestamp();
         let period_id = Self::get_current_period_id(&config, period, current_time);
 
-        let top_scores: Vec<PlayerScore> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::TopScores(period, period_id))
+        let top_scores = Self::read_top_scores(&env, &key, period, period_id)
             .unwrap_or(Vec::new(&env));
 
         for i in 0..top_scores.len() {
             let score = top_scores.get(i).unwrap();
             if score.player == player {
-                return (i + 1) as u32; // Rank is 1-indexed
+                return i + 1; // Rank is 1-indexed
             }
         }
 
         0 // Not ranked
     }
 
-    /// Get a player's score for a specific time period
-    pub fn get_player_score(env: Env, player: Address, period: TimePeriod) -> Option<PlayerScore> {
-        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+    /// Get a player's score on a board for a specific time period
+    pub fn get_board_player_score(
+        env: Env,
+        key: BoardKey,
+        player: Address,
+        period: TimePeriod,
+    ) -> Option<PlayerScore> {
+        let config = Self::load_config(&env);
         let current_time = env.ledger().timestamp();
         let period_id = Self::get_current_period_id(&config, period, current_time);
 
-        env.storage()
-            .persistent()
-            .get(&DataKey::PlayerScore(player, period, period_id))
+        Self::read_player_score(&env, &key, &player, period, period_id)
     }
 
     /// Get a player's cumulative all-time score
@@ -371,10 +839,12 @@ impl LeaderboardContract {
 
     /// Get the current high score record for a time period type
     pub fn get_high_score(env: Env, period: TimePeriod) -> i128 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::HighScore(period))
-            .unwrap_or(0)
+        Self::get_board_high_score(env, BoardKey::Global, period)
+    }
+
+    /// Get the current high score record on a board for a time period type
+    pub fn get_board_high_score(env: Env, key: BoardKey, period: TimePeriod) -> i128 {
+        Self::read_high_score(&env, &key, period)
     }
 
     /// Get total number of unique players
@@ -387,7 +857,7 @@ impl LeaderboardContract {
 
     /// Get the current configuration
     pub fn get_config(env: Env) -> LeaderboardConfig {
-        env.storage().persistent().get(&DataKey::Config).unwrap()
+        Self::load_config(&env)
     }
 
     /// Check if an address is an authorized verifier
@@ -400,7 +870,7 @@ impl LeaderboardContract {
 
     /// Get the current period ID for a time period type
     pub fn get_current_period_id_view(env: Env, period: TimePeriod) -> u64 {
-        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(&env);
         let current_time = env.ledger().timestamp();
         Self::get_current_period_id(&config, period, current_time)
     }
@@ -415,24 +885,219 @@ impl LeaderboardContract {
         }
     }
 
+    fn load_config(env: &Env) -> LeaderboardConfig {
+        let raw: Val = env.storage().persistent().get(&DataKey::Config).unwrap();
+        // Decoding a struct with the wrong field count traps, so check the
+        // stored field names first
+        let fields = Map::<Symbol, Val>::try_from_val(env, &raw).unwrap();
+        if fields.contains_key(Symbol::new(env, "snapshot_size")) {
+            return LeaderboardConfig::try_from_val(env, &raw).unwrap();
+        }
+        let legacy = LegacyLeaderboardConfig::try_from_val(env, &raw).unwrap();
+        LeaderboardConfig {
+            admin: legacy.admin,
+            max_top_entries: legacy.max_top_entries,
+            daily_period_length: legacy.daily_period_length,
+            weekly_period_length: legacy.weekly_period_length,
+            snapshot_size: DEFAULT_SNAPSHOT_SIZE,
+            paused: legacy.paused,
+        }
+    }
+
+    fn load_board(env: &Env, key: &BoardKey) -> Board {
+        let board: Option<Board> = env.storage().persistent().get(&DataKey::Board(key.clone()));
+        match board {
+            Some(board) => board,
+            // Contracts initialized before boards existed have no stored global board
+            None if *key == BoardKey::Global => Board {
+                key: BoardKey::Global,
+                aggregation: Aggregation::Sum,
+                creator: Self::load_config(env).admin,
+                created_at: 0,
+            },
+            None => panic!("Board not found"),
+        }
+    }
+
+    fn read_player_score(
+        env: &Env,
+        key: &BoardKey,
+        player: &Address,
+        period: TimePeriod,
+        period_id: u64,
+    ) -> Option<PlayerScore> {
+        let score = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PlayerScore(key.clone(), player.clone(), period, period_id));
+        if score.is_some() || *key != BoardKey::Global {
+            return score;
+        }
+        env.storage()
+            .persistent()
+            .get(&LegacyDataKey::PlayerScore(player.clone(), period, period_id))
+    }
+
+    fn read_top_scores(
+        env: &Env,
+        key: &BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+    ) -> Option<Vec<PlayerScore>> {
+        let top_scores = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TopScores(key.clone(), period, period_id));
+        if top_scores.is_some() || *key != BoardKey::Global {
+            return top_scores;
+        }
+        env.storage()
+            .persistent()
+            .get(&LegacyDataKey::TopScores(period, period_id))
+    }
+
+    fn read_high_score(env: &Env, key: &BoardKey, period: TimePeriod) -> i128 {
+        let high: Option<i128> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::HighScore(key.clone(), period));
+        match high {
+            Some(high) => high,
+            None if *key == BoardKey::Global => env
+                .storage()
+                .persistent()
+                .get(&LegacyDataKey::HighScore(period))
+                .unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    fn update_board_scores(
+        env: &Env,
+        config: &LeaderboardConfig,
+        board: &Board,
+        player: &Address,
+        score: i128,
+        current_time: u64,
+    ) {
+        // Calculate period IDs
+        let daily_period_id = current_time / config.daily_period_length;
+        let weekly_period_id = current_time / config.weekly_period_length;
+        let all_time_period_id = 0u64; // All-time uses 0 as period ID
+
+        // Freeze the previous daily/weekly ranking if this submission starts a new period
+        Self::roll_period(env, config, &board.key, TimePeriod::Daily, daily_period_id, current_time);
+        Self::roll_period(env, config, &board.key, TimePeriod::Weekly, weekly_period_id, current_time);
+
+        // Update scores for each time period
+        Self::update_period_score(env, config, board, player, score, TimePeriod::Daily, daily_period_id, current_time);
+        Self::update_period_score(env, config, board, player, score, TimePeriod::Weekly, weekly_period_id, current_time);
+        Self::update_period_score(env, config, board, player, score, TimePeriod::AllTime, all_time_period_id, current_time);
+    }
+
+    fn roll_period(
+        env: &Env,
+        config: &LeaderboardConfig,
+        key: &BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+        current_time: u64,
+    ) {
+        if period == TimePeriod::AllTime {
+            return;
+        }
+
+        let last_key = DataKey::LastPeriod(key.clone(), period);
+        let last: Option<u64> = env.storage().persistent().get(&last_key);
+        if last == Some(period_id) {
+            return;
+        }
+
+        if let Some(last_id) = last {
+            if last_id < period_id
+                && !env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::Snapshot(key.clone(), period, last_id))
+            {
+                Self::freeze_period(env, config, key, period, last_id, current_time);
+            }
+        }
+
+        env.storage().persistent().set(&last_key, &period_id);
+    }
+
+    fn freeze_period(
+        env: &Env,
+        config: &LeaderboardConfig,
+        key: &BoardKey,
+        period: TimePeriod,
+        period_id: u64,
+        current_time: u64,
+    ) {
+        let top_scores = Self::read_top_scores(env, key, period, period_id)
+            .unwrap_or(Vec::new(env));
+
+        let mut entries = Vec::new(env);
+        for i in 0..top_scores.len().min(config.snapshot_size) {
+            entries.push_back(top_scores.get(i).unwrap());
+        }
+
+        let snapshot = PeriodSnapshot {
+            board: key.clone(),
+            period,
+            period_id,
+            entries,
+            closed_at: current_time,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Snapshot(key.clone(), period, period_id), &snapshot);
+
+        env.events().publish(
+            (PERIOD_CLOSED, key.clone(), period),
+            (period_id, snapshot.entries.len()),
+        );
+    }
+
+    fn snapshot_rank(snapshot: &PeriodSnapshot, player: &Address) -> u32 {
+        for i in 0..snapshot.entries.len() {
+            if snapshot.entries.get(i).unwrap().player == *player {
+                return i + 1;
+            }
+        }
+        0
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn update_period_score(
         env: &Env,
         config: &LeaderboardConfig,
+        board: &Board,
         player: &Address,
         score: i128,
         period: TimePeriod,
         period_id: u64,
         current_time: u64,
     ) {
+        let key = &board.key;
+
         // Get existing score for this period
-        let existing_score: Option<PlayerScore> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlayerScore(player.clone(), period, period_id));
+        let existing_score = Self::read_player_score(env, key, player, period, period_id);
 
         let new_score = match existing_score {
             Some(mut existing) => {
-                existing.score += score;
+                existing.score = match board.aggregation {
+                    Aggregation::Sum => existing.score + score,
+                    Aggregation::Latest => score,
+                    Aggregation::Best => {
+                        if score <= existing.score {
+                            // Not an improvement, ranking is unchanged
+                            return;
+                        }
+                        score
+                    }
+                };
                 existing.timestamp = current_time;
                 existing
             }
@@ -447,42 +1112,43 @@ impl LeaderboardContract {
 
         // Save player's score
         env.storage().persistent().set(
-            &DataKey::PlayerScore(player.clone(), period, period_id),
+            &DataKey::PlayerScore(key.clone(), player.clone(), period, period_id),
             &new_score,
         );
 
         // Update top scores list
-        Self::update_top_scores_list(env, config, &new_score, period, period_id);
+        Self::update_top_scores_list(env, config, key, &new_score, period, period_id);
 
         // Check and update high score record
-        let current_high: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::HighScore(period))
-            .unwrap_or(0);
+        let current_high = Self::read_high_score(env, key, period);
 
         if new_score.score > current_high {
             env.storage()
                 .persistent()
-                .set(&DataKey::HighScore(period), &new_score.score);
+                .set(&DataKey::HighScore(key.clone(), period), &new_score.score);
 
-            // Emit new high score event
-            env.events()
-                .publish((NEW_HIGH_SCORE, period), (player.clone(), new_score.score));
+            // Emit new high score event; the global board keeps its original topic
+            if *key == BoardKey::Global {
+                env.events()
+                    .publish((NEW_HIGH_SCORE, period), (player.clone(), new_score.score));
+            } else {
+                env.events().publish(
+                    (BOARD_HIGH_SCORE, key.clone(), period),
+                    (player.clone(), new_score.score),
+                );
+            }
         }
     }
 
     fn update_top_scores_list(
         env: &Env,
         config: &LeaderboardConfig,
+        key: &BoardKey,
         player_score: &PlayerScore,
         period: TimePeriod,
         period_id: u64,
     ) {
-        let top_scores: Vec<PlayerScore> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::TopScores(period, period_id))
+        let top_scores = Self::read_top_scores(env, key, period, period_id)
             .unwrap_or(Vec::new(env));
 
         // Remove existing entry for this player if present
@@ -521,38 +1187,38 @@ impl LeaderboardContract {
         // If not inserted yet and list not full, append
         if !inserted && final_list.len() < config.max_top_entries {
             final_list.push_back(player_score.clone());
-            new_rank = final_list.len() as u32;
+            new_rank = final_list.len();
         }
 
         // Emit rank change event if rank changed
         if new_rank > 0 && new_rank != old_rank {
             env.events().publish(
                 (RANK_CHANGE, player_score.player.clone()),
-                (period, old_rank, new_rank),
+                (key.clone(), period, old_rank, new_rank),
             );
         }
 
         env.storage()
             .persistent()
-            .set(&DataKey::TopScores(period, period_id), &final_list);
+            .set(&DataKey::TopScores(key.clone(), period, period_id), &final_list);
     }
 
     fn assert_admin(env: &Env, user: &Address) {
-        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(env);
         if config.admin != *user {
             panic!("Admin only");
         }
     }
 
     fn assert_not_paused(env: &Env) {
-        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(env);
         if config.paused {
             panic!("Contract is paused");
         }
     }
 
     fn assert_authorized_submitter(env: &Env, submitter: &Address) {
-        let config: LeaderboardConfig = env.storage().persistent().get(&DataKey::Config).unwrap();
+        let config = Self::load_config(env);
 
         // Admin is always authorized
         if config.admin == *submitter {
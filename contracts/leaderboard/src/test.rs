@@ -2,11 +2,11 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, Env,
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, IntoVal, TryFromVal, Val, Vec,
 };
 
-fn setup_contract(env: &Env) -> (LeaderboardContractClient, Address) {
+fn setup_contract(env: &Env) -> (LeaderboardContractClient<'_>, Address) {
     let admin = Address::generate(env);
     let contract_id = env.register_contract(None, LeaderboardContract);
     let client = LeaderboardContractClient::new(env, &contract_id);
@@ -626,3 +626,514 @@ fn test_zero_score_submission() {
     assert!(score.is_some());
     assert_eq!(score.unwrap().score, 0);
 }
+
+#[test]
+fn test_global_board_exists_after_init() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_contract(&env);
+
+    let board = client.get_board(&BoardKey::Global).unwrap();
+    assert_eq!(board.aggregation, Aggregation::Sum);
+    assert_eq!(board.creator, admin);
+    assert!(client.get_board(&BoardKey::Puzzle(1)).is_none());
+}
+
+#[test]
+fn test_verifier_creates_board_on_demand() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let (client, admin) = setup_contract(&env);
+    let verifier = Address::generate(&env);
+    client.add_verifier(&admin, &verifier);
+
+    let key = BoardKey::Category(PuzzleCategory::Logic);
+    client.create_board(&verifier, &key, &Aggregation::Best);
+
+    let board = client.get_board(&key).unwrap();
+    assert_eq!(board.key, key);
+    assert_eq!(board.aggregation, Aggregation::Best);
+    assert_eq!(board.creator, verifier);
+    assert_eq!(board.created_at, 1000);
+}
+
+#[test]
+#[should_panic(expected = "Board already exists")]
+fn test_create_duplicate_board() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_contract(&env);
+
+    client.create_board(&admin, &BoardKey::Season(1), &Aggregation::Sum);
+    client.create_board(&admin, &BoardKey::Season(1), &Aggregation::Best);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized submitter")]
+fn test_create_board_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin) = setup_contract(&env);
+    let stranger = Address::generate(&env);
+
+    client.create_board(&stranger, &BoardKey::Puzzle(7), &Aggregation::Best);
+}
+
+#[test]
+#[should_panic(expected = "Board not found")]
+fn test_submit_to_missing_board() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_contract(&env);
+    let player = Address::generate(&env);
+
+    client.submit_board_score(&admin, &BoardKey::Puzzle(7), &player, &100);
+}
+
+#[test]
+fn test_board_aggregation_modes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let (client, admin) = setup_contract(&env);
+    let player = Address::generate(&env);
+
+    let best = BoardKey::Puzzle(1);
+    let sum = BoardKey::Puzzle(2);
+    let latest = BoardKey::Puzzle(3);
+    client.create_board(&admin, &best, &Aggregation::Best);
+    client.create_board(&admin, &sum, &Aggregation::Sum);
+    client.create_board(&admin, &latest, &Aggregation::Latest);
+
+    for key in [best.clone(), sum.clone(), latest.clone()] {
+        client.submit_board_score(&admin, &key, &player, &500);
+        client.submit_board_score(&admin, &key, &player, &800);
+        client.submit_board_score(&admin, &key, &player, &300);
+    }
+
+    let score_of = |key: &BoardKey| {
+        client
+            .get_board_player_score(key, &player, &TimePeriod::AllTime)
+            .unwrap()
+            .score
+    };
+    assert_eq!(score_of(&best), 800);
+    assert_eq!(score_of(&sum), 1600);
+    assert_eq!(score_of(&latest), 300);
+
+    // High score tracks the best value seen regardless of aggregation
+    assert_eq!(client.get_board_high_score(&latest, &TimePeriod::AllTime), 800);
+}
+
+#[test]
+fn test_boards_rank_independently() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let (client, admin) = setup_contract(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let puzzle = BoardKey::Puzzle(42);
+    let season = BoardKey::Season(3);
+    client.create_board(&admin, &puzzle, &Aggregation::Best);
+    client.create_board(&admin, &season, &Aggregation::Sum);
+
+    client.submit_board_score(&admin, &puzzle, &alice, &900);
+    client.submit_board_score(&admin, &puzzle, &bob, &400);
+    client.submit_board_score(&admin, &season, &alice, &100);
+    client.submit_board_score(&admin, &season, &bob, &700);
+
+    assert_eq!(client.get_board_player_rank(&puzzle, &alice, &TimePeriod::Daily), 1);
+    assert_eq!(client.get_board_player_rank(&puzzle, &bob, &TimePeriod::Daily), 2);
+    assert_eq!(client.get_board_player_rank(&season, &bob, &TimePeriod::Weekly), 1);
+    assert_eq!(client.get_board_player_rank(&season, &alice, &TimePeriod::Weekly), 2);
+
+    let top = client.get_board_top_players(&season, &TimePeriod::AllTime, &10);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top.get(0).unwrap().player, bob);
+
+    // Board submissions stay off the global board and all-time totals
+    assert_eq!(client.get_player_rank(&alice, &TimePeriod::AllTime), 0);
+    assert_eq!(client.get_top_players(&TimePeriod::AllTime, &10).len(), 0);
+    assert_eq!(client.get_player_all_time_total(&alice), 0);
+    assert_eq!(client.get_total_players(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Use submit_score for the global board")]
+fn test_submit_board_score_rejects_global() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_contract(&env);
+    let player = Address::generate(&env);
+
+    client.submit_board_score(&admin, &BoardKey::Global, &player, &100);
+}
+
+fn setup_reward_token<'a>(env: &Env, admin: &Address, amount: i128) -> token::Client<'a> {
+    let token_admin = Address::generate(env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    token::StellarAssetClient::new(env, &token_id).mint(admin, &amount);
+    token::Client::new(env, &token_id)
+}
+
+#[test]
+fn test_period_rollover_freezes_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin) = setup_contract(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.submit_score(&admin, &alice, &1000);
+    client.submit_score(&admin, &bob, &2000);
+    assert!(client
+        .get_period_snapshot(&BoardKey::Global, &TimePeriod::Daily, &0)
+        .is_none());
+
+    // First submission of day 1 closes day 0
+    env.ledger().set_timestamp(86_400);
+    client.submit_score(&admin, &alice, &10);
+
+    let snapshot = client
+        .get_period_snapshot(&BoardKey::Global, &TimePeriod::Daily, &0)
+        .unwrap();
+    assert_eq!(snapshot.period_id, 0);
+    assert_eq!(snapshot.closed_at, 86_400);
+    assert_eq!(snapshot.entries.len(), 2);
+    assert_eq!(snapshot.entries.get(0).unwrap().player, bob);
+    assert_eq!(
+        client.get_snapshot_rank(&BoardKey::Global, &TimePeriod::Daily, &0, &alice),
+        2
+    );
+
+    // Weekly period is still open
+    assert!(client
+        .get_period_snapshot(&BoardKey::Global, &TimePeriod::Weekly, &0)
+        .is_none());
+}
+
+#[test]
+fn test_snapshot_size_limits_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin) = setup_contract(&env);
+    client.update_snapshot_size(&admin, &2);
+
+    for score in [100i128, 200, 300] {
+        let player = Address::generate(&env);
+        client.submit_score(&admin, &player, &score);
+    }
+
+    env.ledger().set_timestamp(86_400);
+    client.close_period(&BoardKey::Global, &TimePeriod::Daily, &0);
+
+    let snapshot = client
+        .get_period_snapshot(&BoardKey::Global, &TimePeriod::Daily, &0)
+        .unwrap();
+    assert_eq!(snapshot.entries.len(), 2);
+    assert_eq!(snapshot.entries.get(0).unwrap().score, 300);
+}
+
+#[test]
+fn test_legacy_config_upgraded_on_read() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_contract(&env);
+    let legacy = LegacyLeaderboardConfig {
+        admin: admin.clone(),
+        max_top_entries: 50,
+        daily_period_length: 86_400,
+        weekly_period_length: 604_800,
+        paused: false,
+    };
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&DataKey::Config, &legacy);
+    });
+
+    let config = client.get_config();
+    assert_eq!(config.max_top_entries, 50);
+    assert_eq!(config.snapshot_size, 10);
+
+    // The next admin update stores the current shape
+    client.update_snapshot_size(&admin, &3);
+    assert_eq!(client.get_config().snapshot_size, 3);
+    assert_eq!(client.get_config().max_top_entries, 50);
+}
+
+#[test]
+#[should_panic(expected = "Period still open")]
+fn test_close_current_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, _admin) = setup_contract(&env);
+    client.close_period(&BoardKey::Global, &TimePeriod::Daily, &0);
+}
+
+#[test]
+#[should_panic(expected = "Period already closed")]
+fn test_close_period_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin) = setup_contract(&env);
+    client.submit_score(&admin, &Address::generate(&env), &10);
+
+    env.ledger().set_timestamp(86_400 * 2);
+    client.close_period(&BoardKey::Global, &TimePeriod::Daily, &0);
+    client.close_period(&BoardKey::Global, &TimePeriod::Daily, &0);
+}
+
+#[test]
+fn test_close_period_requires_board_with_scores() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(86_400 * 2);
+
+    let (client, _admin) = setup_contract(&env);
+    assert!(client
+        .try_close_period(&BoardKey::Puzzle(99), &TimePeriod::Daily, &0)
+        .is_err());
+    assert!(client
+        .try_close_period(&BoardKey::Global, &TimePeriod::Daily, &0)
+        .is_err());
+    assert!(client
+        .get_period_snapshot(&BoardKey::Global, &TimePeriod::Daily, &0)
+        .is_none());
+}
+
+#[test]
+fn test_sweep_unclaimed_rewards_after_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin) = setup_contract(&env);
+    let token = setup_reward_token(&env, &admin, 1_000);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.submit_score(&admin, &alice, &500);
+    client.submit_score(&admin, &bob, &300);
+
+    env.ledger().set_timestamp(86_400);
+    client.close_period(&BoardKey::Global, &TimePeriod::Daily, &0);
+    client.attach_period_rewards(
+        &admin,
+        &BoardKey::Global,
+        &TimePeriod::Daily,
+        &0,
+        &token.address,
+        &soroban_sdk::vec![&env, 100i128, 50],
+    );
+    client.claim_period_reward(&alice, &BoardKey::Global, &TimePeriod::Daily, &0);
+
+    // Nothing can be swept while winners may still claim
+    assert!(client
+        .try_sweep_period_rewards(&admin, &BoardKey::Global, &TimePeriod::Daily, &0)
+        .is_err());
+
+    env.ledger().set_timestamp(86_400 + 2_592_001);
+    assert!(client
+        .try_claim_period_reward(&bob, &BoardKey::Global, &TimePeriod::Daily, &0)
+        .is_err());
+    assert_eq!(
+        client.sweep_period_rewards(&admin, &BoardKey::Global, &TimePeriod::Daily, &0),
+        50
+    );
+    assert_eq!(token.balance(&admin), 900);
+    assert_eq!(
+        client.sweep_period_rewards(&admin, &BoardKey::Global, &TimePeriod::Daily, &0),
+        0
+    );
+}
+
+#[test]
+fn test_board_period_snapshot_and_reward_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin) = setup_contract(&env);
+    let token = setup_reward_token(&env, &admin, 1_000);
+
+    let key = BoardKey::Puzzle(5);
+    client.create_board(&admin, &key, &Aggregation::Best);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.submit_board_score(&admin, &key, &alice, &900);
+    client.submit_board_score(&admin, &key, &bob, &400);
+
+    env.ledger().set_timestamp(604_800);
+    client.close_period(&key, &TimePeriod::Weekly, &0);
+
+    // Third place is unfunded because only two players ranked
+    let amounts = soroban_sdk::vec![&env, 300i128, 100, 50];
+    client.attach_period_rewards(&admin, &key, &TimePeriod::Weekly, &0, &token.address, &amounts);
+
+    let curve = client.get_period_rewards(&key, &TimePeriod::Weekly, &0).unwrap();
+    assert_eq!(curve.funded, 400);
+    assert_eq!(token.balance(&admin), 600);
+    assert_eq!(token.balance(&client.address), 400);
+
+    assert_eq!(client.claim_period_reward(&alice, &key, &TimePeriod::Weekly, &0), 300);
+    assert_eq!(client.claim_period_reward(&bob, &key, &TimePeriod::Weekly, &0), 100);
+    assert_eq!(token.balance(&alice), 300);
+    assert_eq!(token.balance(&bob), 100);
+    assert!(client.has_claimed_reward(&key, &TimePeriod::Weekly, &0, &alice));
+    assert_eq!(
+        client.get_period_rewards(&key, &TimePeriod::Weekly, &0).unwrap().claimed,
+        400
+    );
+}
+
+#[test]
+#[should_panic(expected = "Reward already claimed")]
+fn test_double_reward_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin) = setup_contract(&env);
+    let token = setup_reward_token(&env, &admin, 1_000);
+    let alice = Address::generate(&env);
+    client.submit_score(&admin, &alice, &500);
+
+    env.ledger().set_timestamp(86_400);
+    client.close_period(&BoardKey::Global, &TimePeriod::Daily, &0);
+    client.attach_period_rewards(
+        &admin,
+        &BoardKey::Global,
+        &TimePeriod::Daily,
+        &0,
+        &token.address,
+        &soroban_sdk::vec![&env, 100i128],
+    );
+
+    client.claim_period_reward(&alice, &BoardKey::Global, &TimePeriod::Daily, &0);
+    client.claim_period_reward(&alice, &BoardKey::Global, &TimePeriod::Daily, &0);
+}
+
+#[test]
+#[should_panic(expected = "Player not ranked")]
+fn test_unranked_player_cannot_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let (client, admin) = setup_contract(&env);
+    let token = setup_reward_token(&env, &admin, 1_000);
+    let alice = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    client.submit_score(&admin, &alice, &500);
+
+    env.ledger().set_timestamp(86_400);
+    client.close_period(&BoardKey::Global, &TimePeriod::Daily, &0);
+    client.attach_period_rewards(
+        &admin,
+        &BoardKey::Global,
+        &TimePeriod::Daily,
+        &0,
+        &token.address,
+        &soroban_sdk::vec![&env, 100i128],
+    );
+
+    client.claim_period_reward(&stranger, &BoardKey::Global, &TimePeriod::Daily, &0);
+}
+
+#[test]
+fn test_global_high_score_event_keeps_topic() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_contract(&env);
+    let player = Address::generate(&env);
+    client.submit_score(&admin, &player, &50);
+
+    let expected_topics: Vec<Val> = (NEW_HIGH_SCORE, TimePeriod::Daily).into_val(&env);
+    let found = env.events().all().iter().any(|(_, topics, data)| {
+        topics == expected_topics
+            && <(Address, i128)>::try_from_val(&env, &data) == Ok((player.clone(), 50))
+    });
+    assert!(found);
+}
+
+#[test]
+fn test_pre_board_storage_keeps_global_scores() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    // Lay storage out as a contract initialized before boards existed
+    let admin = Address::generate(&env);
+    let veteran = Address::generate(&env);
+    let contract_id = env.register_contract(None, LeaderboardContract);
+    let client = LeaderboardContractClient::new(&env, &contract_id);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        let config = LegacyLeaderboardConfig {
+            admin: admin.clone(),
+            max_top_entries: 100,
+            daily_period_length: 86_400,
+            weekly_period_length: 604_800,
+            paused: false,
+        };
+        storage.set(&DataKey::Config, &config);
+        storage.set(&DataKey::TotalPlayers, &1u32);
+        storage.set(&DataKey::PlayerAllTimeScore(veteran.clone()), &500i128);
+
+        let score = PlayerScore {
+            player: veteran.clone(),
+            score: 500,
+            timestamp: 900,
+            period: TimePeriod::AllTime,
+            period_id: 0,
+        };
+        let mut top = Vec::new(&env);
+        top.push_back(score.clone());
+        storage.set(&LegacyDataKey::PlayerScore(veteran.clone(), TimePeriod::AllTime, 0), &score);
+        storage.set(&LegacyDataKey::TopScores(TimePeriod::AllTime, 0), &top);
+        storage.set(&LegacyDataKey::HighScore(TimePeriod::AllTime), &500i128);
+    });
+
+    // Existing data reads through the global board
+    assert_eq!(client.get_board(&BoardKey::Global).unwrap().aggregation, Aggregation::Sum);
+    assert_eq!(client.get_player_score(&veteran, &TimePeriod::AllTime).unwrap().score, 500);
+    assert_eq!(client.get_player_rank(&veteran, &TimePeriod::AllTime), 1);
+    assert_eq!(client.get_high_score(&TimePeriod::AllTime), 500);
+
+    // New submissions build on the old entries
+    let newcomer = Address::generate(&env);
+    client.submit_score(&admin, &newcomer, &400);
+    client.submit_score(&admin, &veteran, &300);
+
+    assert_eq!(client.get_player_score(&veteran, &TimePeriod::AllTime).unwrap().score, 800);
+    assert_eq!(client.get_player_all_time_total(&veteran), 800);
+    assert_eq!(client.get_total_players(), 2);
+    assert_eq!(client.get_high_score(&TimePeriod::AllTime), 800);
+
+    let top = client.get_top_players(&TimePeriod::AllTime, &10);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top.get(0).unwrap().player, veteran);
+    assert_eq!(top.get(1).unwrap().player, newcomer);
+}
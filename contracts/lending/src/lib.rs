@@ -483,12 +483,23 @@ impl LendingContract {
                 token_client.transfer(from, to, &asset.amount);
             }
             AssetType::NFT => {
-                let transfer_args = (from.clone(), to.clone(), asset.nft_id);
-                env.invoke_contract::<()>(
-                    &asset.contract,
-                    &Symbol::new(env, "transfer"),
-                    transfer_args.into_val(env),
-                );
+                // Moving a user's NFT needs this contract's approval on the NFT contract
+                let this = env.current_contract_address();
+                if *from == this {
+                    let transfer_args = (from.clone(), to.clone(), asset.nft_id);
+                    env.invoke_contract::<()>(
+                        &asset.contract,
+                        &Symbol::new(env, "transfer"),
+                        transfer_args.into_val(env),
+                    );
+                } else {
+                    let transfer_args = (this, from.clone(), to.clone(), asset.nft_id);
+                    env.invoke_contract::<()>(
+                        &asset.contract,
+                        &Symbol::new(env, "transfer_from"),
+                        transfer_args.into_val(env),
+                    );
+                }
             }
         }
     }
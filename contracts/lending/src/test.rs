@@ -1,10 +1,10 @@
 #![cfg(test)]
 
 use super::*;
+use achievement_nft::{AchievementNFT, AchievementNFTClient};
 use soroban_sdk::{
-    contract, contractimpl, contracttype,
     testutils::{Address as _, Ledger},
-    token, Address, Env,
+    token, Address, Env, String,
 };
 
 fn setup_token<'a>(
@@ -213,13 +213,16 @@ fn test_nft_loan_repayment_flow() {
     repayment_admin_client.mint(&borrower, &500);
     collateral_admin_client.mint(&borrower, &2_000);
 
-    let nft_contract_id = env.register_contract(None, MockNft);
-    let nft_client = MockNftClient::new(&env, &nft_contract_id);
-    let nft_id: u32 = 1;
-    nft_client.mint(&lender, &nft_id);
+    let nft_contract_id = env.register_contract(None, AchievementNFT);
+    let nft_client = AchievementNFTClient::new(&env, &nft_contract_id);
+    let nft_admin = Address::generate(&env);
+    nft_client.initialize(&nft_admin);
+    let nft_id = nft_client.craftmint(&nft_admin, &lender, &1, &String::from_str(&env, "Loaned"));
 
     let contract_id = env.register_contract(None, LendingContract);
     let client = LendingContractClient::new(&env, &contract_id);
+    // The lender lets the lending contract escrow the NFT
+    nft_client.approve(&lender, &contract_id, &nft_id, &(env.ledger().sequence() + 1_000));
 
     let offer_id = client.create_offer(
         &lender,
@@ -248,6 +251,9 @@ fn test_nft_loan_repayment_flow() {
     assert_eq!(nft_client.owner_of(&nft_id), borrower.clone());
 
     env.ledger().set_timestamp(100);
+    // Returning the NFT also needs the borrower's approval
+    assert!(client.try_repay(&borrower, &loan_id, &330).is_err());
+    nft_client.approve(&borrower, &contract_id, &nft_id, &(env.ledger().sequence() + 1_000));
     client.repay(&borrower, &loan_id, &330);
 
     let loan = client.get_loan(&loan_id).unwrap();
@@ -256,42 +262,3 @@ fn test_nft_loan_repayment_flow() {
     assert_eq!(collateral_client.balance(&borrower), 2_000);
     assert_eq!(repayment_client.balance(&lender), 330);
 }
-
-#[contracttype]
-enum MockNftDataKey {
-    Owner(u32),
-}
-
-#[contract]
-struct MockNft;
-
-#[contractimpl]
-impl MockNft {
-    pub fn mint(env: Env, to: Address, token_id: u32) {
-        env.storage()
-            .persistent()
-            .set(&MockNftDataKey::Owner(token_id), &to);
-    }
-
-    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
-        from.require_auth();
-        let owner: Address = env
-            .storage()
-            .persistent()
-            .get(&MockNftDataKey::Owner(token_id))
-            .unwrap();
-        if owner != from {
-            panic!("Not owner");
-        }
-        env.storage()
-            .persistent()
-            .set(&MockNftDataKey::Owner(token_id), &to);
-    }
-
-    pub fn owner_of(env: Env, token_id: u32) -> Address {
-        env.storage()
-            .persistent()
-            .get(&MockNftDataKey::Owner(token_id))
-            .unwrap()
-    }
-}
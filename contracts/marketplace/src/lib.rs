@@ -258,19 +258,19 @@ impl MarketplaceContract {
             .set(&DataKey::Listing(listing_id), &listing);
 
         // Update indexes
-        let mut seller_listings = Self::get_listings_by_seller(&env, &seller);
+        let mut seller_listings = Self::get_listings_by_seller(env.clone(), seller.clone());
         seller_listings.push_back(listing_id);
         env.storage()
             .instance()
             .set(&DataKey::ListingsBySeller(seller.clone()), &seller_listings);
 
-        let mut asset_listings = Self::get_listings_by_asset(&env, &asset.contract, &asset.token_id);
+        let mut asset_listings = Self::get_listings_by_asset(env.clone(), asset.contract.clone(), asset.token_id);
         asset_listings.push_back(listing_id);
         env.storage()
             .instance()
             .set(&DataKey::ListingsByAsset(asset.contract.clone(), asset.token_id), &asset_listings);
 
-        let mut active_listings = Self::get_active_listings(&env);
+        let mut active_listings = Self::get_active_listings(env.clone());
         active_listings.push_back(listing_id);
         env.storage()
             .instance()
@@ -408,7 +408,7 @@ impl MarketplaceContract {
             .set(&DataKey::Offer(offer_id), &offer);
 
         // Update indexes
-        let mut listing_offers = Self::get_offers_by_listing(&env, listing_id);
+        let mut listing_offers = Self::get_offers_by_listing(env.clone(), listing_id);
         listing_offers.push_back(offer_id);
         env.storage()
             .instance()
@@ -615,7 +615,7 @@ impl MarketplaceContract {
             .set(&DataKey::CounterOffer(counter_offer_id), &counter_offer);
 
         // Update indexes
-        let mut offer_counters = Self::get_counter_offers_by_offer(&env, offer_id);
+        let mut offer_counters = Self::get_counter_offers_by_offer(env.clone(), offer_id);
         offer_counters.push_back(counter_offer_id);
         env.storage()
             .instance()
@@ -672,13 +672,12 @@ impl MarketplaceContract {
 
         let token_client = token::Client::new(&env, &listing.payment_token);
 
-        // Refund original offer amount
-        token_client.transfer(&env.current_contract_address(), &buyer, &offer.price);
-
-        // Take new payment amount
+        // The original offer stays escrowed; only collect the difference
         let price_difference = counter_offer.price - offer.price;
         if price_difference > 0 {
             token_client.transfer(&buyer, &env.current_contract_address(), &price_difference);
+        } else if price_difference < 0 {
+            token_client.transfer(&env.current_contract_address(), &buyer, &-price_difference);
         }
 
         // Calculate fees and royalties
@@ -818,12 +817,14 @@ impl MarketplaceContract {
         }
     }
 
-    /// Transfer asset to contract (escrow)
+    /// Transfer asset to contract (escrow). The seller must have approved this
+    /// contract for the token (or as an operator) on the NFT contract.
     fn transfer_asset_to_contract(env: &Env, from: &Address, asset: &Asset) {
-        let transfer_args = (from.clone(), env.current_contract_address(), asset.token_id).into_val(env);
+        let this = env.current_contract_address();
+        let transfer_args = (this.clone(), from.clone(), this, asset.token_id).into_val(env);
         env.invoke_contract::<()>(
             &asset.contract,
-            &Symbol::new(env, "transfer"),
+            &Symbol::new(env, "transfer_from"),
             transfer_args,
         );
     }
@@ -868,7 +869,7 @@ impl MarketplaceContract {
             let mut new_history = Vec::new(env);
             let start_index = history.len() - 100;
             for i in start_index..history.len() {
-                new_history.push_back(*history.get(i).unwrap());
+                new_history.push_back(history.get(i).unwrap());
             }
             history = new_history;
         }
@@ -880,7 +881,7 @@ impl MarketplaceContract {
 
     /// Remove listing from active listings
     fn remove_from_active_listings(env: &Env, listing_id: u64) {
-        let mut active_listings = Self::get_active_listings(env);
+        let mut active_listings = Self::get_active_listings(env.clone());
         if let Some(index) = active_listings.first_index_of(listing_id) {
             active_listings.remove(index);
             env.storage()
@@ -891,7 +892,7 @@ impl MarketplaceContract {
 
     /// Refund all offers on a listing
     fn refund_all_offers(env: &Env, listing_id: u64) {
-        let offers = Self::get_offers_by_listing(env, listing_id);
+        let offers = Self::get_offers_by_listing(env.clone(), listing_id);
         let listing: Listing = env
             .storage()
             .instance()
@@ -915,7 +916,7 @@ impl MarketplaceContract {
 
     /// Refund other offers (except the accepted one)
     fn refund_other_offers(env: &Env, listing_id: u64, accepted_offer_id: u64) {
-        let offers = Self::get_offers_by_listing(env, listing_id);
+        let offers = Self::get_offers_by_listing(env.clone(), listing_id);
         let listing: Listing = env
             .storage()
             .instance()
@@ -959,43 +960,43 @@ impl MarketplaceContract {
     }
 
     /// Get all listings by seller
-    pub fn get_listings_by_seller(env: &Env, seller: &Address) -> Vec<u64> {
+    pub fn get_listings_by_seller(env: Env, seller: Address) -> Vec<u64> {
         env.storage()
             .instance()
-            .get(&DataKey::ListingsBySeller(seller.clone()))
-            .unwrap_or(Vec::new(env))
+            .get(&DataKey::ListingsBySeller(seller))
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Get all listings for an asset
-    pub fn get_listings_by_asset(env: &Env, contract: &Address, token_id: &u32) -> Vec<u64> {
+    pub fn get_listings_by_asset(env: Env, contract: Address, token_id: u32) -> Vec<u64> {
         env.storage()
             .instance()
-            .get(&DataKey::ListingsByAsset(contract.clone(), *token_id))
-            .unwrap_or(Vec::new(env))
+            .get(&DataKey::ListingsByAsset(contract, token_id))
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Get all active listings
-    pub fn get_active_listings(env: &Env) -> Vec<u64> {
+    pub fn get_active_listings(env: Env) -> Vec<u64> {
         env.storage()
             .instance()
             .get(&DataKey::ActiveListings)
-            .unwrap_or(Vec::new(env))
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Get all offers for a listing
-    pub fn get_offers_by_listing(env: &Env, listing_id: u64) -> Vec<u64> {
+    pub fn get_offers_by_listing(env: Env, listing_id: u64) -> Vec<u64> {
         env.storage()
             .instance()
             .get(&DataKey::OffersByListing(listing_id))
-            .unwrap_or(Vec::new(env))
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Get all counter offers for an offer
-    pub fn get_counter_offers_by_offer(env: &Env, offer_id: u64) -> Vec<u64> {
+    pub fn get_counter_offers_by_offer(env: Env, offer_id: u64) -> Vec<u64> {
         env.storage()
             .instance()
             .get(&DataKey::CounterOffersByOffer(offer_id))
-            .unwrap_or(Vec::new(env))
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Get price history for an asset
@@ -1013,7 +1014,7 @@ impl MarketplaceContract {
             return None;
         }
 
-        let sum: i128 = history.iter().fold(0i128, |acc, &price| acc + price);
+        let sum: i128 = history.iter().sum();
         Some(sum / history.len() as i128)
     }
 
@@ -1030,7 +1031,7 @@ impl MarketplaceContract {
                 min = price;
             }
         }
-        Some(*min)
+        Some(min)
     }
 
     /// Get maximum price from history
@@ -1046,7 +1047,7 @@ impl MarketplaceContract {
                 max = price;
             }
         }
-        Some(*max)
+        Some(max)
     }
 
     /// Get marketplace configuration
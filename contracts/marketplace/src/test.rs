@@ -1,20 +1,40 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    token, Address, Env, symbol_short,
-};
-
-// Helper function to create a mock NFT contract
-fn create_mock_nft(env: &Env) -> Address {
-    // For testing, we'll use a simple mock that tracks ownership
-    // In a real scenario, this would be the actual NFT contract
-    Address::generate(env)
+use achievement_nft::{AchievementNFT, AchievementNFTClient};
+use soroban_sdk::{testutils::Address as _, token, Address, Env, String};
+
+// Helper function to deploy an achievement NFT collection, returning its address and admin
+fn create_nft(env: &Env) -> (Address, Address) {
+    let nft_contract = env.register_contract(None, AchievementNFT);
+    let nft_admin = Address::generate(env);
+    AchievementNFTClient::new(env, &nft_contract).initialize(&nft_admin);
+    (nft_contract, nft_admin)
+}
+
+// Helper function to mint the next achievement NFT to its first owner and
+// approve the marketplace to escrow it
+fn mint_nft(
+    env: &Env,
+    nft_contract: &Address,
+    nft_admin: &Address,
+    owner: &Address,
+    token_id: u32,
+    marketplace: &Address,
+) {
+    let nft = AchievementNFTClient::new(env, nft_contract);
+    let minted = nft.craftmint(
+        nft_admin,
+        owner,
+        &1,
+        &String::from_str(env, "Listed achievement"),
+    );
+    assert_eq!(minted, token_id);
+    nft.approve(owner, marketplace, &token_id, &(env.ledger().sequence() + 1_000));
 }
 
 // Helper function to create a test asset
-fn create_test_asset(env: &Env, nft_contract: Address, token_id: u32) -> Asset {
+fn create_test_asset(_env: &Env, nft_contract: Address, token_id: u32) -> Asset {
     Asset {
         asset_type: AssetType::NFT,
         contract: nft_contract,
@@ -36,7 +56,7 @@ fn test_initialize() {
         &fee_recipient,
         &250, // 2.5% fee
         &3600, // 1 hour min
-        &86400 * 30, // 30 days max
+        &(86400 * 30), // 30 days max
     );
 
     let config = client.get_config();
@@ -53,8 +73,8 @@ fn test_create_listing() {
     // Setup token
     let token_admin = Address::generate(&env);
     let token_contract_id = env.register_stellar_asset_contract_v2(token_admin.clone()).address();
-    let token_client = token::Client::new(&env, &token_contract_id);
-    let token_admin_client = token::StellarAssetClient::new(&env, &token_contract_id);
+    let _token_client = token::Client::new(&env, &token_contract_id);
+    let _token_admin_client = token::StellarAssetClient::new(&env, &token_contract_id);
 
     // Setup marketplace
     let contract_id = env.register_contract(None, MarketplaceContract);
@@ -62,15 +82,16 @@ fn test_create_listing() {
 
     let admin = Address::generate(&env);
     let fee_recipient = Address::generate(&env);
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Setup seller and NFT
     let seller = Address::generate(&env);
-    let nft_contract = create_mock_nft(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
     let token_id = 1u32;
 
     // Create listing
     let asset = create_test_asset(&env, nft_contract.clone(), token_id);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller, token_id, &contract_id);
     let creator = Some(Address::generate(&env));
     let listing_id = client.create_listing(
         &seller,
@@ -91,11 +112,11 @@ fn test_create_listing() {
 
     // Verify listing appears in seller's listings
     let seller_listings = client.get_listings_by_seller(&seller);
-    assert!(seller_listings.contains(&listing_id));
+    assert!(seller_listings.contains(listing_id));
 
     // Verify listing appears in active listings
     let active_listings = client.get_active_listings();
-    assert!(active_listings.contains(&listing_id));
+    assert!(active_listings.contains(listing_id));
 }
 
 #[test]
@@ -115,7 +136,7 @@ fn test_buy_listing() {
 
     let admin = Address::generate(&env);
     let fee_recipient = Address::generate(&env);
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Setup users
     let seller = Address::generate(&env);
@@ -126,8 +147,9 @@ fn test_buy_listing() {
     token_admin_client.mint(&buyer, &10000);
 
     // Create listing
-    let nft_contract = create_mock_nft(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
     let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller, 1, &contract_id);
     let listing_id = client.create_listing(
         &seller,
         &asset,
@@ -161,7 +183,7 @@ fn test_buy_listing() {
     // Verify price history
     let history = client.get_price_history(&nft_contract, &1u32);
     assert_eq!(history.len(), 1);
-    assert_eq!(history.get(0).unwrap(), &1000);
+    assert_eq!(history.get(0).unwrap(), 1000);
 }
 
 #[test]
@@ -181,7 +203,7 @@ fn test_create_and_accept_offer() {
 
     let admin = Address::generate(&env);
     let fee_recipient = Address::generate(&env);
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Setup users
     let seller = Address::generate(&env);
@@ -192,8 +214,9 @@ fn test_create_and_accept_offer() {
     token_admin_client.mint(&buyer, &10000);
 
     // Create listing
-    let nft_contract = create_mock_nft(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
     let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller, 1, &contract_id);
     let listing_id = client.create_listing(
         &seller,
         &asset,
@@ -257,7 +280,7 @@ fn test_counter_offer() {
 
     let admin = Address::generate(&env);
     let fee_recipient = Address::generate(&env);
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Setup users
     let seller = Address::generate(&env);
@@ -267,8 +290,9 @@ fn test_counter_offer() {
     token_admin_client.mint(&buyer, &10000);
 
     // Create listing
-    let nft_contract = create_mock_nft(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
     let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller, 1, &contract_id);
     let listing_id = client.create_listing(
         &seller,
         &asset,
@@ -302,6 +326,52 @@ fn test_counter_offer() {
 
     // Verify buyer paid the difference (900 - 800 = 100)
     assert_eq!(token_client.balance(&buyer), 10000 - 900);
+    // The escrowed offer plus the difference covers every payout
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_lower_counter_offer_refunds_difference() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // Setup token
+    let token_admin = Address::generate(&env);
+    let token_contract_id = env.register_stellar_asset_contract_v2(token_admin.clone()).address();
+    let token_client = token::Client::new(&env, &token_contract_id);
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_contract_id);
+
+    // Setup marketplace
+    let contract_id = env.register_contract(None, MarketplaceContract);
+    let client = MarketplaceContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
+
+    // Setup users
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    token_admin_client.mint(&buyer, &10000);
+
+    // Create listing and offer
+    let (nft_contract, nft_admin) = create_nft(&env);
+    let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller, 1, &contract_id);
+    let listing_id = client.create_listing(&seller, &asset, &token_contract_id, &1000, &None, &0);
+    let offer_id = client.create_offer(&buyer, &listing_id, &800, &None);
+
+    // Counter below the escrowed offer
+    let counter_offer_id = client.create_counter_offer(&seller, &offer_id, &600, &None);
+    client.accept_counter_offer(&buyer, &counter_offer_id);
+
+    // Buyer gets 200 of the escrowed 800 back
+    assert_eq!(token_client.balance(&buyer), 10000 - 600);
+    // Seller gets: 600 - 15 fee = 585
+    assert_eq!(token_client.balance(&seller), 585);
+    assert_eq!(token_client.balance(&fee_recipient), 15);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(AchievementNFTClient::new(&env, &nft_contract).owner_of(&1), buyer);
 }
 
 #[test]
@@ -320,7 +390,7 @@ fn test_cancel_listing() {
 
     let admin = Address::generate(&env);
     let fee_recipient = Address::generate(&env);
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Setup users
     let seller = Address::generate(&env);
@@ -330,8 +400,9 @@ fn test_cancel_listing() {
     token_admin_client.mint(&buyer, &10000);
 
     // Create listing
-    let nft_contract = create_mock_nft(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
     let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller, 1, &contract_id);
     let listing_id = client.create_listing(
         &seller,
         &asset,
@@ -382,7 +453,7 @@ fn test_cancel_offer() {
 
     let admin = Address::generate(&env);
     let fee_recipient = Address::generate(&env);
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Setup users
     let seller = Address::generate(&env);
@@ -392,8 +463,9 @@ fn test_cancel_offer() {
     token_admin_client.mint(&buyer, &10000);
 
     // Create listing
-    let nft_contract = create_mock_nft(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
     let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller, 1, &contract_id);
     let listing_id = client.create_listing(
         &seller,
         &asset,
@@ -439,7 +511,7 @@ fn test_reject_offer() {
 
     let admin = Address::generate(&env);
     let fee_recipient = Address::generate(&env);
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Setup users
     let seller = Address::generate(&env);
@@ -449,8 +521,9 @@ fn test_reject_offer() {
     token_admin_client.mint(&buyer, &10000);
 
     // Create listing
-    let nft_contract = create_mock_nft(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
     let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller, 1, &contract_id);
     let listing_id = client.create_listing(
         &seller,
         &asset,
@@ -491,11 +564,10 @@ fn test_price_discovery() {
 
     let admin = Address::generate(&env);
     let fee_recipient = Address::generate(&env);
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Setup users
     let seller1 = Address::generate(&env);
-    let seller2 = Address::generate(&env);
     let buyer1 = Address::generate(&env);
     let buyer2 = Address::generate(&env);
 
@@ -503,8 +575,9 @@ fn test_price_discovery() {
     token_admin_client.mint(&buyer1, &10000);
     token_admin_client.mint(&buyer2, &10000);
 
-    let nft_contract = create_mock_nft(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
     let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller1, 1, &contract_id);
 
     // Create and sell first listing
     let listing_id1 = client.create_listing(
@@ -517,9 +590,15 @@ fn test_price_discovery() {
     );
     client.buy(&buyer1, &listing_id1);
 
-    // Create and sell second listing (different price)
+    // Resell the same asset (different price); the new owner approves the escrow
+    AchievementNFTClient::new(&env, &nft_contract).approve(
+        &buyer1,
+        &contract_id,
+        &1,
+        &(env.ledger().sequence() + 1_000),
+    );
     let listing_id2 = client.create_listing(
-        &seller2,
+        &buyer1,
         &asset,
         &token_contract_id,
         &1500,
@@ -531,8 +610,8 @@ fn test_price_discovery() {
     // Check price history
     let history = client.get_price_history(&nft_contract, &1u32);
     assert_eq!(history.len(), 2);
-    assert_eq!(history.get(0).unwrap(), &1000);
-    assert_eq!(history.get(1).unwrap(), &1500);
+    assert_eq!(history.get(0).unwrap(), 1000);
+    assert_eq!(history.get(1).unwrap(), 1500);
 
     // Check average price
     let avg_price = client.get_average_price(&nft_contract, &1u32).unwrap();
@@ -564,7 +643,7 @@ fn test_multiple_offers_refund() {
 
     let admin = Address::generate(&env);
     let fee_recipient = Address::generate(&env);
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Setup users
     let seller = Address::generate(&env);
@@ -578,8 +657,9 @@ fn test_multiple_offers_refund() {
     token_admin_client.mint(&buyer3, &10000);
 
     // Create listing
-    let nft_contract = create_mock_nft(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
     let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+    mint_nft(&env, &nft_contract, &nft_admin, &seller, 1, &contract_id);
     let listing_id = client.create_listing(
         &seller,
         &asset,
@@ -628,7 +708,7 @@ fn test_update_config() {
     let fee_recipient = Address::generate(&env);
     let new_fee_recipient = Address::generate(&env);
 
-    client.initialize(&admin, &fee_recipient, &250, &3600, &86400 * 30);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
 
     // Update config
     client.update_config(
@@ -643,3 +723,34 @@ fn test_update_config() {
     assert_eq!(config.fee_recipient, new_fee_recipient);
     assert_eq!(config.fee_bps, 300);
 }
+
+#[test]
+fn test_listing_requires_marketplace_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract_id = env.register_stellar_asset_contract_v2(token_admin.clone()).address();
+
+    let contract_id = env.register_contract(None, MarketplaceContract);
+    let client = MarketplaceContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    client.initialize(&admin, &fee_recipient, &250, &3600, &(86400 * 30));
+
+    // Minted without approving the marketplace
+    let seller = Address::generate(&env);
+    let (nft_contract, nft_admin) = create_nft(&env);
+    let nft = AchievementNFTClient::new(&env, &nft_contract);
+    nft.craftmint(&nft_admin, &seller, &1, &String::from_str(&env, "Listed achievement"));
+    let asset = create_test_asset(&env, nft_contract.clone(), 1u32);
+
+    let result = client.try_create_listing(&seller, &asset, &token_contract_id, &1000, &None, &0);
+    assert!(result.is_err());
+    assert_eq!(nft.owner_of(&1), seller);
+
+    // An operator approval is enough to escrow the token
+    nft.approve_for_all(&seller, &contract_id, &(env.ledger().sequence() + 1_000));
+    client.create_listing(&seller, &asset, &token_contract_id, &1000, &None, &0);
+    assert_eq!(nft.owner_of(&1), contract_id);
+}
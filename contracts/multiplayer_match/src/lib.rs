@@ -1,17 +1,10 @@
 #![no_std]
 use soroban_sdk::{
-    auth::Context,
     contract, contracterror, contractimpl, contracttype,
-    token, Address, Env, Map, Symbol, Vec, BytesN, Bytes,
-    symbol_short, panic_with_error
+    token, xdr::ToXdr, Address, Env, Map, Vec, BytesN, Bytes,
+    panic_with_error
 };
 
-mod token_spec {
-    soroban_sdk::contractimport!(
-        file = "soroban_token_spec.wasm"  // Assume this is the path or ID for token interface
-    );
-}
-
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -28,10 +21,12 @@ pub enum Error {
     DisputeNotFound = 10,
     AlreadyResolved = 11,
     NotCreator = 12,
+    InvalidOp = 13,
+    DeadlineNotReached = 14,
 }
 
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Status {
     Open,
     Started,
@@ -55,6 +50,8 @@ pub struct MatchData {
     pot: i128,
     create_time: u64,
     join_deadline: u64,
+    submission_duration: u64,
+    reveal_duration: u64,
     submission_deadline: u64,
     reveal_deadline: u64,
     commits: Map<Address, BytesN<32>>,
@@ -75,6 +72,7 @@ pub struct MultiplayerPuzzleMatch;
 #[contractimpl]
 impl MultiplayerPuzzleMatch {
     // Create a new match
+    #[allow(clippy::too_many_arguments)]
     pub fn create_match(
         env: Env,
         creator: Address,
@@ -106,9 +104,11 @@ impl MultiplayerPuzzleMatch {
             min_players,
             players,
             status: Status::Open,
-            pot: 0,
+            pot: entry_fee,
             create_time,
             join_deadline,
+            submission_duration,
+            reveal_duration,
             submission_deadline: 0,  // set when started
             reveal_deadline: 0,
             commits: Map::new(&env),
@@ -117,14 +117,10 @@ impl MultiplayerPuzzleMatch {
             resolved: Map::new(&env),
         };
 
-        env.storage().persistent().set(&DataKey::Match(counter), &match_data);
-
         // Creator pays entry fee
-        Self::transfer_to_contract(&env, &token, &creator, entry_fee);
+        Self::transfer_to_contract(&env, &token, &match_data.creator, entry_fee);
 
-        let mut updated_match: MatchData = env.storage().persistent().get(&DataKey::Match(counter));
-        updated_match.pot += entry_fee;
-        env.storage().persistent().set(&DataKey::Match(counter), &updated_match);
+        env.storage().persistent().set(&DataKey::Match(counter), &match_data);
 
         counter
     }
@@ -199,16 +195,6 @@ impl MultiplayerPuzzleMatch {
 
         let now = env.ledger().timestamp();
         match_data.status = Status::Submission;
-        match_data.submission_deadline = now + match_data.submission_duration;  // Assume submission_duration set in create, wait, in params it's submission_duration
-        // Note: in create, I have submission_duration as param, but in struct it's 0, wait, fix: add to struct? Wait, in create I have submission_duration, reveal_duration.
-        // Correction: in create, add them to MatchData.
-
-        // Assume fixed in create.
-        // In code above, I have submission_duration, reveal_duration in params, but not in struct.
-        // Fix: add to MatchData.
-
-        // Assuming added: submission_duration, reveal_duration in struct, set in create.
-
         match_data.submission_deadline = now + match_data.submission_duration;
         env.storage().persistent().set(&DataKey::Match(match_id), &match_data);
     }
@@ -263,12 +249,7 @@ impl MultiplayerPuzzleMatch {
 
         let commit = match_data.commits.get(player.clone()).unwrap_or_else(|| panic_with_error!(&env, Error::NoResults));
 
-        let mut hash_input = Bytes::new(&env);
-        hash_input.append(&player.to_xdr(&env));
-        hash_input.append(&score.to_xdr(&env));
-        hash_input.append(&secret);
-
-        let computed_hash = env.crypto().sha256(&hash_input);
+        let computed_hash = Self::commitment_hash(&env, &player, score, &secret);
 
         if computed_hash != commit {
             panic_with_error!(&env, Error::InvalidReveal);
@@ -293,7 +274,7 @@ impl MultiplayerPuzzleMatch {
         }
 
         if disputer == disputed {
-            panic_with_error!(&env, Error::InvalidOp);  // Add InvalidOp to Error if needed
+            panic_with_error!(&env, Error::InvalidOp);
         }
 
         let mut disputers = match_data.disputes.get(disputed.clone()).unwrap_or(Vec::new(&env));
@@ -303,9 +284,7 @@ impl MultiplayerPuzzleMatch {
         disputers.push_back(disputer);
         match_data.disputes.set(disputed, disputers);
 
-        if match_data.status != Status::Disputed {
-            match_data.status = Status::Disputed;
-        }
+        match_data.status = Status::Disputed;
 
         env.storage().persistent().set(&DataKey::Match(match_id), &match_data);
     }
@@ -320,11 +299,11 @@ impl MultiplayerPuzzleMatch {
             panic_with_error!(&env, Error::InvalidStatus);
         }
 
-        if !match_data.disputes.has(&disputed) {
+        if !match_data.disputes.contains_key(disputed.clone()) {
             panic_with_error!(&env, Error::DisputeNotFound);
         }
 
-        if match_data.resolved.has(&disputed) {
+        if match_data.resolved.contains_key(disputed.clone()) {
             panic_with_error!(&env, Error::AlreadyResolved);
         }
 
@@ -335,7 +314,11 @@ impl MultiplayerPuzzleMatch {
         }
 
         // Check if all disputes resolved
-        let all_resolved = match_data.disputes.keys().all(|key| match_data.resolved.has(&key));
+        let all_resolved = match_data
+            .disputes
+            .keys()
+            .iter()
+            .all(|key| match_data.resolved.contains_key(key));
         if all_resolved {
             match_data.status = Status::Finished;
         }
@@ -347,17 +330,19 @@ impl MultiplayerPuzzleMatch {
     pub fn evaluate_match(env: Env, match_id: u64) {
         let mut match_data: MatchData = Self::get_match(&env, match_id);
 
-        if let Status::Finished = match_data.status {} else {
-            if env.ledger().timestamp() <= match_data.reveal_deadline {
-                panic_with_error!(&env, Error::DeadlinePassed);  // Wait, opposite
-            }
-            if match_data.status == Status::Reveal {
-                match_data.status = Status::Finished;
-            } else if match_data.status == Status::Disputed {
-                panic_with_error!(&env, Error::InvalidStatus);  // Need resolve first
-            } else {
+        if match_data.status != Status::Finished {
+            if match_data.status != Status::Reveal {
+                // Disputes must be resolved first
                 panic_with_error!(&env, Error::InvalidStatus);
             }
+            if env.ledger().timestamp() <= match_data.reveal_deadline {
+                panic_with_error!(&env, Error::DeadlineNotReached);
+            }
+            match_data.status = Status::Finished;
+        }
+
+        if match_data.pot == 0 {
+            panic_with_error!(&env, Error::AlreadyResolved);
         }
 
         // Get valid results
@@ -375,9 +360,14 @@ impl MultiplayerPuzzleMatch {
         }
 
         // Find max score
-        let max_score = valid_results.values().max().unwrap();
+        let max_score = valid_results.values().iter().max().unwrap();
 
-        let winners: Vec<Address> = valid_results.keys().filter(|key| valid_results.get_unchecked(key.clone()) == max_score).collect();
+        let mut winners: Vec<Address> = Vec::new(&env);
+        for (player, score) in valid_results.iter() {
+            if score == max_score {
+                winners.push_back(player);
+            }
+        }
 
         let prize = match_data.pot / winners.len() as i128;
 
@@ -397,8 +387,8 @@ impl MultiplayerPuzzleMatch {
 
         match match_data.status {
             Status::Open if now > match_data.join_deadline && match_data.players.len() < match_data.min_players => {
-                match_data.status = Status::Abandoned;
                 Self::refund_all(&env, match_id);
+                return;
             }
             Status::Submission if now > match_data.submission_deadline => {
                 // Move to reveal with what we have
@@ -420,20 +410,29 @@ impl MultiplayerPuzzleMatch {
         env.storage().persistent().get(&DataKey::Match(match_id)).unwrap_or_else(|| panic_with_error!(env, Error::MatchNotFound))
     }
 
+    fn commitment_hash(env: &Env, player: &Address, score: i128, secret: &Bytes) -> BytesN<32> {
+        let mut hash_input = Bytes::new(env);
+        hash_input.append(&player.clone().to_xdr(env));
+        hash_input.append(&score.to_xdr(env));
+        hash_input.append(secret);
+        env.crypto().sha256(&hash_input).into()
+    }
+
     fn transfer_to_contract(env: &Env, token: &Address, from: &Address, amount: i128) {
-        let client = token_spec::Client::new(env, token);
+        let client = token::Client::new(env, token);
         client.transfer(from, &env.current_contract_address(), &amount);
     }
 
     fn transfer_from_contract(env: &Env, token: &Address, to: &Address, amount: i128) {
-        let client = token_spec::Client::new(env, token);
+        let client = token::Client::new(env, token);
         client.transfer(&env.current_contract_address(), to, &amount);
     }
 
     fn refund_all(env: &Env, match_id: u64) {
         let match_data: MatchData = Self::get_match(env, match_id);
 
-        let refund = match_data.entry_fee;  // Since pot = entry_fee * players, but each gets back their fee
+        // Every player paid the same entry fee
+        let refund = match_data.entry_fee;
 
         for player in match_data.players.iter() {
             Self::transfer_from_contract(env, &match_data.token, &player, refund);
@@ -450,50 +449,143 @@ impl MultiplayerPuzzleMatch {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::{Address as _, Ledger}, Env, Address};
+    use soroban_sdk::{testutils::{Address as _, Ledger}, token::StellarAssetClient, Env, Address};
+
+    fn setup(env: &Env) -> (MultiplayerPuzzleMatchClient<'_>, Address) {
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, MultiplayerPuzzleMatch);
+        let admin = Address::generate(env);
+        let token = env.register_stellar_asset_contract_v2(admin).address();
+        (MultiplayerPuzzleMatchClient::new(env, &contract_id), token)
+    }
+
+    fn funded_player(env: &Env, token: &Address) -> Address {
+        let player = Address::generate(env);
+        StellarAssetClient::new(env, token).mint(&player, &1000);
+        player
+    }
+
+    fn commit(env: &Env, player: &Address, score: i128, secret: &Bytes) -> BytesN<32> {
+        let mut hash_input = Bytes::new(env);
+        hash_input.append(&player.clone().to_xdr(env));
+        hash_input.append(&score.to_xdr(env));
+        hash_input.append(secret);
+        env.crypto().sha256(&hash_input).into()
+    }
 
     #[test]
     fn test_match_lifecycle() {
         let env = Env::default();
-        env.mock_all_auths();
-
-        let creator = Address::generate(&env);
-        let token = Address::generate(&env);
-        let match_id = MultiplayerPuzzleMatch::create_match(
-            env.clone(),
-            creator.clone(),
-            token.clone(),
-            100,
-            5,
-            2,
-            3600,
-            1800,
-            1800,
-        );
+        let (client, token) = setup(&env);
+        let token_client = token::Client::new(&env, &token);
 
-        // Join
-        let player2 = Address::generate(&env);
-        MultiplayerPuzzleMatch::join_match(env.clone(), match_id, player2.clone());
+        let creator = funded_player(&env, &token);
+        let match_id = client.create_match(&creator, &token, &100, &5, &2, &3600, &1800, &1800);
 
-        // Started since min=2
+        // Join; the match starts once min_players is reached
+        let player2 = funded_player(&env, &token);
+        client.join_match(&match_id, &player2);
+        assert_eq!(token_client.balance(&client.address), 200);
 
-        // Submit commits
-        let commit1 = env.crypto().sha256(&Bytes::from_array(&env, &[1;32]));  // Dummy
-        MultiplayerPuzzleMatch::submit_commit(env.clone(), match_id, creator.clone(), commit1.clone());
-        let commit2 = env.crypto().sha256(&Bytes::from_array(&env, &[2;32]));
-        MultiplayerPuzzleMatch::submit_commit(env.clone(), match_id, player2.clone(), commit2.clone());
+        let secret1 = Bytes::from_array(&env, &[1; 10]);
+        let secret2 = Bytes::from_array(&env, &[2; 10]);
+        client.submit_commit(&match_id, &creator, &commit(&env, &creator, 100, &secret1));
+        client.submit_commit(&match_id, &player2, &commit(&env, &player2, 90, &secret2));
 
-        // Reveal
-        env.ledger().set_timestamp(env.ledger().timestamp() + 1900);  // Advance time
-        MultiplayerPuzzleMatch::reveal_result(env.clone(), match_id, creator.clone(), 100, Bytes::from_array(&env, &[1;10]));
-        MultiplayerPuzzleMatch::reveal_result(env.clone(), match_id, player2.clone(), 90, Bytes::from_array(&env, &[2;10]));
+        client.reveal_result(&match_id, &creator, &100, &secret1);
+        client.reveal_result(&match_id, &player2, &90, &secret2);
 
-        // Evaluate
         env.ledger().set_timestamp(env.ledger().timestamp() + 1900);
-        MultiplayerPuzzleMatch::evaluate_match(env.clone(), match_id);
+        client.evaluate_match(&match_id);
 
-        // Check balances, etc. (assume token mock)
+        assert_eq!(token_client.balance(&creator), 1100);
+        assert_eq!(token_client.balance(&player2), 900);
+        assert_eq!(token_client.balance(&client.address), 0);
     }
 
-    // More tests for leave, timeout, dispute, etc.
-}
\ No newline at end of file
+    #[test]
+    fn test_leave_refunds_entry_fee() {
+        let env = Env::default();
+        let (client, token) = setup(&env);
+        let token_client = token::Client::new(&env, &token);
+
+        let creator = funded_player(&env, &token);
+        let match_id = client.create_match(&creator, &token, &100, &5, &3, &3600, &1800, &1800);
+        let player2 = funded_player(&env, &token);
+        client.join_match(&match_id, &player2);
+
+        client.leave_match(&match_id, &player2);
+        assert_eq!(token_client.balance(&player2), 1000);
+        assert_eq!(token_client.balance(&client.address), 100);
+    }
+
+    #[test]
+    fn test_wrong_reveal_rejected() {
+        let env = Env::default();
+        let (client, token) = setup(&env);
+
+        let creator = funded_player(&env, &token);
+        let match_id = client.create_match(&creator, &token, &100, &2, &2, &3600, &1800, &1800);
+        let player2 = funded_player(&env, &token);
+        client.join_match(&match_id, &player2);
+
+        let secret = Bytes::from_array(&env, &[1; 10]);
+        client.submit_commit(&match_id, &creator, &commit(&env, &creator, 50, &secret));
+        client.submit_commit(&match_id, &player2, &commit(&env, &player2, 40, &secret));
+
+        let result = client.try_reveal_result(&match_id, &creator, &99, &secret);
+        assert_eq!(result, Err(Ok(Error::InvalidReveal.into())));
+    }
+
+    #[test]
+    fn test_dispute_invalidates_score() {
+        let env = Env::default();
+        let (client, token) = setup(&env);
+        let token_client = token::Client::new(&env, &token);
+
+        let creator = funded_player(&env, &token);
+        let match_id = client.create_match(&creator, &token, &100, &2, &2, &3600, &1800, &1800);
+        let player2 = funded_player(&env, &token);
+        client.join_match(&match_id, &player2);
+
+        let secret = Bytes::from_array(&env, &[7; 10]);
+        client.submit_commit(&match_id, &creator, &commit(&env, &creator, 10, &secret));
+        client.submit_commit(&match_id, &player2, &commit(&env, &player2, 500, &secret));
+        client.reveal_result(&match_id, &creator, &10, &secret);
+        client.reveal_result(&match_id, &player2, &500, &secret);
+
+        client.raise_dispute(&match_id, &creator, &player2);
+        assert_eq!(
+            client.try_evaluate_match(&match_id),
+            Err(Ok(Error::InvalidStatus.into()))
+        );
+
+        client.resolve_dispute(&match_id, &player2, &false);
+        client.evaluate_match(&match_id);
+
+        assert_eq!(token_client.balance(&creator), 1100);
+        assert_eq!(token_client.balance(&player2), 900);
+    }
+
+    #[test]
+    fn test_timeout_abandons_and_refunds() {
+        let env = Env::default();
+        let (client, token) = setup(&env);
+        let token_client = token::Client::new(&env, &token);
+
+        let creator = funded_player(&env, &token);
+        let match_id = client.create_match(&creator, &token, &100, &5, &3, &3600, &1800, &1800);
+        let player2 = funded_player(&env, &token);
+        client.join_match(&match_id, &player2);
+
+        env.ledger().set_timestamp(env.ledger().timestamp() + 3601);
+        client.handle_timeout(&match_id);
+
+        assert_eq!(token_client.balance(&creator), 1000);
+        assert_eq!(token_client.balance(&player2), 1000);
+        assert_eq!(
+            client.try_join_match(&match_id, &funded_player(&env, &token)),
+            Err(Ok(Error::InvalidStatus.into()))
+        );
+    }
+}
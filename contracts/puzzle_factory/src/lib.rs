@@ -1,15 +1,30 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, BytesN, Env, Symbol, Vec,
+    contract, contractclient, contractimpl, contracttype, token, Address, BytesN, Env, Map,
+    String, Symbol, Vec,
 };
 
+const MAX_TAGS: u32 = 10;
+const MIN_RATINGS_FOR_FLAG: u32 = 5;
+const DIFFICULTY_DRIFT_THRESHOLD: u32 = 3; // on the 1-10 scale
+const PLAYER_TTL_THRESHOLD: u32 = 100_000;
+const PLAYER_TTL_EXTEND: u32 = 500_000;
+
+/// Share table of `royalty_splitter::RoyaltySplitter`, in basis points
+#[contractclient(name = "RoyaltySplitterClient")]
+pub trait RoyaltySplitterInterface {
+    fn get_splits(env: Env) -> Map<Address, u32>;
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PuzzleStatus {
     Active,
     Inactive,
     Deprecated,
+    Draft,  // awaiting submission for review (curation enabled)
+    Review, // awaiting a curator
 }
 
 #[contracttype]
@@ -30,8 +45,11 @@ pub struct PuzzleMetadata {
     pub creator: Address,
     pub category: PuzzleCategory,
     pub difficulty: u32, // 1-10 scale
-    pub title: Symbol,
-    pub description: Symbol,
+    pub title: String,
+    pub description: String,
+    pub content_uri: String,        // full puzzle bundle, e.g. ipfs://...
+    pub content_hash: BytesN<32>,   // sha256 of the bundle at content_uri
+    pub tags: Vec<Symbol>,
     pub version: u32,
     pub status: PuzzleStatus,
     pub created_at: u64,
@@ -39,6 +57,18 @@ pub struct PuzzleMetadata {
     pub royalty_percentage: u32, // basis points (100 = 1%)
     pub total_plays: u64,
     pub successful_plays: u64,
+    pub flagged: bool, // rated difficulty drifted from the declared one
+}
+
+/// Descriptive, off-chain-backed part of a puzzle supplied by its creator
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PuzzleContent {
+    pub title: String,
+    pub description: String,
+    pub content_uri: String,
+    pub content_hash: BytesN<32>,
+    pub tags: Vec<Symbol>,
 }
 
 #[contracttype]
@@ -59,6 +89,28 @@ pub struct PuzzleInstance {
     pub config: PuzzleConfig,
 }
 
+/// Immutable snapshot of a puzzle's config for one version, with its own play stats
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PuzzleVersion {
+    pub puzzle_id: u32,
+    pub version: u32,
+    pub config: PuzzleConfig,
+    pub status: PuzzleStatus,
+    pub created_at: u64,
+    pub total_plays: u64,
+    pub successful_plays: u64,
+}
+
+/// Aggregate of player ratings for a puzzle
+#[contracttype]
+#[derive(Clone, Debug, Default)]
+pub struct PuzzleRatings {
+    pub rating_count: u32,
+    pub difficulty_total: u32, // sum of 1-10 ratings
+    pub quality_total: u32,    // sum of 1-5 ratings
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct CreatorStats {
@@ -68,6 +120,9 @@ pub struct CreatorStats {
     pub total_royalties_earned: i128,
     pub average_difficulty: u32,
     pub success_rate: u32, // basis points
+    pub total_ratings: u32,
+    pub quality_total: u32,
+    pub average_quality: u32, // 1-5 scale x100
 }
 
 #[contracttype]
@@ -78,12 +133,25 @@ pub enum DataKey {
     PuzzlesByCategory(PuzzleCategory),
     PuzzlesByCreator(Address),
     PuzzlesByDifficulty(u32),
+    PuzzlesByTag(Symbol),
     ActivePuzzles,
     CreatorStats(Address),
     AuthorizedCreators(Address),
     PendingRoyalties(Address),
     RoyaltyPool,
     TotalRoyaltiesDistributed,
+    PuzzleVersion(u32, u32), // (puzzle_id, version)
+    CurationRequired,
+    Curator(Address),
+    Completed(u32, Address), // Persistent: (puzzle_id, player)
+    Rating(u32, Address),    // Persistent: (puzzle_id, player)
+    PuzzleRatings(u32),
+    FlaggedPuzzles,
+    PaymentToken,
+    Treasury,
+    RoyaltyRecipient(u32),
+    RoyaltySplitter(Address),
+    Verifier,
 }
 
 #[contracttype]
@@ -100,6 +168,17 @@ pub enum FactoryEvent {
     RoyaltyCalculated,
     RoyaltyDistributed,
     RoyaltyWithdrawn,
+    VersionDeprecated,
+    CuratorAdded,
+    CuratorRemoved,
+    PuzzleSubmitted,
+    PuzzleApproved,
+    PuzzleRejected,
+    PuzzleRated,
+    PuzzleFlagged,
+    PuzzleUnflagged,
+    PlatformFeePaid,
+    RoyaltyRouted,
 }
 
 #[contract]
@@ -172,11 +251,13 @@ impl PuzzleFactory {
                 total_royalties_earned: 0,
                 average_difficulty: 0,
                 success_rate: 0,
+                total_ratings: 0,
+                quality_total: 0,
+                average_quality: 0,
             });
 
         stats.total_puzzles += 1;
-        stats.active_puzzles += 1;
-        
+
         // Update average difficulty
         stats.average_difficulty = ((stats.average_difficulty * (stats.total_puzzles - 1)) + difficulty) / stats.total_puzzles;
 
@@ -195,20 +276,68 @@ impl PuzzleFactory {
         env.events().publish((FactoryEvent::CreatorRevoked, creator), ());
     }
 
+    fn validate_content(content: &PuzzleContent) {
+        if content.title.is_empty() {
+            panic!("title required");
+        }
+
+        if content.tags.len() > MAX_TAGS {
+            panic!("too many tags");
+        }
+
+        for (i, tag) in content.tags.iter().enumerate() {
+            if content.tags.iter().skip(i + 1).any(|other| other == tag) {
+                panic!("duplicate tag");
+            }
+        }
+    }
+
+    fn index_tags(env: &Env, puzzle_id: u32, tags: &Vec<Symbol>) {
+        for tag in tags.iter() {
+            let mut tag_puzzles: Vec<u32> = env
+                .storage()
+                .instance()
+                .get(&DataKey::PuzzlesByTag(tag.clone()))
+                .unwrap_or(Vec::new(env));
+            tag_puzzles.push_back(puzzle_id);
+            env.storage().instance().set(&DataKey::PuzzlesByTag(tag), &tag_puzzles);
+        }
+    }
+
+    fn unindex_tags(env: &Env, puzzle_id: u32, tags: &Vec<Symbol>) {
+        for tag in tags.iter() {
+            let mut tag_puzzles: Vec<u32> = env
+                .storage()
+                .instance()
+                .get(&DataKey::PuzzlesByTag(tag.clone()))
+                .unwrap_or(Vec::new(env));
+
+            if let Some(idx) = tag_puzzles.first_index_of(puzzle_id) {
+                tag_puzzles.remove(idx);
+
+                // Remove tag index if empty
+                if tag_puzzles.is_empty() {
+                    env.storage().instance().remove(&DataKey::PuzzlesByTag(tag));
+                } else {
+                    env.storage().instance().set(&DataKey::PuzzlesByTag(tag), &tag_puzzles);
+                }
+            }
+        }
+    }
+
     pub fn create_puzzle(
         env: Env,
         creator: Address,
         category: PuzzleCategory,
         difficulty: u32,
-        title: Symbol,
-        description: Symbol,
+        content: PuzzleContent,
         config: PuzzleConfig,
         royalty_percentage: u32,
     ) -> u32 {
         Self::require_authorized_creator(&env, &creator);
         creator.require_auth();
 
-        if difficulty < 1 || difficulty > 10 {
+        if !(1..=10).contains(&difficulty) {
             panic!("difficulty must be between 1 and 10");
         }
 
@@ -220,23 +349,34 @@ impl PuzzleFactory {
             panic!("invalid time window");
         }
 
+        Self::validate_content(&content);
+
         let puzzle_id = Self::generate_puzzle_id(&env);
         let now = env.ledger().timestamp();
+        let status = if Self::is_curation_required(env.clone()) {
+            PuzzleStatus::Draft
+        } else {
+            PuzzleStatus::Active
+        };
 
         let metadata = PuzzleMetadata {
             id: puzzle_id,
             creator: creator.clone(),
             category: category.clone(),
             difficulty,
-            title,
-            description,
+            title: content.title,
+            description: content.description,
+            content_uri: content.content_uri,
+            content_hash: content.content_hash,
+            tags: content.tags,
             version: 1,
-            status: PuzzleStatus::Active,
+            status: status.clone(),
             created_at: now,
             updated_at: now,
             royalty_percentage,
             total_plays: 0,
             successful_plays: 0,
+            flagged: false,
         };
 
         let puzzle_instance = PuzzleInstance {
@@ -246,6 +386,7 @@ impl PuzzleFactory {
 
         // Store puzzle
         env.storage().instance().set(&DataKey::Puzzle(puzzle_id), &puzzle_instance);
+        Self::store_version(&env, &puzzle_instance);
 
         // Update indexes
         let mut category_puzzles: Vec<u32> = env
@@ -272,17 +413,15 @@ impl PuzzleFactory {
         difficulty_puzzles.push_back(puzzle_id);
         env.storage().instance().set(&DataKey::PuzzlesByDifficulty(difficulty), &difficulty_puzzles);
 
-        let mut active_puzzles: Vec<u32> = env
-            .storage()
-            .instance()
-            .get(&DataKey::ActivePuzzles)
-            .unwrap_or(Vec::new(&env));
-        active_puzzles.push_back(puzzle_id);
-        env.storage().instance().set(&DataKey::ActivePuzzles, &active_puzzles);
+        Self::index_tags(&env, puzzle_id, &metadata.tags);
 
         // Update creator stats
         Self::update_creator_stats(&env, &metadata.creator, difficulty, true);
 
+        if status == PuzzleStatus::Active {
+            Self::add_to_active(&env, puzzle_id, &metadata.creator);
+        }
+
         env.events().publish((FactoryEvent::PuzzleCreated, puzzle_id, metadata.creator), ());
 
         puzzle_id
@@ -291,8 +430,7 @@ impl PuzzleFactory {
     pub fn update_puzzle(
         env: Env,
         puzzle_id: u32,
-        title: Option<Symbol>,
-        description: Option<Symbol>,
+        content: Option<PuzzleContent>,
         config: Option<PuzzleConfig>,
         royalty_percentage: Option<u32>,
     ) {
@@ -305,14 +443,24 @@ impl PuzzleFactory {
         Self::require_authorized_creator(&env, &puzzle.metadata.creator);
         puzzle.metadata.creator.require_auth();
 
+        // Puzzles created before version history existed get their current
+        // version snapshotted before it is replaced
+        Self::ensure_version(&env, &puzzle);
+
         let mut updated_puzzle = puzzle;
 
-        if let Some(new_title) = title {
-            updated_puzzle.metadata.title = new_title;
-        }
+        if let Some(new_content) = content {
+            Self::validate_content(&new_content);
+            if updated_puzzle.metadata.status != PuzzleStatus::Deprecated {
+                Self::unindex_tags(&env, puzzle_id, &updated_puzzle.metadata.tags);
+                Self::index_tags(&env, puzzle_id, &new_content.tags);
+            }
 
-        if let Some(new_description) = description {
-            updated_puzzle.metadata.description = new_description;
+            updated_puzzle.metadata.title = new_content.title;
+            updated_puzzle.metadata.description = new_content.description;
+            updated_puzzle.metadata.content_uri = new_content.content_uri;
+            updated_puzzle.metadata.content_hash = new_content.content_hash;
+            updated_puzzle.metadata.tags = new_content.tags;
         }
 
         if let Some(new_config) = config {
@@ -333,8 +481,71 @@ impl PuzzleFactory {
         updated_puzzle.metadata.version += 1;
 
         env.storage().instance().set(&DataKey::Puzzle(puzzle_id), &updated_puzzle);
+        Self::store_version(&env, &updated_puzzle);
+
+        env.events().publish((FactoryEvent::PuzzleUpdated, puzzle_id), updated_puzzle.metadata.version);
+    }
+
+    fn store_version(env: &Env, puzzle: &PuzzleInstance) {
+        let version = PuzzleVersion {
+            puzzle_id: puzzle.metadata.id,
+            version: puzzle.metadata.version,
+            config: puzzle.config.clone(),
+            status: PuzzleStatus::Active,
+            created_at: puzzle.metadata.updated_at,
+            total_plays: 0,
+            successful_plays: 0,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::PuzzleVersion(version.puzzle_id, version.version), &version);
+    }
+
+    fn ensure_version(env: &Env, puzzle: &PuzzleInstance) {
+        let key = DataKey::PuzzleVersion(puzzle.metadata.id, puzzle.metadata.version);
+        if !env.storage().instance().has(&key) {
+            Self::store_version(env, puzzle);
+        }
+    }
+
+    /// Deprecate an older version of a puzzle. The current version can only be
+    /// retired through `deprecate_puzzle`.
+    pub fn deprecate_version(env: Env, puzzle_id: u32, version: u32) {
+        let puzzle: PuzzleInstance = env
+            .storage()
+            .instance()
+            .get(&DataKey::Puzzle(puzzle_id))
+            .expect("puzzle not found");
 
-        env.events().publish((FactoryEvent::PuzzleUpdated, puzzle_id), ());
+        Self::require_authorized_creator(&env, &puzzle.metadata.creator);
+        puzzle.metadata.creator.require_auth();
+
+        if version == puzzle.metadata.version {
+            panic!("cannot deprecate current version");
+        }
+
+        let key = DataKey::PuzzleVersion(puzzle_id, version);
+        let mut record: PuzzleVersion = env
+            .storage()
+            .instance()
+            .get(&key)
+            .expect("version not found");
+
+        if record.status == PuzzleStatus::Deprecated {
+            panic!("version already deprecated");
+        }
+
+        record.status = PuzzleStatus::Deprecated;
+        env.storage().instance().set(&key, &record);
+
+        env.events().publish((FactoryEvent::VersionDeprecated, puzzle_id), version);
+    }
+
+    pub fn get_puzzle_version(env: Env, puzzle_id: u32, version: u32) -> PuzzleVersion {
+        env.storage()
+            .instance()
+            .get(&DataKey::PuzzleVersion(puzzle_id, version))
+            .expect("version not found")
     }
 
     pub fn activate_puzzle(env: Env, puzzle_id: u32) {
@@ -351,23 +562,258 @@ impl PuzzleFactory {
             return;
         }
 
+        // Drafts only go live through the curation flow
+        if puzzle.metadata.status == PuzzleStatus::Draft || puzzle.metadata.status == PuzzleStatus::Review {
+            panic!("puzzle not approved");
+        }
+
         puzzle.metadata.status = PuzzleStatus::Active;
         puzzle.metadata.updated_at = env.ledger().timestamp();
 
+        Self::add_to_active(&env, puzzle_id, &puzzle.metadata.creator);
+
+        env.storage().instance().set(&DataKey::Puzzle(puzzle_id), &puzzle);
+
+        env.events().publish((FactoryEvent::PuzzleActivated, puzzle_id), ());
+    }
+
+    fn add_to_active(env: &Env, puzzle_id: u32, creator: &Address) {
         let mut active_puzzles: Vec<u32> = env
             .storage()
             .instance()
             .get(&DataKey::ActivePuzzles)
-            .unwrap_or(Vec::new(&env));
+            .unwrap_or(Vec::new(env));
         
-        if !active_puzzles.contains(&puzzle_id) {
+        if !active_puzzles.contains(puzzle_id) {
             active_puzzles.push_back(puzzle_id);
             env.storage().instance().set(&DataKey::ActivePuzzles, &active_puzzles);
+            Self::adjust_active_count(env, creator, true);
+        }
+    }
+
+    fn remove_from_active(env: &Env, puzzle_id: u32, creator: &Address) {
+        let mut active_puzzles: Vec<u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActivePuzzles)
+            .unwrap_or(Vec::new(env));
+
+        let index = active_puzzles.iter().position(|id| id == puzzle_id);
+        if let Some(idx) = index {
+            active_puzzles.remove(idx.try_into().unwrap());
+            env.storage().instance().set(&DataKey::ActivePuzzles, &active_puzzles);
+            Self::adjust_active_count(env, creator, false);
+        }
+    }
+
+    /// Creator stats count the creator's puzzles in the active list only
+    fn adjust_active_count(env: &Env, creator: &Address, added: bool) {
+        let key = DataKey::CreatorStats(creator.clone());
+        if let Some(mut stats) = env.storage().instance().get::<_, CreatorStats>(&key) {
+            if added {
+                stats.active_puzzles += 1;
+            } else {
+                stats.active_puzzles = stats.active_puzzles.saturating_sub(1);
+            }
+            env.storage().instance().set(&key, &stats);
+        }
+    }
+
+    /// Admin: when enabled, new puzzles start as `Draft` and need a curator's
+    /// approval before they can be played
+    pub fn set_curation_required(env: Env, required: bool) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::CurationRequired, &required);
+    }
+
+    pub fn is_curation_required(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::CurationRequired)
+            .unwrap_or(false)
+    }
+
+    pub fn add_curator(env: Env, curator: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Curator(curator.clone()), &true);
+        env.events().publish((FactoryEvent::CuratorAdded, curator), ());
+    }
+
+    pub fn remove_curator(env: Env, curator: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().remove(&DataKey::Curator(curator.clone()));
+        env.events().publish((FactoryEvent::CuratorRemoved, curator), ());
+    }
+
+    pub fn is_curator(env: Env, curator: Address) -> bool {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("admin not set");
+
+        curator == admin
+            || env
+                .storage()
+                .instance()
+                .get(&DataKey::Curator(curator))
+                .unwrap_or(false)
+    }
+
+    fn require_curator(env: &Env, curator: &Address) {
+        curator.require_auth();
+        if !Self::is_curator(env.clone(), curator.clone()) {
+            panic!("not a curator");
+        }
+    }
+
+    /// Creator: move a draft into the review queue
+    pub fn submit_for_review(env: Env, puzzle_id: u32) {
+        let mut puzzle = Self::get_puzzle(env.clone(), puzzle_id);
+
+        Self::require_authorized_creator(&env, &puzzle.metadata.creator);
+        puzzle.metadata.creator.require_auth();
+
+        if puzzle.metadata.status != PuzzleStatus::Draft {
+            panic!("puzzle is not a draft");
         }
 
+        puzzle.metadata.status = PuzzleStatus::Review;
+        puzzle.metadata.updated_at = env.ledger().timestamp();
         env.storage().instance().set(&DataKey::Puzzle(puzzle_id), &puzzle);
 
-        env.events().publish((FactoryEvent::PuzzleActivated, puzzle_id), ());
+        env.events().publish((FactoryEvent::PuzzleSubmitted, puzzle_id), ());
+    }
+
+    /// Curator: approve a puzzle under review and make it playable
+    pub fn approve_puzzle(env: Env, curator: Address, puzzle_id: u32) {
+        Self::require_curator(&env, &curator);
+        let mut puzzle = Self::get_puzzle(env.clone(), puzzle_id);
+
+        if puzzle.metadata.status != PuzzleStatus::Review {
+            panic!("puzzle is not under review");
+        }
+
+        puzzle.metadata.status = PuzzleStatus::Active;
+        puzzle.metadata.updated_at = env.ledger().timestamp();
+        env.storage().instance().set(&DataKey::Puzzle(puzzle_id), &puzzle);
+        Self::add_to_active(&env, puzzle_id, &puzzle.metadata.creator);
+
+        env.events().publish((FactoryEvent::PuzzleApproved, puzzle_id, curator), ());
+    }
+
+    /// Curator: send a puzzle under review back to its creator as a draft
+    pub fn reject_puzzle(env: Env, curator: Address, puzzle_id: u32) {
+        Self::require_curator(&env, &curator);
+        let mut puzzle = Self::get_puzzle(env.clone(), puzzle_id);
+
+        if puzzle.metadata.status != PuzzleStatus::Review {
+            panic!("puzzle is not under review");
+        }
+
+        puzzle.metadata.status = PuzzleStatus::Draft;
+        puzzle.metadata.updated_at = env.ledger().timestamp();
+        env.storage().instance().set(&DataKey::Puzzle(puzzle_id), &puzzle);
+
+        env.events().publish((FactoryEvent::PuzzleRejected, puzzle_id, curator), ());
+    }
+
+    /// Player: rate a completed puzzle's difficulty (1-10) and quality (1-5), once
+    pub fn rate_puzzle(env: Env, player: Address, puzzle_id: u32, difficulty: u32, quality: u32) {
+        player.require_auth();
+
+        let mut puzzle = Self::get_puzzle(env.clone(), puzzle_id);
+
+        let completed: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Completed(puzzle_id, player.clone()))
+            .unwrap_or(false);
+        if !completed {
+            panic!("puzzle not completed");
+        }
+
+        if !(1..=10).contains(&difficulty) {
+            panic!("difficulty must be between 1 and 10");
+        }
+        if !(1..=5).contains(&quality) {
+            panic!("quality must be between 1 and 5");
+        }
+
+        let rating_key = DataKey::Rating(puzzle_id, player.clone());
+        if env.storage().persistent().has(&rating_key) {
+            panic!("already rated");
+        }
+        env.storage().persistent().set(&rating_key, &(difficulty, quality));
+        env.storage()
+            .persistent()
+            .extend_ttl(&rating_key, PLAYER_TTL_THRESHOLD, PLAYER_TTL_EXTEND);
+
+        let mut ratings = Self::get_puzzle_ratings(env.clone(), puzzle_id);
+        ratings.rating_count += 1;
+        ratings.difficulty_total += difficulty;
+        ratings.quality_total += quality;
+        env.storage().instance().set(&DataKey::PuzzleRatings(puzzle_id), &ratings);
+
+        // Feed the creator's running quality average
+        let creator = puzzle.metadata.creator.clone();
+        let mut stats = Self::get_creator_stats(env.clone(), creator.clone());
+        stats.total_ratings += 1;
+        stats.quality_total += quality;
+        stats.average_quality = stats.quality_total * 100 / stats.total_ratings;
+        env.storage().instance().set(&DataKey::CreatorStats(creator), &stats);
+
+        env.events().publish((FactoryEvent::PuzzleRated, puzzle_id, player), (difficulty, quality));
+
+        // Flag puzzles whose rated difficulty drifts from the declared one
+        if !puzzle.metadata.flagged && ratings.rating_count >= MIN_RATINGS_FOR_FLAG {
+            let rated = ratings.difficulty_total / ratings.rating_count;
+            if rated.abs_diff(puzzle.metadata.difficulty) >= DIFFICULTY_DRIFT_THRESHOLD {
+                puzzle.metadata.flagged = true;
+                env.storage().instance().set(&DataKey::Puzzle(puzzle_id), &puzzle);
+
+                let mut flagged: Vec<u32> = Self::get_flagged_puzzles(env.clone());
+                flagged.push_back(puzzle_id);
+                env.storage().instance().set(&DataKey::FlaggedPuzzles, &flagged);
+
+                env.events().publish((FactoryEvent::PuzzleFlagged, puzzle_id), rated);
+            }
+        }
+    }
+
+    /// Curator: clear a difficulty flag after reviewing the puzzle
+    pub fn clear_flag(env: Env, curator: Address, puzzle_id: u32) {
+        Self::require_curator(&env, &curator);
+        let mut puzzle = Self::get_puzzle(env.clone(), puzzle_id);
+
+        if !puzzle.metadata.flagged {
+            panic!("puzzle is not flagged");
+        }
+
+        puzzle.metadata.flagged = false;
+        env.storage().instance().set(&DataKey::Puzzle(puzzle_id), &puzzle);
+
+        let mut flagged = Self::get_flagged_puzzles(env.clone());
+        if let Some(idx) = flagged.first_index_of(puzzle_id) {
+            flagged.remove(idx);
+            env.storage().instance().set(&DataKey::FlaggedPuzzles, &flagged);
+        }
+
+        env.events().publish((FactoryEvent::PuzzleUnflagged, puzzle_id, curator), ());
+    }
+
+    pub fn get_puzzle_ratings(env: Env, puzzle_id: u32) -> PuzzleRatings {
+        env.storage()
+            .instance()
+            .get(&DataKey::PuzzleRatings(puzzle_id))
+            .unwrap_or_default()
+    }
+
+    pub fn get_flagged_puzzles(env: Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FlaggedPuzzles)
+            .unwrap_or(Vec::new(&env))
     }
 
     pub fn deactivate_puzzle(env: Env, puzzle_id: u32) {
@@ -384,21 +830,16 @@ impl PuzzleFactory {
             return;
         }
 
+        // Deactivating an unapproved puzzle would let it skip curation on reactivation
+        if puzzle.metadata.status == PuzzleStatus::Draft || puzzle.metadata.status == PuzzleStatus::Review {
+            panic!("puzzle not approved");
+        }
+
         puzzle.metadata.status = PuzzleStatus::Inactive;
         puzzle.metadata.updated_at = env.ledger().timestamp();
 
         // Remove from active puzzles
-        let mut active_puzzles: Vec<u32> = env
-            .storage()
-            .instance()
-            .get(&DataKey::ActivePuzzles)
-            .unwrap_or(Vec::new(&env));
-        
-        let index = active_puzzles.iter().position(|id| id == puzzle_id);
-        if let Some(idx) = index {
-            active_puzzles.remove(idx.try_into().unwrap());
-            env.storage().instance().set(&DataKey::ActivePuzzles, &active_puzzles);
-        }
+        Self::remove_from_active(&env, puzzle_id, &puzzle.metadata.creator);
 
         env.storage().instance().set(&DataKey::Puzzle(puzzle_id), &puzzle);
 
@@ -417,21 +858,18 @@ impl PuzzleFactory {
                 total_royalties_earned: 0,
                 average_difficulty: 0,
                 success_rate: 0,
+                total_ratings: 0,
+                quality_total: 0,
+                average_quality: 0,
             });
 
-        if stats.active_puzzles > 0 {
-            stats.active_puzzles -= 1;
-        }
-
         if stats.total_puzzles > 0 {
             stats.total_puzzles -= 1;
             
             // Recalculate average difficulty
-            if stats.total_puzzles > 0 {
-                stats.average_difficulty = (stats.average_difficulty * (stats.total_puzzles + 1) - difficulty) / stats.total_puzzles;
-            } else {
-                stats.average_difficulty = 0;
-            }
+            stats.average_difficulty = (stats.average_difficulty * (stats.total_puzzles + 1) - difficulty)
+                .checked_div(stats.total_puzzles)
+                .unwrap_or(0);
         }
 
         // If creator has no more puzzles, remove stats entry
@@ -462,17 +900,7 @@ impl PuzzleFactory {
 
         // Remove from active puzzles if it was active
         if old_status == PuzzleStatus::Active {
-            let mut active_puzzles: Vec<u32> = env
-                .storage()
-                .instance()
-                .get(&DataKey::ActivePuzzles)
-                .unwrap_or(Vec::new(&env));
-            
-            let index = active_puzzles.iter().position(|id| id == puzzle_id);
-            if let Some(idx) = index {
-                active_puzzles.remove(idx.try_into().unwrap());
-                env.storage().instance().set(&DataKey::ActivePuzzles, &active_puzzles);
-            }
+            Self::remove_from_active(&env, puzzle_id, &puzzle.metadata.creator);
         }
 
         // Remove from category index
@@ -532,6 +960,9 @@ impl PuzzleFactory {
             }
         }
 
+        // Remove from tag indexes
+        Self::unindex_tags(&env, puzzle_id, &puzzle.metadata.tags);
+
         // Update creator stats
         Self::update_creator_stats_on_deprecation(&env, &puzzle.metadata.creator, puzzle.metadata.difficulty);
 
@@ -547,6 +978,10 @@ impl PuzzleFactory {
             .expect("puzzle not found")
     }
 
+    pub fn get_puzzle_config(env: Env, puzzle_id: u32) -> PuzzleConfig {
+        Self::get_puzzle(env, puzzle_id).config
+    }
+
     pub fn get_puzzles_by_category(env: Env, category: PuzzleCategory) -> Vec<u32> {
         env.storage()
             .instance()
@@ -568,6 +1003,13 @@ impl PuzzleFactory {
             .unwrap_or(Vec::new(&env))
     }
 
+    pub fn get_puzzles_by_tag(env: Env, tag: Symbol) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PuzzlesByTag(tag))
+            .unwrap_or(Vec::new(&env))
+    }
+
     pub fn get_active_puzzles(env: Env) -> Vec<u32> {
         env.storage()
             .instance()
@@ -586,6 +1028,9 @@ impl PuzzleFactory {
                 total_royalties_earned: 0,
                 average_difficulty: 0,
                 success_rate: 0,
+                total_ratings: 0,
+                quality_total: 0,
+                average_quality: 0,
             })
     }
 
@@ -613,37 +1058,112 @@ impl PuzzleFactory {
             .unwrap_or(0u32)
     }
 
+    /// Admin: link the verification contract that reports solves. Only it can
+    /// record a successful play; without one every success is refused.
+    pub fn set_verifier(env: Env, verifier: Option<Address>) {
+        Self::require_admin(&env);
+        match verifier {
+            Some(address) => env.storage().instance().set(&DataKey::Verifier, &address),
+            None => env.storage().instance().remove(&DataKey::Verifier),
+        }
+    }
+
+    pub fn get_verifier(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Verifier)
+    }
+
+    /// Record a play against the puzzle's current version
     pub fn record_play(env: Env, puzzle_id: u32, player: Address, success: bool, payment_amount: Option<i128>) {
+        let puzzle = Self::get_puzzle(env.clone(), puzzle_id);
+        Self::record_version_play(env, puzzle_id, puzzle.metadata.version, player, success, payment_amount);
+    }
+
+    /// Record a play against a specific, non-deprecated version, e.g. a player
+    /// finishing a run started before the puzzle was updated
+    pub fn record_version_play(
+        env: Env,
+        puzzle_id: u32,
+        version: u32,
+        player: Address,
+        success: bool,
+        payment_amount: Option<i128>,
+    ) {
         let mut puzzle: PuzzleInstance = env
             .storage()
             .instance()
             .get(&DataKey::Puzzle(puzzle_id))
             .expect("puzzle not found");
 
-        // Check if puzzle is active and within time window
+        // Check if puzzle is active
         if puzzle.metadata.status != PuzzleStatus::Active {
             panic!("puzzle is not active");
         }
 
+        Self::ensure_version(&env, &puzzle);
+        let version_key = DataKey::PuzzleVersion(puzzle_id, version);
+        let mut version_record: PuzzleVersion = env
+            .storage()
+            .instance()
+            .get(&version_key)
+            .expect("version not found");
+        if version_record.status == PuzzleStatus::Deprecated {
+            panic!("puzzle version is deprecated");
+        }
+
+        // The played version's window applies
         let now = env.ledger().timestamp();
-        if now < puzzle.config.start_time || now > puzzle.config.end_time {
+        if now < version_record.config.start_time || now > version_record.config.end_time {
             panic!("puzzle is not in playable time window");
         }
 
         player.require_auth();
 
+        // Players cannot vouch for their own solves
+        if success {
+            let verifier: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::Verifier)
+                .expect("no verifier linked");
+            verifier.require_auth();
+        }
+
         // Update play statistics
         puzzle.metadata.total_plays += 1;
+        version_record.total_plays += 1;
         if success {
             puzzle.metadata.successful_plays += 1;
+            version_record.successful_plays += 1;
+            let completed_key = DataKey::Completed(puzzle_id, player.clone());
+            env.storage().persistent().set(&completed_key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&completed_key, PLAYER_TTL_THRESHOLD, PLAYER_TTL_EXTEND);
         }
+        env.storage().instance().set(&version_key, &version_record);
 
-        // Calculate and distribute royalties if payment provided
+        // Collect payment, escrow the royalty and send the platform share to the treasury
         if let Some(amount) = payment_amount {
             if amount > 0 {
+                let token_client = Self::payment_token_client(&env);
+                let this = env.current_contract_address();
+                token_client.transfer(&player, &this, &amount);
+
                 let royalty_amount = Self::calculate_royalty_amount(&env, amount, puzzle.metadata.royalty_percentage);
+                let platform_amount = amount - royalty_amount;
+                if platform_amount > 0 {
+                    let treasury: Address = env
+                        .storage()
+                        .instance()
+                        .get(&DataKey::Treasury)
+                        .expect("treasury not configured");
+                    token_client.transfer(&this, &treasury, &platform_amount);
+                    env.events().publish((FactoryEvent::PlatformFeePaid, puzzle_id, treasury), platform_amount);
+                }
+
                 if royalty_amount > 0 {
-                    Self::distribute_royalty(&env, &puzzle.metadata.creator, royalty_amount);
+                    let payee = Self::get_royalty_recipient(env.clone(), puzzle_id);
+                    Self::distribute_royalty(&env, &payee, royalty_amount);
                     
                     // Update creator stats
                     Self::update_creator_royalties(&env, &puzzle.metadata.creator, royalty_amount);
@@ -663,7 +1183,7 @@ impl PuzzleFactory {
             player, 
             success, 
             payment_amount.unwrap_or(0)
-        ), ());
+        ), version);
     }
 
     fn calculate_royalty_amount(_env: &Env, payment_amount: i128, royalty_percentage: u32) -> i128 {
@@ -716,6 +1236,9 @@ impl PuzzleFactory {
                 total_royalties_earned: 0,
                 average_difficulty: 0,
                 success_rate: 0,
+                total_ratings: 0,
+                quality_total: 0,
+                average_quality: 0,
             });
 
         stats.total_royalties_earned += amount;
@@ -743,11 +1266,9 @@ impl PuzzleFactory {
             }
         }
 
-        let success_rate: u32 = if total_plays > 0 {
-            ((successful_plays * 10000) / total_plays) as u32 // basis points
-        } else {
-            0
-        };
+        let success_rate: u32 = (successful_plays * 10000)
+            .checked_div(total_plays)
+            .unwrap_or(0) as u32; // basis points
 
         let mut stats: CreatorStats = env
             .storage()
@@ -760,6 +1281,9 @@ impl PuzzleFactory {
                 total_royalties_earned: 0,
                 average_difficulty: 0,
                 success_rate: 0,
+                total_ratings: 0,
+                quality_total: 0,
+                average_quality: 0,
             });
 
         stats.success_rate = success_rate;
@@ -769,24 +1293,141 @@ impl PuzzleFactory {
     pub fn withdraw_royalties(env: Env, creator: Address) -> i128 {
         creator.require_auth();
 
-        let pending_royalties: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PendingRoyalties(creator.clone()))
-            .unwrap_or(0);
+        let pending_royalties: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingRoyalties(creator.clone()))
+            .unwrap_or(0);
+
+        if pending_royalties <= 0 {
+            return 0;
+        }
+
+        // Clear pending royalties
+        env.storage().instance().remove(&DataKey::PendingRoyalties(creator.clone()));
+
+        Self::payment_token_client(&env).transfer(&env.current_contract_address(), &creator, &pending_royalties);
+
+        env.events().publish((FactoryEvent::RoyaltyWithdrawn, creator.clone(), pending_royalties), ());
+
+        pending_royalties
+    }
+
+    /// Pay royalties escrowed for a splitter straight to its co-authors, using
+    /// the splitter's current shares. Anyone can call this; funds only ever
+    /// move to the addresses listed in the registered splitter.
+    pub fn forward_royalties(env: Env, splitter: Address) -> i128 {
+        let registered: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::RoyaltySplitter(splitter.clone()))
+            .unwrap_or(false);
+        if !registered {
+            panic!("not a royalty splitter");
+        }
+
+        let pending_royalties = Self::get_pending_royalties(env.clone(), splitter.clone());
+        if pending_royalties <= 0 {
+            return 0;
+        }
+
+        env.storage().instance().remove(&DataKey::PendingRoyalties(splitter.clone()));
+
+        let splits = Self::load_splits(&env, &splitter);
+        let token_client = Self::payment_token_client(&env);
+        let this = env.current_contract_address();
+        let mut remaining = pending_royalties;
+        let last = splits.len() - 1;
+        for (i, (co_author, bps)) in splits.iter().enumerate() {
+            // The last co-author also receives the rounding dust
+            let share = if i as u32 == last {
+                remaining
+            } else {
+                pending_royalties * bps as i128 / 10000
+            };
+            remaining -= share;
+            if share > 0 {
+                token_client.transfer(&this, &co_author, &share);
+                env.events().publish((FactoryEvent::RoyaltyWithdrawn, co_author, share), ());
+            }
+        }
+
+        env.events().publish((FactoryEvent::RoyaltyWithdrawn, splitter, pending_royalties), ());
+
+        pending_royalties
+    }
+
+    /// Admin: token plays are paid in and the treasury receiving the platform share.
+    /// The token can only be set once so escrowed royalties stay redeemable.
+    pub fn set_payment_config(env: Env, token: Address, treasury: Address) {
+        Self::require_admin(&env);
+        if let Some(current) = Self::get_payment_token(env.clone()) {
+            if current != token {
+                panic!("payment token already set");
+            }
+        }
+        env.storage().instance().set(&DataKey::PaymentToken, &token);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+    }
+
+    pub fn get_payment_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PaymentToken)
+    }
+
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Treasury)
+    }
+
+    /// Creator: route a puzzle's royalties to a `royalty_splitter` contract
+    /// (co-authored puzzles), or back to themselves with `None`
+    pub fn set_royalty_recipient(env: Env, puzzle_id: u32, splitter: Option<Address>) {
+        let puzzle = Self::get_puzzle(env.clone(), puzzle_id);
+
+        Self::require_authorized_creator(&env, &puzzle.metadata.creator);
+        puzzle.metadata.creator.require_auth();
 
-        if pending_royalties <= 0 {
-            return 0;
+        match splitter {
+            Some(splitter) => {
+                Self::load_splits(&env, &splitter);
+                env.storage().instance().set(&DataKey::RoyaltyRecipient(puzzle_id), &splitter);
+                env.storage().instance().set(&DataKey::RoyaltySplitter(splitter.clone()), &true);
+                env.events().publish((FactoryEvent::RoyaltyRouted, puzzle_id), splitter);
+            }
+            None => {
+                env.storage().instance().remove(&DataKey::RoyaltyRecipient(puzzle_id));
+                env.events().publish((FactoryEvent::RoyaltyRouted, puzzle_id), puzzle.metadata.creator);
+            }
         }
+    }
 
-        // Clear pending royalties
-        env.storage().instance().remove(&DataKey::PendingRoyalties(creator.clone()));
+    /// Address that accrues a puzzle's royalties: its splitter if set, else the creator
+    pub fn get_royalty_recipient(env: Env, puzzle_id: u32) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoyaltyRecipient(puzzle_id))
+            .unwrap_or_else(|| Self::get_puzzle(env.clone(), puzzle_id).metadata.creator)
+    }
 
-        // In a real implementation, you would transfer tokens here
-        // For now, we just emit an event and return the amount
-        env.events().publish((FactoryEvent::RoyaltyWithdrawn, creator.clone(), pending_royalties), ());
+    /// Shares of an initialized splitter; anything else is refused
+    fn load_splits(env: &Env, splitter: &Address) -> Map<Address, u32> {
+        let splits = match RoyaltySplitterClient::new(env, splitter).try_get_splits() {
+            Ok(Ok(splits)) => splits,
+            _ => panic!("invalid royalty splitter"),
+        };
+        let total: u32 = splits.values().iter().sum();
+        if splits.is_empty() || total != 10000 {
+            panic!("invalid royalty splitter");
+        }
+        splits
+    }
 
-        pending_royalties
+    fn payment_token_client(env: &Env) -> token::Client<'_> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PaymentToken)
+            .expect("payment token not configured");
+        token::Client::new(env, &token)
     }
 
     pub fn get_pending_royalties(env: Env, creator: Address) -> i128 {
@@ -805,11 +1446,39 @@ impl PuzzleFactory {
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrows_for_generic_args)]
 mod tests {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::{Address as _, Ledger as _};
     use soroban_sdk::symbol_short;
 
+    const PLAYER_FUNDS: i128 = 1_000_000;
+
+    fn setup_payment_token<'a>(
+        env: &'a Env,
+        client: &PuzzleFactoryClient,
+        player: &Address,
+    ) -> (token::Client<'a>, Address) {
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        token::StellarAssetClient::new(env, &token_id).mint(player, &PLAYER_FUNDS);
+
+        let treasury = Address::generate(env);
+        client.set_payment_config(&token_id, &treasury);
+        (token::Client::new(env, &token_id), treasury)
+    }
+
+    fn test_content(env: &Env, title: &str, description: &str) -> PuzzleContent {
+        PuzzleContent {
+            title: String::from_str(env, title),
+            description: String::from_str(env, description),
+            content_uri: String::from_str(env, "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+            content_hash: BytesN::from_array(env, &[7; 32]),
+            tags: Vec::new(env),
+        }
+    }
+
     #[test]
     fn test_initialization() {
         let env = Env::default();
@@ -867,8 +1536,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Logic,
             &5,
-            &symbol_short!("TestPzl"),
-            &symbol_short!("TestDesc"),
+            &test_content(&env, "TestPzl", "TestDesc"),
             &config,
             &100,
         );
@@ -946,8 +1614,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Mathematics,
             &3,
-            &symbol_short!("MathPzl"),
-            &symbol_short!("MathDesc"),
+            &test_content(&env, "MathPzl", "MathDesc"),
             &config,
             &50,
         );
@@ -985,8 +1652,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Pattern,
             &7,
-            &symbol_short!("PatPzl"),
-            &symbol_short!("PatDesc"),
+            &test_content(&env, "PatPzl", "PatDesc"),
             &config,
             &150,
         );
@@ -1036,8 +1702,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Logic,
             &3,
-            &symbol_short!("LogPzl1"),
-            &symbol_short!("LogDesc1"),
+            &test_content(&env, "LogPzl1", "LogDesc1"),
             &config.clone(),
             &100,
         );
@@ -1046,8 +1711,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Logic,
             &7,
-            &symbol_short!("LogPzl2"),
-            &symbol_short!("LogDesc2"),
+            &test_content(&env, "LogPzl2", "LogDesc2"),
             &config.clone(),
             &200,
         );
@@ -1088,7 +1752,9 @@ mod tests {
         let player = Address::generate(&env);
         
         env.mock_all_auths();
+        env.ledger().set_timestamp(1500);
         client.initialize(&admin);
+        client.set_verifier(&Some(Address::generate(&env)));
         client.authorize_creator(&creator);
 
         let config = PuzzleConfig {
@@ -1104,8 +1770,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Logic,
             &5,
-            &symbol_short!("TestPzl"),
-            &symbol_short!("TestDesc"),
+            &test_content(&env, "TestPzl", "TestDesc"),
             &config,
             &100,
         );
@@ -1134,8 +1799,11 @@ mod tests {
         let player = Address::generate(&env);
         
         env.mock_all_auths();
+        env.ledger().set_timestamp(1500);
         client.initialize(&admin);
+        client.set_verifier(&Some(Address::generate(&env)));
         client.authorize_creator(&creator);
+        let (token_client, treasury) = setup_payment_token(&env, &client, &player);
 
         let config = PuzzleConfig {
             solution_hash: BytesN::from_array(&env, &[0; 32]),
@@ -1151,8 +1819,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Mathematics,
             &3,
-            &symbol_short!("MathPzl"),
-            &symbol_short!("MathDesc"),
+            &test_content(&env, "MathPzl", "MathDesc"),
             &config,
             &1000, // 10% royalty
         );
@@ -1179,6 +1846,11 @@ mod tests {
         // Verify creator stats updated
         let stats = client.get_creator_stats(&creator);
         assert_eq!(stats.total_royalties_earned, expected_royalty);
+
+        // Payment is collected; the royalty stays in escrow, the rest goes to the treasury
+        assert_eq!(token_client.balance(&player), PLAYER_FUNDS - payment_amount);
+        assert_eq!(token_client.balance(&contract_id), expected_royalty);
+        assert_eq!(token_client.balance(&treasury), payment_amount - expected_royalty);
     }
 
     #[test]
@@ -1192,8 +1864,11 @@ mod tests {
         let player = Address::generate(&env);
         
         env.mock_all_auths();
+        env.ledger().set_timestamp(1500);
         client.initialize(&admin);
+        client.set_verifier(&Some(Address::generate(&env)));
         client.authorize_creator(&creator);
+        let (token_client, treasury) = setup_payment_token(&env, &client, &player);
 
         let config = PuzzleConfig {
             solution_hash: BytesN::from_array(&env, &[0; 32]),
@@ -1208,8 +1883,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Pattern,
             &7,
-            &symbol_short!("PatPzl"),
-            &symbol_short!("PatDesc"),
+            &test_content(&env, "PatPzl", "PatDesc"),
             &config,
             &500, // 5% royalty
         );
@@ -1237,6 +1911,11 @@ mod tests {
         // Total distributed should remain the same
         let total_distributed = client.get_total_royalties_distributed();
         assert_eq!(total_distributed, expected_total_royalties);
+
+        // Withdrawal moves real funds out of escrow
+        assert_eq!(token_client.balance(&creator), expected_total_royalties);
+        assert_eq!(token_client.balance(&contract_id), 0);
+        assert_eq!(token_client.balance(&treasury), 6000 - expected_total_royalties);
     }
 
     #[test]
@@ -1250,8 +1929,11 @@ mod tests {
         let player = Address::generate(&env);
         
         env.mock_all_auths();
+        env.ledger().set_timestamp(1500);
         client.initialize(&admin);
+        client.set_verifier(&Some(Address::generate(&env)));
         client.authorize_creator(&creator);
+        let (token_client, treasury) = setup_payment_token(&env, &client, &player);
 
         let config = PuzzleConfig {
             solution_hash: BytesN::from_array(&env, &[0; 32]),
@@ -1267,8 +1949,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Spatial,
             &4,
-            &symbol_short!("SpaPzl"),
-            &symbol_short!("SpaDesc"),
+            &test_content(&env, "SpaPzl", "SpaDesc"),
             &config,
             &0, // 0% royalty
         );
@@ -1286,6 +1967,10 @@ mod tests {
 
         let total_distributed = client.get_total_royalties_distributed();
         assert_eq!(total_distributed, 0);
+
+        // Whole payment goes to the treasury
+        assert_eq!(token_client.balance(&treasury), 1000);
+        assert_eq!(token_client.balance(&contract_id), 0);
     }
 
     #[test]
@@ -1301,6 +1986,7 @@ mod tests {
         
         env.mock_all_auths();
         client.initialize(&admin);
+        client.set_verifier(&Some(Address::generate(&env)));
         client.authorize_creator(&creator);
 
         let config = PuzzleConfig {
@@ -1316,8 +2002,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Cryptography,
             &6,
-            &symbol_short!("CrypPzl"),
-            &symbol_short!("CrypDesc"),
+            &test_content(&env, "CrypPzl", "CrypDesc"),
             &config,
             &200,
         );
@@ -1342,6 +2027,7 @@ mod tests {
         
         env.mock_all_auths();
         client.initialize(&admin);
+        client.set_verifier(&Some(Address::generate(&env)));
         client.authorize_creator(&creator);
 
         // Create puzzle with future start time
@@ -1359,8 +2045,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Sequence,
             &5,
-            &symbol_short!("SeqPzl"),
-            &symbol_short!("SeqDesc"),
+            &test_content(&env, "SeqPzl", "SeqDesc"),
             &config,
             &150,
         );
@@ -1380,7 +2065,9 @@ mod tests {
         let player = Address::generate(&env);
         
         env.mock_all_auths();
+        env.ledger().set_timestamp(1500);
         client.initialize(&admin);
+        client.set_verifier(&Some(Address::generate(&env)));
         client.authorize_creator(&creator);
 
         let config = PuzzleConfig {
@@ -1397,8 +2084,7 @@ mod tests {
             &creator,
             &PuzzleCategory::Logic,
             &5,
-            &symbol_short!("TestPzl"),
-            &symbol_short!("TestDesc"),
+            &test_content(&env, "TestPzl", "TestDesc"),
             &config,
             &100,
         );
@@ -1412,4 +2098,443 @@ mod tests {
         let stats = client.get_creator_stats(&creator);
         assert_eq!(stats.success_rate, 6666); // 66.66% in basis points
     }
+
+    fn setup_versioned_puzzle(env: &Env) -> (PuzzleFactoryClient<'_>, u32) {
+        let contract_id = env.register_contract(None, PuzzleFactory);
+        let client = PuzzleFactoryClient::new(env, &contract_id);
+
+        let admin = Address::generate(env);
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1500);
+        client.initialize(&admin);
+        client.set_verifier(&Some(Address::generate(env)));
+
+        let config = PuzzleConfig {
+            solution_hash: BytesN::from_array(env, &[1; 32]),
+            start_time: 1000,
+            end_time: 2000,
+            max_attempts: 3,
+            time_limit: Some(300),
+            reward_points: 100,
+        };
+        let puzzle_id = client.create_puzzle(
+            &admin,
+            &PuzzleCategory::Logic,
+            &5,
+            &test_content(env, "VerPzl", "VerDesc"),
+            &config,
+            &0,
+        );
+        (client, puzzle_id)
+    }
+
+    fn updated_config(env: &Env) -> PuzzleConfig {
+        PuzzleConfig {
+            solution_hash: BytesN::from_array(env, &[2; 32]),
+            start_time: 1000,
+            end_time: 3000,
+            max_attempts: 5,
+            time_limit: None,
+            reward_points: 200,
+        }
+    }
+
+    #[test]
+    fn test_update_keeps_version_history() {
+        let env = Env::default();
+        let (client, puzzle_id) = setup_versioned_puzzle(&env);
+
+        client.update_puzzle(&puzzle_id, &None, &Some(updated_config(&env)), &None);
+
+        let v1 = client.get_puzzle_version(&puzzle_id, &1);
+        let v2 = client.get_puzzle_version(&puzzle_id, &2);
+        assert_eq!(v1.config.solution_hash, BytesN::from_array(&env, &[1; 32]));
+        assert_eq!(v2.config.solution_hash, BytesN::from_array(&env, &[2; 32]));
+        assert_eq!(v1.status, PuzzleStatus::Active);
+        assert_eq!(client.get_puzzle(&puzzle_id).metadata.version, 2);
+    }
+
+    #[test]
+    fn test_plays_recorded_per_version() {
+        let env = Env::default();
+        let (client, puzzle_id) = setup_versioned_puzzle(&env);
+        let player = Address::generate(&env);
+
+        client.record_play(&puzzle_id, &player, &false, &None::<i128>);
+        client.update_puzzle(&puzzle_id, &None, &Some(updated_config(&env)), &None);
+        client.record_play(&puzzle_id, &player, &true, &None::<i128>);
+        // A run started on version 1 can still be reported against it
+        client.record_version_play(&puzzle_id, &1, &player, &true, &None::<i128>);
+
+        let v1 = client.get_puzzle_version(&puzzle_id, &1);
+        let v2 = client.get_puzzle_version(&puzzle_id, &2);
+        assert_eq!((v1.total_plays, v1.successful_plays), (2, 1));
+        assert_eq!((v2.total_plays, v2.successful_plays), (1, 1));
+
+        let puzzle = client.get_puzzle(&puzzle_id);
+        assert_eq!(puzzle.metadata.total_plays, 3);
+        assert_eq!(puzzle.metadata.successful_plays, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "puzzle version is deprecated")]
+    fn test_deprecated_version_cannot_be_played() {
+        let env = Env::default();
+        let (client, puzzle_id) = setup_versioned_puzzle(&env);
+        let player = Address::generate(&env);
+
+        client.update_puzzle(&puzzle_id, &None, &Some(updated_config(&env)), &None);
+        client.deprecate_version(&puzzle_id, &1);
+        assert_eq!(client.get_puzzle_version(&puzzle_id, &1).status, PuzzleStatus::Deprecated);
+
+        // The current version stays playable
+        client.record_play(&puzzle_id, &player, &true, &None::<i128>);
+        client.record_version_play(&puzzle_id, &1, &player, &true, &None::<i128>);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot deprecate current version")]
+    fn test_cannot_deprecate_current_version() {
+        let env = Env::default();
+        let (client, puzzle_id) = setup_versioned_puzzle(&env);
+
+        client.deprecate_version(&puzzle_id, &1);
+    }
+
+    #[test]
+    fn test_rich_metadata_and_tag_search() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleFactory);
+        let client = PuzzleFactoryClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        let config = PuzzleConfig {
+            solution_hash: BytesN::from_array(&env, &[0; 32]),
+            start_time: 1000,
+            end_time: 2000,
+            max_attempts: 3,
+            time_limit: None,
+            reward_points: 100,
+        };
+
+        let mut content = test_content(
+            &env,
+            "The Lighthouse Keeper's Riddle (Part 1)",
+            "Follow the beams across the bay and find which lamp never turns off.",
+        );
+        content.tags = Vec::from_array(&env, [symbol_short!("riddle"), symbol_short!("story")]);
+        let puzzle1 = client.create_puzzle(&admin, &PuzzleCategory::Logic, &4, &content, &config, &0);
+
+        let mut content = test_content(&env, "Prime Spiral", "Spot the pattern.");
+        content.tags = Vec::from_array(&env, [symbol_short!("riddle")]);
+        let puzzle2 = client.create_puzzle(&admin, &PuzzleCategory::Mathematics, &6, &content, &config, &0);
+
+        let metadata = client.get_puzzle(&puzzle1).metadata;
+        assert_eq!(
+            metadata.title,
+            String::from_str(&env, "The Lighthouse Keeper's Riddle (Part 1)")
+        );
+        assert_eq!(metadata.content_hash, BytesN::from_array(&env, &[7; 32]));
+        assert_eq!(metadata.tags.len(), 2);
+
+        assert_eq!(
+            client.get_puzzles_by_tag(&symbol_short!("riddle")),
+            Vec::from_array(&env, [puzzle1, puzzle2])
+        );
+        assert_eq!(
+            client.get_puzzles_by_tag(&symbol_short!("story")),
+            Vec::from_array(&env, [puzzle1])
+        );
+
+        // Retagging moves the puzzle between indexes
+        let mut content = test_content(&env, "Prime Spiral", "Spot the pattern.");
+        content.tags = Vec::from_array(&env, [symbol_short!("primes")]);
+        client.update_puzzle(&puzzle2, &Some(content), &None, &None);
+        assert_eq!(
+            client.get_puzzles_by_tag(&symbol_short!("riddle")),
+            Vec::from_array(&env, [puzzle1])
+        );
+        assert_eq!(
+            client.get_puzzles_by_tag(&symbol_short!("primes")),
+            Vec::from_array(&env, [puzzle2])
+        );
+
+        // Deprecation drops the tag index entries
+        client.deprecate_puzzle(&puzzle1);
+        assert!(client.get_puzzles_by_tag(&symbol_short!("story")).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate tag")]
+    fn test_duplicate_tags_rejected() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleFactory);
+        let client = PuzzleFactoryClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+
+        let config = PuzzleConfig {
+            solution_hash: BytesN::from_array(&env, &[0; 32]),
+            start_time: 1000,
+            end_time: 2000,
+            max_attempts: 3,
+            time_limit: None,
+            reward_points: 100,
+        };
+        let mut content = test_content(&env, "Twins", "Two of the same.");
+        content.tags = Vec::from_array(&env, [symbol_short!("logic"), symbol_short!("logic")]);
+        client.create_puzzle(&admin, &PuzzleCategory::Logic, &4, &content, &config, &0);
+    }
+
+    #[test]
+    fn test_curation_flow() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleFactory);
+        let client = PuzzleFactoryClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let curator = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+        client.authorize_creator(&creator);
+        client.add_curator(&curator);
+        client.set_curation_required(&true);
+
+        let config = PuzzleConfig {
+            solution_hash: BytesN::from_array(&env, &[0; 32]),
+            start_time: 1000,
+            end_time: 2000,
+            max_attempts: 3,
+            time_limit: None,
+            reward_points: 100,
+        };
+        let puzzle_id = client.create_puzzle(
+            &creator,
+            &PuzzleCategory::Pattern,
+            &5,
+            &test_content(&env, "Draft", "Needs review"),
+            &config,
+            &0,
+        );
+        assert_eq!(client.get_puzzle(&puzzle_id).metadata.status, PuzzleStatus::Draft);
+        assert!(client.get_active_puzzles().is_empty());
+        let stats = client.get_creator_stats(&creator);
+        assert_eq!(stats.total_puzzles, 1);
+        assert_eq!(stats.active_puzzles, 0);
+
+        client.submit_for_review(&puzzle_id);
+        client.reject_puzzle(&curator, &puzzle_id);
+        assert_eq!(client.get_puzzle(&puzzle_id).metadata.status, PuzzleStatus::Draft);
+        assert_eq!(client.get_creator_stats(&creator).active_puzzles, 0);
+
+        client.submit_for_review(&puzzle_id);
+        assert_eq!(client.get_puzzle(&puzzle_id).metadata.status, PuzzleStatus::Review);
+        client.approve_puzzle(&curator, &puzzle_id);
+        assert_eq!(client.get_puzzle(&puzzle_id).metadata.status, PuzzleStatus::Active);
+        assert_eq!(client.get_active_puzzles(), Vec::from_array(&env, [puzzle_id]));
+        assert_eq!(client.get_creator_stats(&creator).active_puzzles, 1);
+
+        client.deactivate_puzzle(&puzzle_id);
+        assert_eq!(client.get_creator_stats(&creator).active_puzzles, 0);
+        client.activate_puzzle(&puzzle_id);
+        assert_eq!(client.get_creator_stats(&creator).active_puzzles, 1);
+        client.deprecate_puzzle(&puzzle_id);
+        assert_eq!(client.get_creator_stats(&creator).active_puzzles, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "puzzle not approved")]
+    fn test_draft_cannot_skip_review() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleFactory);
+        let client = PuzzleFactoryClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin);
+        client.set_curation_required(&true);
+
+        let config = PuzzleConfig {
+            solution_hash: BytesN::from_array(&env, &[0; 32]),
+            start_time: 1000,
+            end_time: 2000,
+            max_attempts: 3,
+            time_limit: None,
+            reward_points: 100,
+        };
+        let puzzle_id = client.create_puzzle(
+            &admin,
+            &PuzzleCategory::Pattern,
+            &5,
+            &test_content(&env, "Draft", "Needs review"),
+            &config,
+            &0,
+        );
+        client.activate_puzzle(&puzzle_id);
+    }
+
+    #[test]
+    fn test_ratings_feed_stats_and_flag_drift() {
+        let env = Env::default();
+        let (client, puzzle_id) = setup_versioned_puzzle(&env);
+        let creator = client.get_puzzle(&puzzle_id).metadata.creator;
+
+        // Declared difficulty is 5; five completers all rate it 9
+        for quality in [5u32, 4, 4, 3, 4] {
+            let player = Address::generate(&env);
+            client.record_play(&puzzle_id, &player, &true, &None::<i128>);
+            client.rate_puzzle(&player, &puzzle_id, &9, &quality);
+        }
+
+        let ratings = client.get_puzzle_ratings(&puzzle_id);
+        assert_eq!(ratings.rating_count, 5);
+        assert_eq!(ratings.difficulty_total, 45);
+        assert_eq!(ratings.quality_total, 20);
+
+        let stats = client.get_creator_stats(&creator);
+        assert_eq!(stats.total_ratings, 5);
+        assert_eq!(stats.average_quality, 400);
+
+        assert!(client.get_puzzle(&puzzle_id).metadata.flagged);
+        assert_eq!(client.get_flagged_puzzles(), Vec::from_array(&env, [puzzle_id]));
+
+        client.clear_flag(&creator, &puzzle_id);
+        assert!(!client.get_puzzle(&puzzle_id).metadata.flagged);
+        assert!(client.get_flagged_puzzles().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "no verifier linked")]
+    fn test_success_requires_linked_verifier() {
+        let env = Env::default();
+        let (client, puzzle_id) = setup_versioned_puzzle(&env);
+        let player = Address::generate(&env);
+
+        client.set_verifier(&None);
+        client.record_play(&puzzle_id, &player, &true, &None::<i128>);
+    }
+
+    #[test]
+    #[should_panic(expected = "puzzle not approved")]
+    fn test_draft_cannot_be_deactivated() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PuzzleFactory);
+        let client = PuzzleFactoryClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1500);
+        client.initialize(&admin);
+        client.set_curation_required(&true);
+
+        let config = PuzzleConfig {
+            solution_hash: BytesN::from_array(&env, &[1; 32]),
+            start_time: 1000,
+            end_time: 2000,
+            max_attempts: 3,
+            time_limit: None,
+            reward_points: 100,
+        };
+        let puzzle_id = client.create_puzzle(
+            &admin,
+            &PuzzleCategory::Pattern,
+            &5,
+            &test_content(&env, "Draft", "Needs review"),
+            &config,
+            &0,
+        );
+        // Draft -> Inactive -> Active would bypass the curators
+        client.deactivate_puzzle(&puzzle_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "puzzle not completed")]
+    fn test_rating_requires_completion() {
+        let env = Env::default();
+        let (client, puzzle_id) = setup_versioned_puzzle(&env);
+        let player = Address::generate(&env);
+
+        client.record_play(&puzzle_id, &player, &false, &None::<i128>);
+        client.rate_puzzle(&player, &puzzle_id, &5, &3);
+    }
+
+    #[test]
+    fn test_royalties_routed_to_splitter() {
+        use royalty_splitter::{RoyaltySplitter, RoyaltySplitterClient as SplitterClient};
+        use soroban_sdk::Map;
+
+        let env = Env::default();
+        let (client, puzzle_id) = setup_versioned_puzzle(&env);
+        let contract_id = client.address.clone();
+        let player = Address::generate(&env);
+        let (token_client, _) = setup_payment_token(&env, &client, &player);
+
+        let author_a = Address::generate(&env);
+        let author_b = Address::generate(&env);
+        let splitter_id = env.register_contract(None, RoyaltySplitter);
+        let splitter = SplitterClient::new(&env, &splitter_id);
+        let mut splits = Map::new(&env);
+        splits.set(author_a.clone(), 6000u32);
+        splits.set(author_b.clone(), 4000u32);
+        splitter.init(&Address::generate(&env), &splits, &0);
+
+        // 10% royalty, routed to the splitter
+        let content = test_content(&env, "CoAuthored", "Two minds");
+        client.update_puzzle(&puzzle_id, &Some(content), &None, &Some(1000));
+        client.set_royalty_recipient(&puzzle_id, &Some(splitter_id.clone()));
+        assert_eq!(client.get_royalty_recipient(&puzzle_id), splitter_id);
+
+        client.record_play(&puzzle_id, &player, &true, &Some(5000i128));
+        assert_eq!(client.get_pending_royalties(&splitter_id), 500);
+
+        assert_eq!(client.forward_royalties(&splitter_id), 500);
+        // Co-authors are paid their shares directly
+        assert_eq!(token_client.balance(&author_a), 300);
+        assert_eq!(token_client.balance(&author_b), 200);
+        assert_eq!(token_client.balance(&splitter_id), 0);
+        assert_eq!(token_client.balance(&contract_id), 0);
+        assert_eq!(client.get_pending_royalties(&splitter_id), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid royalty splitter")]
+    fn test_royalty_recipient_must_be_a_splitter() {
+        let env = Env::default();
+        let (client, puzzle_id) = setup_versioned_puzzle(&env);
+
+        let wallet = Address::generate(&env);
+        client.set_royalty_recipient(&puzzle_id, &Some(wallet));
+    }
+
+    #[test]
+    #[should_panic(expected = "payment token already set")]
+    fn test_payment_token_is_set_once() {
+        let env = Env::default();
+        let (client, _) = setup_versioned_puzzle(&env);
+        let player = Address::generate(&env);
+        let (_, treasury) = setup_payment_token(&env, &client, &player);
+
+        // Switching treasury is fine, switching token is not
+        let token = client.get_payment_token().unwrap();
+        client.set_payment_config(&token, &Address::generate(&env));
+        let other = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_payment_config(&other, &treasury);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a royalty splitter")]
+    fn test_forward_royalties_requires_registered_splitter() {
+        let env = Env::default();
+        let (client, _) = setup_versioned_puzzle(&env);
+
+        client.forward_royalties(&Address::generate(&env));
+    }
 }
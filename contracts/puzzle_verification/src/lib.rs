@@ -1,9 +1,72 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, Symbol,
+    contract, contractclient, contractimpl, contracttype, token, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
 };
 
+/// Default minimum seconds between `commit_solution` and `verify_solution`
+const DEFAULT_REVEAL_DELAY: u64 = 10;
+
+/// Per-player entries live in persistent storage and are bumped on every access
+const PLAYER_TTL_THRESHOLD: u32 = 518_400; // ~30 days @ 5s/ledger
+const PLAYER_TTL_BUMP: u32 = 1_036_800; // ~60 days @ 5s/ledger
+
+/// Minter interface of `reward_token::RewardToken`
+#[contractclient(name = "RewardTokenClient")]
+pub trait RewardTokenInterface {
+    fn mint(env: Env, minter: Address, to: Address, amount: i128);
+}
+
+/// How reward tokens reach players
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayoutMode {
+    /// Pay out of a balance held by this contract (e.g. native XLM SAC)
+    Transfer,
+    /// Mint through `reward_token`; this contract must be an authorized minter
+    Mint,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardConfig {
+    pub token: Address,
+    pub mode: PayoutMode,
+}
+
+/// Mirror of `puzzle_factory::PuzzleConfig`
+#[contracttype]
+#[derive(Clone)]
+pub struct FactoryPuzzleConfig {
+    pub solution_hash: BytesN<32>,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub max_attempts: u32,
+    pub time_limit: Option<u64>, // seconds
+    pub reward_points: i128,
+}
+
+/// Subset of `puzzle_factory::PuzzleFactory` used by this contract
+#[contractclient(name = "PuzzleFactoryClient")]
+pub trait PuzzleFactoryInterface {
+    fn get_puzzle_config(env: Env, puzzle_id: u32) -> FactoryPuzzleConfig;
+    fn record_play(
+        env: Env,
+        puzzle_id: u32,
+        player: Address,
+        success: bool,
+        payment_amount: Option<i128>,
+    );
+}
+
+/// Subset of `achievement_nft::AchievementNFT`; this contract must be an
+/// authorized verifier there
+#[contractclient(name = "AchievementNftClient")]
+pub trait AchievementNftInterface {
+    fn mark_puzzle_completed(env: Env, caller: Address, user: Address, puzzle_id: u32);
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PuzzleMeta {
@@ -15,6 +78,34 @@ pub struct PuzzleMeta {
     pub reward_points: i128,
 }
 
+/// A puzzle together with the limits that apply to each player
+struct PuzzleRules {
+    meta: PuzzleMeta,
+    max_attempts: u32, // 0 = unlimited
+    time_limit: Option<u64>,
+    factory: Option<Address>,
+}
+
+/// A player's sealed answer: `sha256(player || puzzle_id || solution || salt)`
+#[contracttype]
+#[derive(Clone)]
+pub struct Commitment {
+    pub hash: BytesN<32>,
+    pub committed_at: u64,
+}
+
+/// On-chain record of a successful solve, readable by other contracts
+#[contracttype]
+#[derive(Clone)]
+pub struct CompletionRecord {
+    pub solution_hash: BytesN<32>,
+    pub completed_at: u64,
+    /// Seconds since `start_attempt`, if the player started the clock
+    pub elapsed_secs: Option<u64>,
+    /// 1 = first player to solve the puzzle
+    pub rank: u32,
+}
+
 #[cfg(test)]
 mod double_claim_test {
     use super::*;
@@ -41,9 +132,14 @@ mod double_claim_test {
         let now = env.ledger().timestamp();
         client.set_puzzle(&7, &hash, &(now - 1), &(now + 1000), &3, &5);
 
-        assert!(client.verify_solution(&player, &7, &preimage));
+        let salt = BytesN::from_array(&env, &[1u8; 32]);
+        let commitment = client.compute_commitment(&player, &7, &preimage, &salt);
+        client.commit_solution(&player, &7, &commitment);
+        env.ledger().set_timestamp(now + 10);
+
+        assert!(client.verify_solution(&player, &7, &preimage, &salt));
         // Second attempt should panic
-        let _ = client.verify_solution(&player, &7, &preimage);
+        let _ = client.verify_solution(&player, &7, &preimage, &salt);
     }
 }
 
@@ -53,6 +149,19 @@ pub enum DataKey {
     Puzzle(u32),
     Completed(Address, u32),
     Rewards(Address),
+    RewardConfig,
+    Budget(u32),
+    Claimable(Address),
+    Commitment(Address, u32),
+    RevealDelay,
+    PuzzleFactory,
+    FailedAttempts(Address, u32),
+    AttemptStart(Address, u32),
+    Solvers(u32),
+    Completion(Address, u32),
+    AchievementNft,
+    FirstSolvedAt(u32),
+    CommitCutoff(u32),
 }
 
 #[contract]
@@ -104,12 +213,165 @@ impl PuzzleVerification {
             .set(&DataKey::Puzzle(puzzle_id), &meta);
     }
 
-    /// Verify solution preimage by hashing on-chain and credit rewards once
+    /// Admin: link a `puzzle_factory` contract. Puzzles not defined with
+    /// `set_puzzle` are then loaded from the factory, including its attempt
+    /// and time limits, and every reveal is reported via `record_play`.
+    pub fn set_puzzle_factory(env: Env, factory: Option<Address>) {
+        Self::require_admin(&env);
+        match factory {
+            Some(address) => env
+                .storage()
+                .instance()
+                .set(&DataKey::PuzzleFactory, &address),
+            None => env.storage().instance().remove(&DataKey::PuzzleFactory),
+        }
+    }
+
+    pub fn get_puzzle_factory(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PuzzleFactory)
+    }
+
+    /// Admin: link an `achievement_nft` contract. Every solve is then marked
+    /// completed there so the player can mint the achievement.
+    pub fn set_achievement_nft(env: Env, achievement_nft: Option<Address>) {
+        Self::require_admin(&env);
+        match achievement_nft {
+            Some(address) => env
+                .storage()
+                .instance()
+                .set(&DataKey::AchievementNft, &address),
+            None => env.storage().instance().remove(&DataKey::AchievementNft),
+        }
+    }
+
+    pub fn get_achievement_nft(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::AchievementNft)
+    }
+
+    /// Start the clock on a timed puzzle. Can only be called once per attempt;
+    /// a failed or timed-out reveal clears the clock so the player can retry.
+    pub fn start_attempt(env: Env, player: Address, puzzle_id: u32) {
+        player.require_auth();
+
+        if Self::is_completed(env.clone(), player.clone(), puzzle_id) {
+            panic!("puzzle already completed");
+        }
+        Self::active_puzzle(&env, puzzle_id);
+
+        let key = DataKey::AttemptStart(player.clone(), puzzle_id);
+        if env.storage().persistent().has(&key) {
+            panic!("attempt already started");
+        }
+        let now = env.ledger().timestamp();
+        Self::save_player(&env, &key, &now);
+
+        env.events().publish(
+            (Symbol::new(&env, "attempt"), Symbol::new(&env, "started")),
+            (player, puzzle_id, now),
+        );
+    }
+
+    pub fn attempt_started_at(env: Env, player: Address, puzzle_id: u32) -> Option<u64> {
+        Self::load_player(&env, &DataKey::AttemptStart(player, puzzle_id))
+    }
+
+    pub fn failed_attempts(env: Env, player: Address, puzzle_id: u32) -> u32 {
+        Self::load_player(&env, &DataKey::FailedAttempts(player, puzzle_id)).unwrap_or(0)
+    }
+
+    /// Admin: minimum seconds a commitment must age before it can be revealed
+    pub fn set_reveal_delay(env: Env, delay_secs: u64) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::RevealDelay, &delay_secs);
+    }
+
+    pub fn get_reveal_delay(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RevealDelay)
+            .unwrap_or(DEFAULT_REVEAL_DELAY)
+    }
+
+    /// Admin: whether a puzzle's commit phase closes at its first successful
+    /// reveal. On by default; turning it off lets players keep their
+    /// `max_attempts` retries after the solution has been revealed.
+    pub fn set_commit_cutoff(env: Env, puzzle_id: u32, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::CommitCutoff(puzzle_id), &enabled);
+    }
+
+    pub fn commit_cutoff(env: Env, puzzle_id: u32) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::CommitCutoff(puzzle_id))
+            .unwrap_or(true)
+    }
+
+    /// Hash a solution the way `commit_solution` expects. Meant for off-chain
+    /// simulation; calling it in a transaction would publish the solution.
+    pub fn compute_commitment(
+        env: Env,
+        player: Address,
+        puzzle_id: u32,
+        solution: Bytes,
+        salt: BytesN<32>,
+    ) -> BytesN<32> {
+        let mut data = player.to_xdr(&env);
+        data.extend_from_array(&puzzle_id.to_be_bytes());
+        data.append(&solution);
+        data.extend_from_array(&salt.to_array());
+        env.crypto().sha256(&data).into()
+    }
+
+    /// Phase one: seal an answer. Committing again replaces the previous
+    /// commitment and restarts the reveal delay. Unless disabled with
+    /// `set_commit_cutoff`, the commit phase closes with the first successful
+    /// reveal, which makes the solution public.
+    pub fn commit_solution(env: Env, player: Address, puzzle_id: u32, commitment: BytesN<32>) {
+        player.require_auth();
+
+        if Self::is_completed(env.clone(), player.clone(), puzzle_id) {
+            panic!("puzzle already completed");
+        }
+        if Self::commit_cutoff(env.clone(), puzzle_id)
+            && Self::first_solved_at(env.clone(), puzzle_id).is_some()
+        {
+            panic!("commit phase closed");
+        }
+        let rules = Self::active_puzzle(&env, puzzle_id);
+        Self::enforce_limits(&env, &player, puzzle_id, &rules);
+
+        Self::save_player(
+            &env,
+            &DataKey::Commitment(player.clone(), puzzle_id),
+            &Commitment {
+                hash: commitment,
+                committed_at: env.ledger().timestamp(),
+            },
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "puzzle"), Symbol::new(&env, "committed")),
+            (player, puzzle_id),
+        );
+    }
+
+    pub fn get_commitment(env: Env, player: Address, puzzle_id: u32) -> Option<Commitment> {
+        Self::load_player(&env, &DataKey::Commitment(player, puzzle_id))
+    }
+
+    /// Phase two: reveal a committed solution, verify it on-chain and credit
+    /// rewards once. A wrong solution consumes the commitment.
     pub fn verify_solution(
         env: Env,
         player: Address,
         puzzle_id: u32,
         solution_preimage: Bytes,
+        salt: BytesN<32>,
     ) -> bool {
         player.require_auth();
 
@@ -119,65 +381,398 @@ impl PuzzleVerification {
         }
 
         // Load puzzle and enforce time window
-        let meta: PuzzleMeta = env
-            .storage()
-            .instance()
-            .get(&DataKey::Puzzle(puzzle_id))
-            .expect("puzzle");
+        let rules = Self::active_puzzle(&env, puzzle_id);
+        let meta = &rules.meta;
+
+        // The reveal must open an aged commitment made by this player
+        let commitment_key = DataKey::Commitment(player.clone(), puzzle_id);
+        let commitment: Commitment =
+            Self::load_player(&env, &commitment_key).expect("no commitment");
         let now = env.ledger().timestamp();
-        if now < meta.start_ts || now > meta.end_ts {
-            panic!("puzzle not active");
+        if now < commitment.committed_at + Self::get_reveal_delay(env.clone()) {
+            panic!("reveal too early");
+        }
+        // A commitment sealed in the same ledger as the first reveal may
+        // have been copied from it
+        if Self::commit_cutoff(env.clone(), puzzle_id)
+            && Self::first_solved_at(env.clone(), puzzle_id)
+                .is_some_and(|solved_at| commitment.committed_at >= solved_at)
+        {
+            panic!("commit phase closed");
         }
+        let opened = Self::compute_commitment(
+            env.clone(),
+            player.clone(),
+            puzzle_id,
+            solution_preimage.clone(),
+            salt,
+        );
+        if opened != commitment.hash {
+            panic!("commitment mismatch");
+        }
+        env.storage().persistent().remove(&commitment_key);
+
+        // The clock keeps running until the reveal; only the forced reveal
+        // delay is added on top of the time limit
+        let timed_out = match rules.time_limit {
+            Some(limit) => Self::attempt_started_at(env.clone(), player.clone(), puzzle_id)
+                .is_none_or(|started_at| {
+                    now > started_at + limit + Self::get_reveal_delay(env.clone())
+                }),
+            None => false,
+        };
 
         // Hash the provided solution and compare
         let computed: BytesN<32> = env.crypto().sha256(&solution_preimage).into();
-        if computed != meta.solution_hash {
+        if timed_out || computed != meta.solution_hash {
+            let failed = Self::failed_attempts(env.clone(), player.clone(), puzzle_id) + 1;
+            Self::save_player(
+                &env,
+                &DataKey::FailedAttempts(player.clone(), puzzle_id),
+                &failed,
+            );
+            env.storage()
+                .persistent()
+                .remove(&DataKey::AttemptStart(player.clone(), puzzle_id));
+            Self::report_play(&env, &rules, &player, puzzle_id, false);
             return false;
         }
 
         // Mark completed
+        Self::save_player(&env, &DataKey::Completed(player.clone(), puzzle_id), &true);
+
+        let rank = Self::solver_count(env.clone(), puzzle_id) + 1;
         env.storage()
             .instance()
-            .set(&DataKey::Completed(player.clone(), puzzle_id), &true);
+            .set(&DataKey::Solvers(puzzle_id), &rank);
+        if rank == 1 {
+            env.storage()
+                .instance()
+                .set(&DataKey::FirstSolvedAt(puzzle_id), &now);
+        }
+        let record = CompletionRecord {
+            solution_hash: meta.solution_hash.clone(),
+            completed_at: now,
+            elapsed_secs: Self::attempt_started_at(env.clone(), player.clone(), puzzle_id)
+                .map(|started_at| now - started_at),
+            rank,
+        };
+        Self::save_player(
+            &env,
+            &DataKey::Completion(player.clone(), puzzle_id),
+            &record,
+        );
 
         // Difficulty-based reward scaling: scale reward_points by difficulty factor (>=1)
         let scaled = meta.reward_points * (meta.difficulty as i128).max(1);
-        let mut rewards: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Rewards(player.clone()))
-            .unwrap_or(0);
+        let mut rewards: i128 =
+            Self::load_player(&env, &DataKey::Rewards(player.clone())).unwrap_or(0);
         rewards += scaled;
-        env.storage()
-            .instance()
-            .set(&DataKey::Rewards(player.clone()), &rewards);
+        Self::save_player(&env, &DataKey::Rewards(player.clone()), &rewards);
+
+        // Reserve tokens from the puzzle budget; an exhausted budget pays nothing
+        let paid = Self::reserve_payout(&env, &player, puzzle_id, scaled);
+
+        Self::report_play(&env, &rules, &player, puzzle_id, true);
+        if let Some(achievement_nft) = Self::get_achievement_nft(env.clone()) {
+            AchievementNftClient::new(&env, &achievement_nft).mark_puzzle_completed(
+                &env.current_contract_address(),
+                &player,
+                &puzzle_id,
+            );
+        }
 
         // Emit completion event
         env.events().publish(
             (Symbol::new(&env, "puzzle"), Symbol::new(&env, "completed")),
-            (player, puzzle_id, scaled),
+            (player, puzzle_id, scaled, paid),
         );
 
         true
     }
 
     pub fn is_completed(env: Env, player: Address, puzzle_id: u32) -> bool {
+        Self::load_player(&env, &DataKey::Completed(player, puzzle_id)).unwrap_or(false)
+    }
+
+    /// Completion details for a solved puzzle, `None` if `player` has not solved it
+    pub fn get_completion(env: Env, player: Address, puzzle_id: u32) -> Option<CompletionRecord> {
+        Self::load_player(&env, &DataKey::Completion(player, puzzle_id))
+    }
+
+    /// Number of players who have solved a puzzle
+    pub fn solver_count(env: Env, puzzle_id: u32) -> u32 {
         env.storage()
             .instance()
-            .get(&DataKey::Completed(player, puzzle_id))
-            .unwrap_or(false)
+            .get(&DataKey::Solvers(puzzle_id))
+            .unwrap_or(0)
     }
 
-    pub fn rewards_of(env: Env, player: Address) -> i128 {
+    /// When the puzzle was first solved; closes the commit phase unless disabled with `set_commit_cutoff`
+    pub fn first_solved_at(env: Env, puzzle_id: u32) -> Option<u64> {
         env.storage()
             .instance()
-            .get(&DataKey::Rewards(player))
-            .unwrap_or(0)
+            .get(&DataKey::FirstSolvedAt(puzzle_id))
+    }
+
+    pub fn rewards_of(env: Env, player: Address) -> i128 {
+        Self::load_player(&env, &DataKey::Rewards(player)).unwrap_or(0)
+    }
+
+    /// Move a player's completion markers for `puzzle_ids` and their reward
+    /// total from instance storage, where earlier versions kept them, into
+    /// persistent storage. Safe to call for any player and more than once.
+    pub fn migrate_player(env: Env, player: Address, puzzle_ids: Vec<u32>) {
+        for puzzle_id in puzzle_ids.iter() {
+            Self::migrate_key::<bool>(&env, DataKey::Completed(player.clone(), puzzle_id));
+        }
+        Self::migrate_key::<i128>(&env, DataKey::Rewards(player));
     }
 
     pub fn get_puzzle(env: Env, puzzle_id: u32) -> Option<PuzzleMeta> {
         env.storage().instance().get(&DataKey::Puzzle(puzzle_id))
     }
+
+    /// Admin: choose the token rewards are paid in. Can only be set once so
+    /// outstanding claims and budgets always refer to the same token.
+    pub fn set_reward_token(env: Env, token: Address, mode: PayoutMode) {
+        Self::require_admin(&env);
+        if env.storage().instance().has(&DataKey::RewardConfig) {
+            panic!("reward token already set");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardConfig, &RewardConfig { token, mode });
+    }
+
+    pub fn get_reward_config(env: Env) -> Option<RewardConfig> {
+        env.storage().instance().get(&DataKey::RewardConfig)
+    }
+
+    /// Add to a puzzle's payout budget. In `Transfer` mode anyone can fund by
+    /// depositing tokens; in `Mint` mode only the admin can raise the mint cap.
+    pub fn fund_puzzle(env: Env, funder: Address, puzzle_id: u32, amount: i128) {
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let config = Self::reward_config(&env);
+        match config.mode {
+            PayoutMode::Transfer => {
+                funder.require_auth();
+                token::Client::new(&env, &config.token).transfer(
+                    &funder,
+                    &env.current_contract_address(),
+                    &amount,
+                );
+            }
+            PayoutMode::Mint => Self::require_admin(&env),
+        }
+
+        let budget = Self::puzzle_budget(env.clone(), puzzle_id) + amount;
+        env.storage()
+            .instance()
+            .set(&DataKey::Budget(puzzle_id), &budget);
+
+        env.events().publish(
+            (Symbol::new(&env, "puzzle"), Symbol::new(&env, "funded")),
+            (funder, puzzle_id, amount),
+        );
+    }
+
+    /// Remaining token budget for a puzzle
+    pub fn puzzle_budget(env: Env, puzzle_id: u32) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Budget(puzzle_id))
+            .unwrap_or(0)
+    }
+
+    /// Admin: take back unspent budget from a puzzle. In `Transfer` mode the
+    /// tokens are sent to `to`; in `Mint` mode the mint cap is just lowered.
+    /// Tokens already reserved for players stay claimable.
+    pub fn withdraw_budget(env: Env, puzzle_id: u32, to: Address, amount: i128) {
+        Self::require_admin(&env);
+        let budget = Self::puzzle_budget(env.clone(), puzzle_id);
+        if amount <= 0 || amount > budget {
+            panic!("invalid amount");
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Budget(puzzle_id), &(budget - amount));
+
+        let config = Self::reward_config(&env);
+        if config.mode == PayoutMode::Transfer {
+            token::Client::new(&env, &config.token).transfer(
+                &env.current_contract_address(),
+                &to,
+                &amount,
+            );
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "budget"), Symbol::new(&env, "withdrawn")),
+            (puzzle_id, to, amount),
+        );
+    }
+
+    /// Tokens reserved for a player and not yet claimed
+    pub fn claimable_of(env: Env, player: Address) -> i128 {
+        Self::load_player(&env, &DataKey::Claimable(player)).unwrap_or(0)
+    }
+
+    /// Pay out all reserved tokens to the player
+    pub fn claim_rewards(env: Env, player: Address) -> i128 {
+        player.require_auth();
+
+        let amount = Self::claimable_of(env.clone(), player.clone());
+        if amount <= 0 {
+            panic!("nothing to claim");
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Claimable(player.clone()));
+
+        let config = Self::reward_config(&env);
+        let this = env.current_contract_address();
+        match config.mode {
+            PayoutMode::Transfer => {
+                token::Client::new(&env, &config.token).transfer(&this, &player, &amount)
+            }
+            PayoutMode::Mint => {
+                RewardTokenClient::new(&env, &config.token).mint(&this, &player, &amount)
+            }
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "rewards"), Symbol::new(&env, "claimed")),
+            (player, amount),
+        );
+
+        amount
+    }
+
+    fn load_puzzle(env: &Env, puzzle_id: u32) -> PuzzleRules {
+        if let Some(meta) = env.storage().instance().get(&DataKey::Puzzle(puzzle_id)) {
+            return PuzzleRules {
+                meta,
+                max_attempts: 0,
+                time_limit: None,
+                factory: None,
+            };
+        }
+
+        let factory: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PuzzleFactory)
+            .expect("puzzle");
+        let config = PuzzleFactoryClient::new(env, &factory).get_puzzle_config(&puzzle_id);
+        PuzzleRules {
+            meta: PuzzleMeta {
+                id: puzzle_id,
+                solution_hash: config.solution_hash,
+                start_ts: config.start_time,
+                end_ts: config.end_time,
+                // Factory reward points are set per puzzle by its creator
+                difficulty: 1,
+                reward_points: config.reward_points,
+            },
+            max_attempts: config.max_attempts,
+            time_limit: config.time_limit,
+            factory: Some(factory),
+        }
+    }
+
+    fn active_puzzle(env: &Env, puzzle_id: u32) -> PuzzleRules {
+        let rules = Self::load_puzzle(env, puzzle_id);
+        let now = env.ledger().timestamp();
+        if now < rules.meta.start_ts || now > rules.meta.end_ts {
+            panic!("puzzle not active");
+        }
+        rules
+    }
+
+    fn enforce_limits(env: &Env, player: &Address, puzzle_id: u32, rules: &PuzzleRules) {
+        if rules.max_attempts > 0
+            && Self::failed_attempts(env.clone(), player.clone(), puzzle_id) >= rules.max_attempts
+        {
+            panic!("max attempts reached");
+        }
+
+        if let Some(limit) = rules.time_limit {
+            let started_at = Self::attempt_started_at(env.clone(), player.clone(), puzzle_id)
+                .expect("attempt not started");
+            if env.ledger().timestamp() > started_at + limit {
+                panic!("time limit exceeded");
+            }
+        }
+    }
+
+    fn report_play(
+        env: &Env,
+        rules: &PuzzleRules,
+        player: &Address,
+        puzzle_id: u32,
+        success: bool,
+    ) {
+        if let Some(factory) = &rules.factory {
+            PuzzleFactoryClient::new(env, factory).record_play(&puzzle_id, player, &success, &None);
+        }
+    }
+
+    fn migrate_key<V: IntoVal<Env, Val> + TryFromVal<Env, Val>>(env: &Env, key: DataKey) {
+        if let Some(value) = env.storage().instance().get::<DataKey, V>(&key) {
+            if !env.storage().persistent().has(&key) {
+                Self::save_player(env, &key, &value);
+            }
+            env.storage().instance().remove(&key);
+        }
+    }
+
+    /// Per-player entries live in persistent storage; `Completed` and
+    /// `Rewards` entries not yet moved by `migrate_player` are still read from
+    /// instance storage, where earlier versions kept them.
+    fn load_player<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+        if env.storage().persistent().has(key) {
+            env.storage()
+                .persistent()
+                .extend_ttl(key, PLAYER_TTL_THRESHOLD, PLAYER_TTL_BUMP);
+            return env.storage().persistent().get(key);
+        }
+        env.storage().instance().get(key)
+    }
+
+    fn save_player<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, PLAYER_TTL_THRESHOLD, PLAYER_TTL_BUMP);
+        env.storage().instance().remove(key);
+    }
+
+    fn reward_config(env: &Env) -> RewardConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardConfig)
+            .expect("reward token")
+    }
+
+    fn reserve_payout(env: &Env, player: &Address, puzzle_id: u32, amount: i128) -> i128 {
+        let budget = Self::puzzle_budget(env.clone(), puzzle_id);
+        let paid = amount.min(budget).max(0);
+        if paid == 0 {
+            return 0;
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Budget(puzzle_id), &(budget - paid));
+        let claimable = Self::claimable_of(env.clone(), player.clone()) + paid;
+        Self::save_player(env, &DataKey::Claimable(player.clone()), &claimable);
+
+        paid
+    }
 }
 
 #[cfg(test)]
@@ -186,6 +781,43 @@ mod test {
     use soroban_sdk::testutils::Address as _;
     use soroban_sdk::testutils::Ledger as _;
 
+    /// Commit, wait out the reveal delay and reveal
+    fn solve(
+        env: &Env,
+        client: &PuzzleVerificationClient,
+        player: &Address,
+        puzzle_id: u32,
+        solution: &Bytes,
+    ) -> bool {
+        let salt = BytesN::from_array(env, &[5u8; 32]);
+        let commitment = client.compute_commitment(player, &puzzle_id, solution, &salt);
+        client.commit_solution(player, &puzzle_id, &commitment);
+        env.ledger()
+            .with_mut(|li| li.timestamp += client.get_reveal_delay());
+        client.verify_solution(player, &puzzle_id, solution, &salt)
+    }
+
+    /// Commit for every player before the first reveal closes the commit
+    /// phase, then reveal in order
+    fn solve_all(
+        env: &Env,
+        client: &PuzzleVerificationClient,
+        players: &[&Address],
+        puzzle_id: u32,
+        solution: &Bytes,
+    ) {
+        let salt = BytesN::from_array(env, &[5u8; 32]);
+        for player in players {
+            let commitment = client.compute_commitment(player, &puzzle_id, solution, &salt);
+            client.commit_solution(player, &puzzle_id, &commitment);
+        }
+        env.ledger()
+            .with_mut(|li| li.timestamp += client.get_reveal_delay());
+        for player in players {
+            assert!(client.verify_solution(player, &puzzle_id, solution, &salt));
+        }
+    }
+
     #[test]
     fn test_verification_flow() {
         let env = Env::default();
@@ -195,11 +827,6 @@ mod test {
         let admin = Address::generate(&env);
         let player = Address::generate(&env);
 
-<<<<<<< Updated upstream
-=======
-        client.set_puzzle(&1, &solution);
-
->>>>>>> Stashed changes
         env.mock_all_auths();
         client.initialize(&admin);
 
@@ -214,12 +841,12 @@ mod test {
 
         // Wrong preimage
         let wrong = Bytes::from_array(&env, &[8u8; 5]);
-        assert_eq!(client.verify_solution(&player, &1, &wrong), false);
-        assert_eq!(client.is_completed(&player, &1), false);
+        assert!(!solve(&env, &client, &player, 1, &wrong));
+        assert!(!client.is_completed(&player, &1));
 
         // Correct preimage
-        assert_eq!(client.verify_solution(&player, &1, &preimage), true);
-        assert_eq!(client.is_completed(&player, &1), true);
+        assert!(solve(&env, &client, &player, 1, &preimage));
+        assert!(client.is_completed(&player, &1));
         // Reward scaled: 50 * difficulty(2) = 100
         assert_eq!(client.rewards_of(&player), 100);
 
@@ -246,7 +873,538 @@ mod test {
         // Expired window
         client.set_puzzle(&42, &hash, &(now - 100), &(now - 50), &1, &10);
 
-        // Attempt verify should panic
-        let _ = client.verify_solution(&player, &42, &preimage);
+        // Attempt to commit should panic
+        let salt = BytesN::from_array(&env, &[0u8; 32]);
+        let commitment = client.compute_commitment(&player, &42, &preimage, &salt);
+        client.commit_solution(&player, &42, &commitment);
+    }
+
+    fn setup_puzzle(env: &Env, client: &PuzzleVerificationClient, puzzle_id: u32) -> Bytes {
+        env.ledger().set_timestamp(1_000);
+        let preimage = Bytes::from_array(env, &[puzzle_id as u8; 4]);
+        let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+        client.set_puzzle(&puzzle_id, &hash, &999, &2_000, &2, &50);
+        preimage
+    }
+
+    #[test]
+    fn test_instance_stored_completion_still_blocks_second_claim() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        let preimage = setup_puzzle(&env, &client, 1);
+
+        // Records as written by earlier versions
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .instance()
+                .set(&DataKey::Completed(player.clone(), 1), &true);
+            env.storage()
+                .instance()
+                .set(&DataKey::Rewards(player.clone()), &100i128);
+        });
+
+        assert!(client.is_completed(&player, &1));
+        assert_eq!(client.rewards_of(&player), 100);
+        let salt = BytesN::from_array(&env, &[5u8; 32]);
+        let commitment = client.compute_commitment(&player, &1, &preimage, &salt);
+        assert!(client.try_commit_solution(&player, &1, &commitment).is_err());
+        assert!(client.try_verify_solution(&player, &1, &preimage, &salt).is_err());
+
+        let mut puzzle_ids = soroban_sdk::Vec::new(&env);
+        puzzle_ids.push_back(1);
+        client.migrate_player(&player, &puzzle_ids);
+        env.as_contract(&contract_id, || {
+            let key = DataKey::Completed(player.clone(), 1);
+            assert!(!env.storage().instance().has(&key));
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&DataKey::Rewards(player.clone())));
+        });
+        assert!(client.is_completed(&player, &1));
+        assert_eq!(client.rewards_of(&player), 100);
+        assert!(client.try_verify_solution(&player, &1, &preimage, &salt).is_err());
+    }
+
+    #[test]
+    fn test_claim_rewards_transfer_mode() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        token::StellarAssetClient::new(&env, &token_id).mint(&sponsor, &1_000);
+        let token_client = token::Client::new(&env, &token_id);
+
+        client.set_reward_token(&token_id, &PayoutMode::Transfer);
+        let preimage = setup_puzzle(&env, &client, 1);
+        client.fund_puzzle(&sponsor, &1, &300);
+        assert_eq!(client.puzzle_budget(&1), 300);
+        assert_eq!(token_client.balance(&contract_id), 300);
+
+        assert!(solve(&env, &client, &player, 1, &preimage));
+        assert_eq!(client.claimable_of(&player), 100);
+        assert_eq!(client.puzzle_budget(&1), 200);
+
+        assert_eq!(client.claim_rewards(&player), 100);
+        assert_eq!(token_client.balance(&player), 100);
+        assert_eq!(client.claimable_of(&player), 0);
+    }
+
+    #[test]
+    fn test_admin_withdraws_unspent_budget() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+
+        let token_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        token::StellarAssetClient::new(&env, &token_id).mint(&sponsor, &1_000);
+        let token_client = token::Client::new(&env, &token_id);
+
+        client.set_reward_token(&token_id, &PayoutMode::Transfer);
+        let preimage = setup_puzzle(&env, &client, 1);
+        client.fund_puzzle(&sponsor, &1, &300);
+        assert!(solve(&env, &client, &player, 1, &preimage));
+
+        // Only the unreserved 200 can be taken back
+        assert!(client.try_withdraw_budget(&1, &sponsor, &201).is_err());
+        client.withdraw_budget(&1, &sponsor, &200);
+        assert_eq!(client.puzzle_budget(&1), 0);
+        assert_eq!(token_client.balance(&sponsor), 900);
+
+        // The player's reserved reward is still paid out
+        assert_eq!(client.claim_rewards(&player), 100);
+        assert_eq!(token_client.balance(&contract_id), 0);
+    }
+
+    #[test]
+    fn test_exhausted_budget_still_records_completion() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        let player3 = Address::generate(&env);
+        client.initialize(&admin);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        token::StellarAssetClient::new(&env, &token_id).mint(&admin, &150);
+        client.set_reward_token(&token_id, &PayoutMode::Transfer);
+        let preimage = setup_puzzle(&env, &client, 1);
+        client.fund_puzzle(&admin, &1, &150);
+
+        // Budget only covers part of the second payout
+        solve_all(&env, &client, &[&player1, &player2, &player3], 1, &preimage);
+
+        assert_eq!(client.claimable_of(&player1), 100);
+        assert_eq!(client.claimable_of(&player2), 50);
+        assert_eq!(client.claimable_of(&player3), 0);
+        assert_eq!(client.puzzle_budget(&1), 0);
+
+        // Points and completion are still recorded
+        assert!(client.is_completed(&player3, &1));
+        assert_eq!(client.rewards_of(&player3), 100);
+    }
+
+    #[test]
+    fn test_claim_rewards_mint_mode() {
+        use reward_token::{RewardToken, RewardTokenClient as TokenClient};
+        use soroban_sdk::String;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+
+        let token_id = env.register_contract(None, RewardToken);
+        let reward_token = TokenClient::new(&env, &token_id);
+        reward_token.initialize(
+            &admin,
+            &String::from_str(&env, "Quest"),
+            &String::from_str(&env, "QST"),
+            &7,
+        );
+        reward_token.authorize_minter(&contract_id);
+
+        client.set_reward_token(&token_id, &PayoutMode::Mint);
+        let preimage = setup_puzzle(&env, &client, 3);
+        client.fund_puzzle(&admin, &3, &1_000);
+
+        assert!(solve(&env, &client, &player, 3, &preimage));
+        assert_eq!(client.claim_rewards(&player), 100);
+        assert_eq!(reward_token.balance(&player), 100);
+        assert_eq!(client.puzzle_budget(&3), 900);
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing to claim")]
+    fn test_claim_without_rewards_panics() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        client.claim_rewards(&player);
+    }
+
+    #[test]
+    #[should_panic(expected = "reveal too early")]
+    fn test_reveal_before_delay_panics() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_reveal_delay(&30);
+        let preimage = setup_puzzle(&env, &client, 1);
+
+        let salt = BytesN::from_array(&env, &[2u8; 32]);
+        let commitment = client.compute_commitment(&player, &1, &preimage, &salt);
+        client.commit_solution(&player, &1, &commitment);
+        env.ledger().with_mut(|li| li.timestamp += 29);
+        client.verify_solution(&player, &1, &preimage, &salt);
+    }
+
+    #[test]
+    #[should_panic(expected = "no commitment")]
+    fn test_reveal_without_commitment_panics() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        let preimage = setup_puzzle(&env, &client, 1);
+
+        client.verify_solution(
+            &player,
+            &1,
+            &preimage,
+            &BytesN::from_array(&env, &[2u8; 32]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "commitment mismatch")]
+    fn test_copied_commitment_cannot_be_revealed() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let solver = Address::generate(&env);
+        let copier = Address::generate(&env);
+        client.initialize(&admin);
+        let preimage = setup_puzzle(&env, &client, 1);
+
+        // The copier replays the solver's public commitment and later reveal
+        let salt = BytesN::from_array(&env, &[3u8; 32]);
+        let commitment = client.compute_commitment(&solver, &1, &preimage, &salt);
+        client.commit_solution(&solver, &1, &commitment);
+        client.commit_solution(&copier, &1, &commitment);
+        env.ledger().with_mut(|li| li.timestamp += 10);
+
+        assert!(client.verify_solution(&solver, &1, &preimage, &salt));
+        client.verify_solution(&copier, &1, &preimage, &salt);
+    }
+
+    #[test]
+    #[should_panic(expected = "commit phase closed")]
+    fn test_commit_after_first_reveal_rejected() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let solver = Address::generate(&env);
+        let copier = Address::generate(&env);
+        client.initialize(&admin);
+        let preimage = setup_puzzle(&env, &client, 1);
+        assert!(client.commit_cutoff(&1));
+
+        assert!(solve(&env, &client, &solver, 1, &preimage));
+        assert_eq!(client.first_solved_at(&1), Some(env.ledger().timestamp()));
+
+        // The revealed solution is public now; sealing it proves nothing
+        let salt = BytesN::from_array(&env, &[3u8; 32]);
+        let commitment = client.compute_commitment(&copier, &1, &preimage, &salt);
+        client.commit_solution(&copier, &1, &commitment);
+    }
+
+    #[test]
+    fn test_retries_left_after_first_solve_without_cutoff() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let solver = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        let (_, puzzle_id, preimage) = setup_factory_puzzle(&env, &client, &admin, 2, None);
+        let wrong = Bytes::from_array(&env, &[0u8; 6]);
+        client.set_commit_cutoff(&puzzle_id, &false);
+
+        assert!(solve(&env, &client, &solver, puzzle_id, &preimage));
+
+        // A failed reveal leaves the player's second attempt usable
+        assert!(!solve(&env, &client, &player, puzzle_id, &wrong));
+        assert!(solve(&env, &client, &player, puzzle_id, &preimage));
+        assert_eq!(client.get_completion(&player, &puzzle_id).unwrap().rank, 2);
+
+        // Turning the cutoff back on shuts out later players
+        let late = Address::generate(&env);
+        client.set_commit_cutoff(&puzzle_id, &true);
+        let salt = BytesN::from_array(&env, &[3u8; 32]);
+        let commitment = client.compute_commitment(&late, &puzzle_id, &preimage, &salt);
+        assert!(client.try_commit_solution(&late, &puzzle_id, &commitment).is_err());
+    }
+
+    fn setup_factory_puzzle(
+        env: &Env,
+        client: &PuzzleVerificationClient,
+        admin: &Address,
+        max_attempts: u32,
+        time_limit: Option<u64>,
+    ) -> (puzzle_factory::PuzzleFactoryClient<'static>, u32, Bytes) {
+        use puzzle_factory::{PuzzleCategory, PuzzleConfig, PuzzleContent, PuzzleFactory};
+        use soroban_sdk::{String, Vec};
+
+        env.ledger().set_timestamp(1_000);
+        let factory_id = env.register_contract(None, PuzzleFactory);
+        let factory = puzzle_factory::PuzzleFactoryClient::new(env, &factory_id);
+        factory.initialize(admin);
+        factory.set_verifier(&Some(client.address.clone()));
+
+        let preimage = Bytes::from_array(env, &[4u8; 6]);
+        let config = PuzzleConfig {
+            solution_hash: env.crypto().sha256(&preimage).into(),
+            start_time: 900,
+            end_time: 5_000,
+            max_attempts,
+            time_limit,
+            reward_points: 40,
+        };
+        let puzzle_id = factory.create_puzzle(
+            admin,
+            &PuzzleCategory::Logic,
+            &3,
+            &PuzzleContent {
+                title: String::from_str(env, "Maze"),
+                description: String::from_str(env, "Find the exit"),
+                content_uri: String::from_str(env, "ipfs://maze"),
+                content_hash: BytesN::from_array(env, &[0u8; 32]),
+                tags: Vec::new(env),
+            },
+            &config,
+            &0,
+        );
+        client.set_puzzle_factory(&Some(factory_id));
+
+        (factory, puzzle_id, preimage)
+    }
+
+    #[test]
+    fn test_factory_puzzle_records_plays() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        let (factory, puzzle_id, preimage) = setup_factory_puzzle(&env, &client, &admin, 3, None);
+
+        let wrong = Bytes::from_array(&env, &[0u8; 6]);
+        assert!(!solve(&env, &client, &player, puzzle_id, &wrong));
+        assert_eq!(client.failed_attempts(&player, &puzzle_id), 1);
+        assert!(solve(&env, &client, &player, puzzle_id, &preimage));
+
+        // Factory reward points are credited without difficulty scaling
+        assert_eq!(client.rewards_of(&player), 40);
+        let puzzle = factory.get_puzzle(&puzzle_id);
+        assert_eq!(puzzle.metadata.total_plays, 2);
+        assert_eq!(puzzle.metadata.successful_plays, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "max attempts reached")]
+    fn test_factory_max_attempts_enforced() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        let (_, puzzle_id, preimage) = setup_factory_puzzle(&env, &client, &admin, 2, None);
+
+        let wrong = Bytes::from_array(&env, &[0u8; 6]);
+        assert!(!solve(&env, &client, &player, puzzle_id, &wrong));
+        assert!(!solve(&env, &client, &player, puzzle_id, &wrong));
+        solve(&env, &client, &player, puzzle_id, &preimage);
+    }
+
+    #[test]
+    fn test_factory_time_limit_within_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        let (_, puzzle_id, preimage) = setup_factory_puzzle(&env, &client, &admin, 3, Some(60));
+
+        client.start_attempt(&player, &puzzle_id);
+        assert_eq!(client.attempt_started_at(&player, &puzzle_id), Some(1_000));
+        env.ledger().with_mut(|li| li.timestamp += 50);
+        // The commit lands inside the limit; the reveal delay does not count
+        assert!(solve(&env, &client, &player, puzzle_id, &preimage));
+    }
+
+    #[test]
+    fn test_completion_record_ranks_solvers() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        client.initialize(&admin);
+        let (_, puzzle_id, preimage) = setup_factory_puzzle(&env, &client, &admin, 3, None);
+        assert!(client.get_completion(&first, &puzzle_id).is_none());
+
+        // The second player commits before the first reveal closes the phase
+        let salt = BytesN::from_array(&env, &[6u8; 32]);
+        let commitment = client.compute_commitment(&second, &puzzle_id, &preimage, &salt);
+        client.commit_solution(&second, &puzzle_id, &commitment);
+
+        client.start_attempt(&first, &puzzle_id);
+        env.ledger().with_mut(|li| li.timestamp += 30);
+        assert!(solve(&env, &client, &first, puzzle_id, &preimage));
+        assert!(client.verify_solution(&second, &puzzle_id, &preimage, &salt));
+
+        let record = client.get_completion(&first, &puzzle_id).unwrap();
+        assert_eq!(record.rank, 1);
+        assert_eq!(record.completed_at, 1_040);
+        // Measured from start_attempt, including the reveal delay
+        assert_eq!(record.elapsed_secs, Some(40));
+        assert_eq!(
+            record.solution_hash,
+            env.crypto().sha256(&preimage).to_bytes()
+        );
+
+        let record = client.get_completion(&second, &puzzle_id).unwrap();
+        assert_eq!(record.rank, 2);
+        assert_eq!(record.elapsed_secs, None);
+        assert_eq!(client.solver_count(&puzzle_id), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "time limit exceeded")]
+    fn test_factory_time_limit_exceeded() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        let (_, puzzle_id, preimage) = setup_factory_puzzle(&env, &client, &admin, 3, Some(60));
+
+        client.start_attempt(&player, &puzzle_id);
+        env.ledger().with_mut(|li| li.timestamp += 61);
+        solve(&env, &client, &player, puzzle_id, &preimage);
+    }
+
+    #[test]
+    fn test_late_reveal_fails_and_attempt_can_restart() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        let (factory, puzzle_id, preimage) =
+            setup_factory_puzzle(&env, &client, &admin, 3, Some(60));
+
+        // Committed in time, but revealed long after the limit
+        client.start_attempt(&player, &puzzle_id);
+        let salt = BytesN::from_array(&env, &[5u8; 32]);
+        let commitment = client.compute_commitment(&player, &puzzle_id, &preimage, &salt);
+        client.commit_solution(&player, &puzzle_id, &commitment);
+        env.ledger().with_mut(|li| li.timestamp += 500);
+        assert!(!client.verify_solution(&player, &puzzle_id, &preimage, &salt));
+        assert!(!client.is_completed(&player, &puzzle_id));
+        assert_eq!(client.failed_attempts(&player, &puzzle_id), 1);
+        assert_eq!(client.attempt_started_at(&player, &puzzle_id), None);
+
+        // The failure frees the clock for a fresh attempt
+        client.start_attempt(&player, &puzzle_id);
+        assert!(solve(&env, &client, &player, puzzle_id, &preimage));
+        assert_eq!(factory.get_puzzle(&puzzle_id).metadata.successful_plays, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt not started")]
+    fn test_timed_puzzle_requires_start_attempt() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PuzzleVerification);
+        let client = PuzzleVerificationClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let player = Address::generate(&env);
+        client.initialize(&admin);
+        let (_, puzzle_id, preimage) = setup_factory_puzzle(&env, &client, &admin, 3, Some(60));
+
+        solve(&env, &client, &player, puzzle_id, &preimage);
     }
 }
@@ -127,6 +127,13 @@ impl RoyaltySplitter {
         env.storage().instance().set(&DataKey::Splits, &new_splits);
     }
 
+    /* ================= VIEWS ================= */
+
+    pub fn get_splits(env: Env) -> Map<Address, u32> {
+        Self::require_init(&env);
+        env.storage().instance().get(&DataKey::Splits).unwrap()
+    }
+
     /* ================= EMERGENCY ================= */
 
     pub fn emergency_withdraw(env: Env, recipient: Address, amount: i128) {
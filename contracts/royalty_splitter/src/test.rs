@@ -9,7 +9,7 @@ fn test_distribution_and_withdraw() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register(RoyaltySplitter, ());
+    let contract_id = env.register_contract(None, RoyaltySplitter);
 
     let admin = Address::generate(&env);
     let alice = Address::generate(&env);
@@ -33,7 +33,7 @@ fn test_invalid_split() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let contract_id = env.register(RoyaltySplitter, ());
+    let contract_id = env.register_contract(None, RoyaltySplitter);
 
     let admin = Address::generate(&env);
     let alice = Address::generate(&env);
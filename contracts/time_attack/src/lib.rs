@@ -1,7 +1,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Bytes,
+    BytesN, Env, Vec,
 };
 
 #[cfg(test)]
@@ -23,6 +24,10 @@ pub enum TimePeriod {
     Daily,
     Weekly,
     AllTime,
+    /// Fixed 30-day window
+    Monthly,
+    /// Admin-configurable window, see `set_season_length`
+    Season,
 }
 
 #[contracttype]
@@ -33,6 +38,14 @@ pub enum DataKey {
     Best(Scope, TimePeriod),
     Board(Scope, TimePeriod),
     LastReset(Scope, TimePeriod),
+    ChallengeConfig,
+    AntiBot,
+    TimeFloor(u32),
+    SubmissionCounter,
+    Submission(u64),
+    Dispute(u64),
+    SeasonLength,
+    PersonalBest(Address, u32),
 }
 
 /// Custom error codes for the contract
@@ -46,6 +59,14 @@ pub enum Error {
     TooFrequent = 4,
     DuplicateReplay = 5,
     ContractNotInitialized = 6,
+    RecordNotFound = 7,
+    ChallengeWindowClosed = 8,
+    ChallengeWindowOpen = 9,
+    InvalidRecordState = 10,
+    InvalidChallenge = 11,
+    PlayerBlocked = 12,
+    ResolutionWindowOpen = 13,
+    AntiBotUnavailable = 14,
     // NOTE(MVP): `InvalidPuzzleId` intentionally omitted until puzzle-id validation rules are defined.
 }
 
@@ -59,6 +80,93 @@ pub struct TimeRecord {
     pub replay_hash: BytesN<32>,
 }
 
+/// Lifecycle of a submitted record while it can still be disputed.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecordStatus {
+    /// Inside the challenge window, not yet on any leaderboard
+    Pending,
+    /// Disputed, waiting for an admin or anti-bot verifier
+    Challenged,
+    /// Applied to leaderboards and bests
+    Finalized,
+    /// Dispute upheld, bond slashed
+    Rejected,
+    /// Dispute never resolved, both bonds refunded and the record kept unranked
+    Unresolved,
+}
+
+/// Grounds for disputing a pending record.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisputeReason {
+    /// The published replay does not hash to the submitted `replay_hash`
+    ReplayMismatch,
+    /// The completion time is below the puzzle's physical floor
+    BelowFloor,
+}
+
+/// Bond and challenge window applied to every submission.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengeConfig {
+    pub bond_token: Address,
+    pub bond_amount: i128,
+    pub challenge_window_secs: u64,
+}
+
+/// A submitted record together with its bond and dispute state.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Submission {
+    pub id: u64,
+    pub puzzle_id: u32,
+    pub record: TimeRecord,
+    pub bond: i128,
+    /// Token the bond was staked in, kept even if the challenge config changes
+    pub bond_token: Option<Address>,
+    pub challenge_deadline: u64,
+    pub status: RecordStatus,
+    /// Player was flagged by anti-bot; the record is kept off public leaderboards
+    pub flagged: bool,
+}
+
+/// A challenge filed against a pending submission.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub record_id: u64,
+    pub challenger: Address,
+    pub reason: DisputeReason,
+    pub evidence_hash: BytesN<32>,
+    pub filed_at: u64,
+    /// Token both bonds were staked in
+    pub bond_token: Option<Address>,
+    /// After this `settle_expired_dispute` refunds both bonds and drops the record
+    pub resolve_deadline: u64,
+}
+
+/// `AntiBotError` codes that mean the player itself is banned or penalized.
+/// These copy `anti_bot::AntiBotError::{BotDetected, PenaltyActive}`, which
+/// this crate cannot import outside tests; `test_anti_bot_error_codes_match`
+/// fails if they drift.
+const ANTI_BOT_BOT_DETECTED: u32 = 5;
+const ANTI_BOT_PENALTY_ACTIVE: u32 = 11;
+
+// We only need submission screening and the verifier registry from the anti-bot contract.
+#[soroban_sdk::contractclient(name = "AntiBotClient")]
+pub trait AntiBot {
+    fn screen_submission(
+        env: Env,
+        caller: Address,
+        player: Address,
+        puzzle_id: u32,
+        solve_time_ms: u64,
+    ) -> bool;
+    fn is_flagged(env: Env, player: Address) -> bool;
+    fn is_verifier(env: Env, address: Address) -> bool;
+}
+
 /// Pure logic classification for future "time bracket competitions".
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -75,6 +183,12 @@ pub struct TimeAttack;
 const LEDGER_THRESHOLD_SHARED: u32 = 518_400; // ~30 days @ 5s/ledger
 const LEDGER_BUMP_SHARED: u32 = 1_036_800; // ~60 days @ 5s/ledger
 
+const DAY_SECONDS: u64 = 86_400;
+const WEEK_SECONDS: u64 = 604_800;
+const MONTH_SECONDS: u64 = 2_592_000; // 30 days
+const DEFAULT_SEASON_SECONDS: u64 = 7_776_000; // 90 days
+const DISPUTE_RESOLUTION_SECONDS: u64 = 604_800; // 7 days to resolve a challenge
+
 #[contractimpl]
 impl TimeAttack {
     fn bump_persistent_ttl(env: &Env, key: &DataKey) {
@@ -99,6 +213,99 @@ impl TimeAttack {
         Ok(())
     }
 
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::ContractNotInitialized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Configure the bond staked with every submission and how long it can be challenged.
+    /// A zero window finalizes records immediately; any other window needs a
+    /// positive bond so challenges are never free.
+    pub fn set_challenge_config(
+        env: Env,
+        bond_token: Address,
+        bond_amount: i128,
+        challenge_window_secs: u64,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        if bond_amount < 0 || (challenge_window_secs > 0 && bond_amount == 0) {
+            return Err(Error::InvalidChallenge);
+        }
+
+        let config = ChallengeConfig {
+            bond_token,
+            bond_amount,
+            challenge_window_secs,
+        };
+        env.storage().instance().set(&DataKey::ChallengeConfig, &config);
+
+        Ok(())
+    }
+
+    pub fn get_challenge_config(env: Env) -> Option<ChallengeConfig> {
+        env.storage().instance().get(&DataKey::ChallengeConfig)
+    }
+
+    /// Link the anti-bot contract that screens every submission and whose
+    /// verifiers may resolve disputes. This contract must be registered as an
+    /// anti-bot verifier for screening to succeed.
+    pub fn set_anti_bot(env: Env, anti_bot: Option<Address>) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        match anti_bot {
+            Some(address) => env.storage().instance().set(&DataKey::AntiBot, &address),
+            None => env.storage().instance().remove(&DataKey::AntiBot),
+        }
+
+        Ok(())
+    }
+
+    /// Set the length of the `Season` leaderboard period in seconds.
+    pub fn set_season_length(env: Env, season_length_secs: u64) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        if season_length_secs == 0 {
+            return Err(Error::InvalidTime);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::SeasonLength, &season_length_secs);
+
+        Ok(())
+    }
+
+    pub fn get_season_length(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SeasonLength)
+            .unwrap_or(DEFAULT_SEASON_SECONDS)
+    }
+
+    pub fn get_anti_bot(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::AntiBot)
+    }
+
+    /// Set the fastest physically possible completion time for a puzzle (0 for global).
+    pub fn set_time_floor(env: Env, puzzle_id: u32, min_time_ms: u64) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        let key = DataKey::TimeFloor(puzzle_id);
+        env.storage().persistent().set(&key, &min_time_ms);
+        Self::bump_persistent_ttl(&env, &key);
+
+        Ok(())
+    }
+
+    pub fn get_time_floor(env: Env, puzzle_id: u32) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::TimeFloor(puzzle_id))
+    }
+
     /// Submit a puzzle completion time
     ///
     /// # Arguments
@@ -108,21 +315,25 @@ impl TimeAttack {
     /// * `completion_time_ms` - Completion time in milliseconds
     /// * `replay_hash` - Hash of the replay data for verification
     ///
+    /// When a challenge window is configured the player stakes the bond and the
+    /// record stays pending until `finalize_record` or `resolve_challenge`.
+    ///
     /// # Returns
-    /// * `Ok(())` - Submission successful
+    /// * `Ok(id)` - Submission id
     /// * `Err(Error)` - Submission failed validation
     ///
     /// # Errors
-    /// - `InvalidTime`: Completion time is 0 or unreasonably high
+    /// - `InvalidTime`: Completion time is 0, unreasonably high or below the puzzle floor
     /// - `TooFrequent`: Player submitted too recently (rate limiting)
     /// - `DuplicateReplay`: Replay hash has been used before
+    /// - `PlayerBlocked`: The linked anti-bot contract refused the player
     pub fn submit_time(
         env: Env,
         player: Address,
         puzzle_id: u32,
         completion_time_ms: u64,
         replay_hash: BytesN<32>,
-    ) -> Result<(), Error> {
+    ) -> Result<u64, Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::ContractNotInitialized);
         }
@@ -135,6 +346,31 @@ impl TimeAttack {
 
         // Validate the submission
         Self::verify_submission(&env, &player, completion_time_ms, &replay_hash, timestamp)?;
+        if let Some(floor) = Self::get_time_floor(env.clone(), puzzle_id) {
+            if completion_time_ms < floor {
+                return Err(Error::InvalidTime);
+            }
+        }
+
+        // Let the linked anti-bot contract learn from the solve and refuse banned players
+        let flagged = match Self::get_anti_bot(env.clone()) {
+            Some(anti_bot) => match AntiBotClient::new(&env, &anti_bot).try_screen_submission(
+                &env.current_contract_address(),
+                &player,
+                &puzzle_id,
+                &completion_time_ms,
+            ) {
+                Ok(Ok(flagged)) => flagged,
+                Err(Ok(err))
+                    if err == soroban_sdk::Error::from_contract_error(ANTI_BOT_BOT_DETECTED)
+                        || err == soroban_sdk::Error::from_contract_error(ANTI_BOT_PENALTY_ACTIVE) =>
+                {
+                    return Err(Error::PlayerBlocked)
+                }
+                _ => return Err(Error::AntiBotUnavailable),
+            },
+            None => false,
+        };
 
         // Create the time record
         let record = TimeRecord {
@@ -144,23 +380,51 @@ impl TimeAttack {
             replay_hash: replay_hash.clone(),
         };
 
-        // Determine the scope based on puzzle_id
-        let scope = if puzzle_id == 0 {
-            Scope::Global
-        } else {
-            Scope::Puzzle(puzzle_id)
+        // Stake the bond; without a challenge window the record is final right away
+        let challenge_config: Option<ChallengeConfig> =
+            env.storage().instance().get(&DataKey::ChallengeConfig);
+        let (bond, window) = match &challenge_config {
+            Some(config) => (config.bond_amount, config.challenge_window_secs),
+            None => (0, 0),
+        };
+        let bond_token = match (&challenge_config, bond > 0) {
+            (Some(config), true) => {
+                token::Client::new(&env, &config.bond_token).transfer(
+                    &player,
+                    &env.current_contract_address(),
+                    &bond,
+                );
+                Some(config.bond_token.clone())
+            }
+            _ => None,
         };
 
-        // Check and reset leaderboards if needed (daily/weekly)
-        Self::check_and_reset_leaderboards(&env, scope, timestamp);
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::SubmissionCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage().instance().set(&DataKey::SubmissionCounter, &id);
+
+        let mut submission = Submission {
+            id,
+            puzzle_id,
+            record,
+            bond,
+            bond_token,
+            challenge_deadline: timestamp + window,
+            status: RecordStatus::Pending,
+            flagged,
+        };
 
-        // Update leaderboards for all time periods
-        Self::update_leaderboard(&env, scope, TimePeriod::AllTime, &record)?;
-        Self::update_leaderboard(&env, scope, TimePeriod::Daily, &record)?;
-        Self::update_leaderboard(&env, scope, TimePeriod::Weekly, &record)?;
+        if window == 0 {
+            Self::apply_record(&env, &mut submission)?;
+            Self::release_bond(&env, &submission.bond_token, &submission.record.player, bond);
+            submission.status = RecordStatus::Finalized;
+        }
 
-        // Update all-time best for this scope (global or per-puzzle)
-        Self::update_alltime_best(&env, scope, &record);
+        Self::save_submission(&env, &submission);
 
         // Mark this submission timestamp for rate limiting (temporary storage)
         env.storage()
@@ -188,6 +452,288 @@ impl TimeAttack {
             (completion_time_ms, timestamp, puzzle_id),
         );
 
+        Ok(id)
+    }
+
+    /// Dispute a pending record during its challenge window.
+    ///
+    /// The challenger stakes the same bond as the submitter. For
+    /// `ReplayMismatch`, `replay` is the published replay whose hash is kept
+    /// as evidence; `BelowFloor` is checked against the puzzle floor directly.
+    pub fn challenge_record(
+        env: Env,
+        challenger: Address,
+        record_id: u64,
+        reason: DisputeReason,
+        replay: Bytes,
+    ) -> Result<(), Error> {
+        challenger.require_auth();
+
+        let mut submission = Self::load_submission(&env, record_id)?;
+        if submission.status != RecordStatus::Pending {
+            return Err(Error::InvalidRecordState);
+        }
+        if env.ledger().timestamp() >= submission.challenge_deadline {
+            return Err(Error::ChallengeWindowClosed);
+        }
+
+        let evidence_hash: BytesN<32> = env.crypto().sha256(&replay).into();
+        match reason {
+            DisputeReason::ReplayMismatch => {
+                if evidence_hash == submission.record.replay_hash {
+                    return Err(Error::InvalidChallenge);
+                }
+            }
+            DisputeReason::BelowFloor => {
+                let floor = Self::get_time_floor(env.clone(), submission.puzzle_id)
+                    .ok_or(Error::InvalidChallenge)?;
+                if submission.record.completion_time_ms >= floor {
+                    return Err(Error::InvalidChallenge);
+                }
+            }
+        }
+
+        // The challenger matches the bond in the token the submitter staked
+        if let (Some(bond_token), true) = (&submission.bond_token, submission.bond > 0) {
+            token::Client::new(&env, bond_token).transfer(
+                &challenger,
+                &env.current_contract_address(),
+                &submission.bond,
+            );
+        }
+
+        submission.status = RecordStatus::Challenged;
+        Self::save_submission(&env, &submission);
+
+        let filed_at = env.ledger().timestamp();
+        let dispute = Dispute {
+            record_id,
+            challenger: challenger.clone(),
+            reason,
+            evidence_hash,
+            filed_at,
+            bond_token: submission.bond_token.clone(),
+            resolve_deadline: filed_at + DISPUTE_RESOLUTION_SECONDS,
+        };
+        let dispute_key = DataKey::Dispute(record_id);
+        env.storage().persistent().set(&dispute_key, &dispute);
+        Self::bump_persistent_ttl(&env, &dispute_key);
+
+        env.events()
+            .publish((symbol_short!("CHALLENGE"), challenger), (record_id, reason));
+
+        Ok(())
+    }
+
+    /// Settle a dispute as the admin or an anti-bot verifier.
+    ///
+    /// Upheld: the record is rejected and the challenger receives both bonds.
+    /// Dismissed: the record is finalized and the submitter receives both bonds.
+    pub fn resolve_challenge(
+        env: Env,
+        resolver: Address,
+        record_id: u64,
+        upheld: bool,
+    ) -> Result<(), Error> {
+        resolver.require_auth();
+        Self::require_resolver(&env, &resolver)?;
+
+        let mut submission = Self::load_submission(&env, record_id)?;
+        if submission.status != RecordStatus::Challenged {
+            return Err(Error::InvalidRecordState);
+        }
+        let dispute: Dispute = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Dispute(record_id))
+            .ok_or(Error::InvalidRecordState)?;
+
+        if upheld {
+            submission.status = RecordStatus::Rejected;
+            Self::release_bond(&env, &dispute.bond_token, &dispute.challenger, submission.bond * 2);
+        } else {
+            Self::apply_record(&env, &mut submission)?;
+            submission.status = RecordStatus::Finalized;
+            Self::release_bond(
+                &env,
+                &dispute.bond_token,
+                &submission.record.player,
+                submission.bond * 2,
+            );
+        }
+        Self::save_submission(&env, &submission);
+
+        env.events()
+            .publish((symbol_short!("RESOLVED"), resolver), (record_id, upheld));
+
+        Ok(())
+    }
+
+    /// Finalize an unchallenged record once its window has passed and return the bond.
+    pub fn finalize_record(env: Env, record_id: u64) -> Result<(), Error> {
+        let mut submission = Self::load_submission(&env, record_id)?;
+        if submission.status != RecordStatus::Pending {
+            return Err(Error::InvalidRecordState);
+        }
+        if env.ledger().timestamp() < submission.challenge_deadline {
+            return Err(Error::ChallengeWindowOpen);
+        }
+
+        Self::apply_record(&env, &mut submission)?;
+        submission.status = RecordStatus::Finalized;
+        Self::release_bond(&env, &submission.bond_token, &submission.record.player, submission.bond);
+        Self::save_submission(&env, &submission);
+
+        env.events().publish(
+            (symbol_short!("FINALIZED"), submission.record.player.clone()),
+            record_id,
+        );
+
+        Ok(())
+    }
+
+    /// Settle a dispute nobody resolved before its deadline.
+    ///
+    /// Callable by anyone. Only unchallenged or cleared records become bests,
+    /// so the record is left unranked and each side gets its own bond back.
+    pub fn settle_expired_dispute(env: Env, record_id: u64) -> Result<(), Error> {
+        let mut submission = Self::load_submission(&env, record_id)?;
+        if submission.status != RecordStatus::Challenged {
+            return Err(Error::InvalidRecordState);
+        }
+        let dispute: Dispute = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Dispute(record_id))
+            .ok_or(Error::InvalidRecordState)?;
+        if env.ledger().timestamp() < dispute.resolve_deadline {
+            return Err(Error::ResolutionWindowOpen);
+        }
+
+        submission.status = RecordStatus::Unresolved;
+        Self::release_bond(&env, &dispute.bond_token, &submission.record.player, submission.bond);
+        Self::release_bond(&env, &dispute.bond_token, &dispute.challenger, submission.bond);
+        Self::save_submission(&env, &submission);
+
+        env.events().publish(
+            (symbol_short!("EXPIRED"), submission.record.player.clone()),
+            record_id,
+        );
+
+        Ok(())
+    }
+
+    pub fn get_submission(env: Env, record_id: u64) -> Option<Submission> {
+        env.storage().persistent().get(&DataKey::Submission(record_id))
+    }
+
+    pub fn get_dispute(env: Env, record_id: u64) -> Option<Dispute> {
+        env.storage().persistent().get(&DataKey::Dispute(record_id))
+    }
+
+    fn load_submission(env: &Env, record_id: u64) -> Result<Submission, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Submission(record_id))
+            .ok_or(Error::RecordNotFound)
+    }
+
+    fn save_submission(env: &Env, submission: &Submission) {
+        let key = DataKey::Submission(submission.id);
+        env.storage().persistent().set(&key, submission);
+        Self::bump_persistent_ttl(env, &key);
+    }
+
+    fn require_resolver(env: &Env, resolver: &Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::ContractNotInitialized)?;
+        if *resolver == admin {
+            return Ok(());
+        }
+
+        if let Some(anti_bot) = env.storage().instance().get::<_, Address>(&DataKey::AntiBot) {
+            if AntiBotClient::new(env, &anti_bot).is_verifier(resolver) {
+                return Ok(());
+            }
+        }
+
+        Err(Error::NotAuthorized)
+    }
+
+    fn release_bond(env: &Env, bond_token: &Option<Address>, to: &Address, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        if let Some(bond_token) = bond_token {
+            token::Client::new(env, bond_token).transfer(
+                &env.current_contract_address(),
+                to,
+                &amount,
+            );
+        }
+    }
+
+    /// Put a record on the leaderboards; only called for unchallenged or cleared records.
+    /// Records from players flagged by anti-bot, at submission or since, stay unranked.
+    fn apply_record(env: &Env, submission: &mut Submission) -> Result<(), Error> {
+        let puzzle_id = submission.puzzle_id;
+        let record = &submission.record;
+
+        // An unreachable anti-bot contract must not lock bonds in a settled
+        // record, so the flag from submission time stands in that case
+        if !submission.flagged {
+            if let Some(anti_bot) = env.storage().instance().get::<_, Address>(&DataKey::AntiBot) {
+                if let Ok(Ok(flagged)) =
+                    AntiBotClient::new(env, &anti_bot).try_is_flagged(&record.player)
+                {
+                    submission.flagged = flagged;
+                }
+            }
+        }
+        if submission.flagged {
+            env.events().publish(
+                (symbol_short!("UNRANKED"), record.player.clone()),
+                (submission.id, puzzle_id),
+            );
+            return Ok(());
+        }
+
+        // Determine the scope based on puzzle_id
+        let scope = if puzzle_id == 0 {
+            Scope::Global
+        } else {
+            Scope::Puzzle(puzzle_id)
+        };
+
+        // Check and reset leaderboards if needed (daily/weekly/monthly/season).
+        // Seeding at the submission time first keeps a first-ever record inside
+        // its own period when it is finalized later.
+        Self::check_and_reset_leaderboards(env, scope, record.timestamp);
+        Self::check_and_reset_leaderboards(env, scope, env.ledger().timestamp());
+
+        // Update leaderboards for all time periods. A record finalized late only
+        // counts towards the periods it was submitted in, never the current ones.
+        Self::update_leaderboard(env, scope, TimePeriod::AllTime, record)?;
+        for period in [
+            TimePeriod::Daily,
+            TimePeriod::Weekly,
+            TimePeriod::Monthly,
+            TimePeriod::Season,
+        ] {
+            if Self::in_current_period(env, scope, period, record.timestamp) {
+                Self::update_leaderboard(env, scope, period, record)?;
+            }
+        }
+
+        // Update all-time best for this scope (global or per-puzzle)
+        Self::update_alltime_best(env, scope, record);
+
+        // Update the player's own best for this puzzle
+        Self::update_personal_best(env, puzzle_id, record);
+
         Ok(())
     }
 
@@ -227,9 +773,22 @@ impl TimeAttack {
         Ok(())
     }
 
+    fn in_current_period(env: &Env, scope: Scope, period: TimePeriod, timestamp: u64) -> bool {
+        let last_reset: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastReset(scope, period))
+            .unwrap_or(0);
+        timestamp >= last_reset
+    }
+
     fn check_and_reset_leaderboards(env: &Env, scope: Scope, current_timestamp: u64) {
-        Self::maybe_reset_period(env, scope, TimePeriod::Daily, 86_400, current_timestamp);
-        Self::maybe_reset_period(env, scope, TimePeriod::Weekly, 604_800, current_timestamp);
+        let season_length = Self::get_season_length(env.clone());
+
+        Self::maybe_reset_period(env, scope, TimePeriod::Daily, DAY_SECONDS, current_timestamp);
+        Self::maybe_reset_period(env, scope, TimePeriod::Weekly, WEEK_SECONDS, current_timestamp);
+        Self::maybe_reset_period(env, scope, TimePeriod::Monthly, MONTH_SECONDS, current_timestamp);
+        Self::maybe_reset_period(env, scope, TimePeriod::Season, season_length, current_timestamp);
     }
 
     fn maybe_reset_period(
@@ -345,6 +904,46 @@ impl TimeAttack {
         }
     }
 
+    fn update_personal_best(env: &Env, puzzle_id: u32, record: &TimeRecord) {
+        let pb_key = DataKey::PersonalBest(record.player.clone(), puzzle_id);
+
+        let current: Option<TimeRecord> = env.storage().persistent().get(&pb_key);
+        let event = match current {
+            None => symbol_short!("FIRST_PB"),
+            Some(pb) if record.completion_time_ms < pb.completion_time_ms => {
+                symbol_short!("NEW_PB")
+            }
+            Some(_) => return,
+        };
+
+        env.storage().persistent().set(&pb_key, record);
+        Self::bump_persistent_ttl(env, &pb_key);
+
+        env.events().publish(
+            (event, record.player.clone()),
+            (puzzle_id, record.completion_time_ms),
+        );
+    }
+
+    /// Get a player's personal best for a puzzle (0 for global)
+    pub fn get_personal_best(env: Env, player: Address, puzzle_id: u32) -> Option<TimeRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PersonalBest(player, puzzle_id))
+    }
+
+    /// Get a player's 1-based rank on a leaderboard, using their fastest entry.
+    /// Returns 0 if the player is not on the board.
+    pub fn get_player_rank(env: Env, puzzle_id: u32, period: TimePeriod, player: Address) -> u32 {
+        let board = Self::get_leaderboard(env, puzzle_id, period);
+        for (i, record) in board.iter().enumerate() {
+            if record.player == player {
+                return i as u32 + 1;
+            }
+        }
+        0
+    }
+
     /// Get the best time for a scope
     ///
     /// # Arguments
@@ -369,7 +968,7 @@ impl TimeAttack {
     /// # Arguments
     /// * `env` - Contract environment
     /// * `puzzle_id` - Puzzle ID (0 for global)
-    /// * `period` - Time period (Daily/Weekly/AllTime)
+    /// * `period` - Time period (Daily/Weekly/Monthly/Season/AllTime)
     ///
     /// # Returns
     /// Vector of time records, ordered by fastest time
@@ -648,4 +1247,608 @@ mod test {
         let alltime_board = client.get_leaderboard(&1u32, &TimePeriod::AllTime);
         assert_eq!(alltime_board.len(), 2);
     }
+
+    fn setup_challenges(
+        env: &Env,
+        window: u64,
+    ) -> (TimeAttackClient<'_>, Address, token::Client<'_>) {
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        client.initialize(&admin);
+
+        let token_admin = Address::generate(env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        client.set_challenge_config(&token_id, &100, &window);
+
+        (client, admin, token::Client::new(env, &token_id))
+    }
+
+    fn fund(env: &Env, token: &token::Client, to: &Address, amount: i128) {
+        token::StellarAssetClient::new(env, &token.address).mint(to, &amount);
+    }
+
+    #[test]
+    fn test_pending_record_not_on_leaderboard_until_finalized() {
+        let env = Env::default();
+        let (client, _admin, token) = setup_challenges(&env, 3_600);
+
+        let player = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+
+        let id = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(token.balance(&player), 900);
+        assert_eq!(client.get_submission(&id).unwrap().status, RecordStatus::Pending);
+        assert!(client.get_best_time(&1u32).is_none());
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::AllTime).len(), 0);
+
+        // Cannot finalize while the window is open
+        assert_eq!(client.try_finalize_record(&id), Err(Ok(Error::ChallengeWindowOpen)));
+
+        env.ledger().with_mut(|li| li.timestamp += 3_600);
+        client.finalize_record(&id);
+
+        assert_eq!(client.get_submission(&id).unwrap().status, RecordStatus::Finalized);
+        assert_eq!(client.get_best_time(&1u32).unwrap().completion_time_ms, 90_000);
+        assert_eq!(token.balance(&player), 1_000);
+    }
+
+    #[test]
+    fn test_upheld_challenge_slashes_bond() {
+        let env = Env::default();
+        let (client, admin, token) = setup_challenges(&env, 3_600);
+
+        let player = Address::generate(&env);
+        let challenger = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+        fund(&env, &token, &challenger, 1_000);
+
+        let id = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+
+        let published_replay = Bytes::from_array(&env, &[7u8; 16]);
+        client.challenge_record(&challenger, &id, &DisputeReason::ReplayMismatch, &published_replay);
+
+        let submission = client.get_submission(&id).unwrap();
+        assert_eq!(submission.status, RecordStatus::Challenged);
+        let dispute = client.get_dispute(&id).unwrap();
+        assert_eq!(dispute.challenger, challenger);
+        assert_eq!(
+            dispute.evidence_hash,
+            BytesN::from(env.crypto().sha256(&published_replay))
+        );
+        assert_eq!(token.balance(&challenger), 900);
+
+        client.resolve_challenge(&admin, &id, &true);
+
+        assert_eq!(client.get_submission(&id).unwrap().status, RecordStatus::Rejected);
+        assert!(client.get_best_time(&1u32).is_none());
+        assert_eq!(token.balance(&player), 900);
+        assert_eq!(token.balance(&challenger), 1_100);
+    }
+
+    #[test]
+    fn test_dismissed_challenge_finalizes_record() {
+        let env = Env::default();
+        let (client, admin, token) = setup_challenges(&env, 3_600);
+
+        let player = Address::generate(&env);
+        let challenger = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+        fund(&env, &token, &challenger, 1_000);
+
+        let id = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        client.challenge_record(
+            &challenger,
+            &id,
+            &DisputeReason::ReplayMismatch,
+            &Bytes::from_array(&env, &[7u8; 16]),
+        );
+        client.resolve_challenge(&admin, &id, &false);
+
+        assert_eq!(client.get_submission(&id).unwrap().status, RecordStatus::Finalized);
+        assert_eq!(client.get_best_time(&1u32).unwrap().player, player);
+        assert_eq!(token.balance(&player), 1_100);
+        assert_eq!(token.balance(&challenger), 900);
+    }
+
+    #[test]
+    fn test_below_floor_challenge_requires_floor_violation() {
+        let env = Env::default();
+        let (client, _admin, token) = setup_challenges(&env, 3_600);
+
+        let player = Address::generate(&env);
+        let challenger = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+        fund(&env, &token, &challenger, 1_000);
+
+        let id = client.submit_time(&player, &1u32, &20_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        let empty = Bytes::new(&env);
+
+        // No floor configured yet
+        assert_eq!(
+            client.try_challenge_record(&challenger, &id, &DisputeReason::BelowFloor, &empty),
+            Err(Ok(Error::InvalidChallenge))
+        );
+
+        client.set_time_floor(&1u32, &30_000u64);
+        client.challenge_record(&challenger, &id, &DisputeReason::BelowFloor, &empty);
+        assert_eq!(client.get_dispute(&id).unwrap().reason, DisputeReason::BelowFloor);
+
+        // New submissions below the floor are refused outright
+        env.ledger().with_mut(|li| li.timestamp += 10);
+        let result = client.try_submit_time(&player, &1u32, &20_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(result, Err(Ok(Error::InvalidTime)));
+    }
+
+    #[test]
+    fn test_challenge_after_window_fails() {
+        let env = Env::default();
+        let (client, _admin, token) = setup_challenges(&env, 3_600);
+
+        let player = Address::generate(&env);
+        let challenger = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+
+        let id = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        env.ledger().with_mut(|li| li.timestamp += 3_600);
+
+        let result = client.try_challenge_record(
+            &challenger,
+            &id,
+            &DisputeReason::ReplayMismatch,
+            &Bytes::from_array(&env, &[7u8; 16]),
+        );
+        assert_eq!(result, Err(Ok(Error::ChallengeWindowClosed)));
+    }
+
+    #[test]
+    fn test_anti_bot_verifier_can_resolve() {
+        let env = Env::default();
+        let (client, admin, token) = setup_challenges(&env, 3_600);
+
+        let anti_bot_id = env.register_contract(None, anti_bot::AntiBot);
+        let anti_bot = anti_bot::AntiBotClient::new(&env, &anti_bot_id);
+        anti_bot.initialize(&admin);
+        let verifier = Address::generate(&env);
+        anti_bot.add_verifier(&verifier);
+
+        let player = Address::generate(&env);
+        let challenger = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+        fund(&env, &token, &challenger, 1_000);
+
+        let id = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        client.challenge_record(
+            &challenger,
+            &id,
+            &DisputeReason::ReplayMismatch,
+            &Bytes::from_array(&env, &[7u8; 16]),
+        );
+
+        // Not a resolver until the anti-bot contract is linked
+        assert_eq!(
+            client.try_resolve_challenge(&verifier, &id, &true),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        client.set_anti_bot(&Some(anti_bot_id));
+        client.resolve_challenge(&verifier, &id, &true);
+        assert_eq!(client.get_submission(&id).unwrap().status, RecordStatus::Rejected);
+    }
+
+    #[test]
+    fn test_bond_paid_back_in_original_token_after_config_change() {
+        let env = Env::default();
+        let (client, _admin, token) = setup_challenges(&env, 3_600);
+
+        let player = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+        let id = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+
+        let other_admin = Address::generate(&env);
+        let other_token = env
+            .register_stellar_asset_contract_v2(other_admin)
+            .address();
+        client.set_challenge_config(&other_token, &100, &3_600);
+
+        env.ledger().with_mut(|li| li.timestamp += 3_600);
+        client.finalize_record(&id);
+        assert_eq!(token.balance(&player), 1_000);
+        assert_eq!(token::Client::new(&env, &other_token).balance(&player), 0);
+    }
+
+    #[test]
+    fn test_challenge_window_requires_bond() {
+        let env = Env::default();
+        let (client, _admin, token) = setup_challenges(&env, 3_600);
+
+        assert_eq!(
+            client.try_set_challenge_config(&token.address, &0, &3_600),
+            Err(Ok(Error::InvalidChallenge))
+        );
+        // Without a window there is nothing to challenge, so no bond is needed
+        client.set_challenge_config(&token.address, &0, &0);
+    }
+
+    #[test]
+    fn test_unresolved_challenge_never_reaches_bests() {
+        let env = Env::default();
+        let (client, admin, token) = setup_challenges(&env, 3_600);
+
+        let player = Address::generate(&env);
+        let challenger = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+        fund(&env, &token, &challenger, 1_000);
+
+        let id = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        client.challenge_record(
+            &challenger,
+            &id,
+            &DisputeReason::ReplayMismatch,
+            &Bytes::from_array(&env, &[7u8; 16]),
+        );
+
+        assert_eq!(
+            client.try_settle_expired_dispute(&id),
+            Err(Ok(Error::ResolutionWindowOpen))
+        );
+
+        env.ledger()
+            .with_mut(|li| li.timestamp += DISPUTE_RESOLUTION_SECONDS);
+        client.settle_expired_dispute(&id);
+
+        // Both bonds come back and the record stays off every board
+        assert_eq!(client.get_submission(&id).unwrap().status, RecordStatus::Unresolved);
+        assert!(client.get_best_time(&1u32).is_none());
+        assert!(client.get_personal_best(&player, &1u32).is_none());
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::AllTime).len(), 0);
+        assert_eq!(token.balance(&player), 1_000);
+        assert_eq!(token.balance(&challenger), 1_000);
+        assert_eq!(
+            client.try_resolve_challenge(&admin, &id, &false),
+            Err(Ok(Error::InvalidRecordState))
+        );
+        assert_eq!(
+            client.try_finalize_record(&id),
+            Err(Ok(Error::InvalidRecordState))
+        );
+        assert!(client.get_best_time(&1u32).is_none());
+    }
+
+    #[test]
+    fn test_late_finalization_skips_current_daily_board() {
+        let env = Env::default();
+        let (client, admin, token) = setup_challenges(&env, 3_600);
+
+        let player = Address::generate(&env);
+        let challenger = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+        fund(&env, &token, &challenger, 1_000);
+
+        let id = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        client.challenge_record(
+            &challenger,
+            &id,
+            &DisputeReason::ReplayMismatch,
+            &Bytes::from_array(&env, &[7u8; 16]),
+        );
+
+        // Resolved two days later, after the daily board has rolled over
+        env.ledger().with_mut(|li| li.timestamp += 2 * 86_400);
+        client.resolve_challenge(&admin, &id, &false);
+
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::AllTime).len(), 1);
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::Daily).len(), 0);
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::Weekly).len(), 1);
+    }
+
+    fn setup_anti_bot<'a>(
+        env: &'a Env,
+        client: &TimeAttackClient,
+        admin: &Address,
+    ) -> anti_bot::AntiBotClient<'a> {
+        let anti_bot_id = env.register_contract(None, anti_bot::AntiBot);
+        let anti_bot = anti_bot::AntiBotClient::new(env, &anti_bot_id);
+        anti_bot.initialize(admin);
+        anti_bot.add_verifier(&client.address);
+        client.set_anti_bot(&Some(anti_bot_id));
+        anti_bot
+    }
+
+    #[test]
+    fn test_submission_feeds_anti_bot_profile() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let anti_bot = setup_anti_bot(&env, &client, &admin);
+
+        let player = Address::generate(&env);
+        client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+
+        let profile = anti_bot.get_profile(&player).unwrap();
+        assert_eq!(profile.total_attempts, 1);
+        assert_eq!(profile.avg_solve_time_ms, 90_000);
+        assert!(anti_bot.get_behavioral_pattern(&player).is_some());
+        assert_eq!(client.get_best_time(&1u32).unwrap().player, player);
+    }
+
+    #[test]
+    fn test_penalized_player_refused() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let anti_bot = setup_anti_bot(&env, &client, &admin);
+
+        let banned = Address::generate(&env);
+        anti_bot.apply_penalty(
+            &banned,
+            &anti_bot::PenaltyType::PermanentBan,
+            &symbol_short!("cheat"),
+            &10,
+        );
+        assert!(anti_bot.is_blacklisted(&banned));
+
+        let result = client.try_submit_time(&banned, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(result, Err(Ok(Error::PlayerBlocked)));
+        assert!(client.get_best_time(&1u32).is_none());
+    }
+
+    #[test]
+    fn test_blacklisted_player_refused() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let anti_bot = setup_anti_bot(&env, &client, &admin);
+
+        let blacklisted = Address::generate(&env);
+        // Blacklisted without a penalty record; `blacklist_player` itself also
+        // applies a PermanentBan, which `test_penalized_player_refused` covers
+        env.as_contract(&anti_bot.address, || {
+            env.storage()
+                .persistent()
+                .set(&anti_bot::DataKey::Blacklisted(blacklisted.clone()), &true);
+        });
+        assert!(anti_bot.is_blacklisted(&blacklisted));
+
+        let result = client.try_submit_time(&blacklisted, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(result, Err(Ok(Error::PlayerBlocked)));
+        assert!(client.get_best_time(&1u32).is_none());
+    }
+
+    #[test]
+    fn test_temporarily_banned_player_refused() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let anti_bot = setup_anti_bot(&env, &client, &admin);
+
+        let banned = Address::generate(&env);
+        anti_bot.apply_penalty(
+            &banned,
+            &anti_bot::PenaltyType::TemporaryBan,
+            &symbol_short!("spam"),
+            &5,
+        );
+        assert!(!anti_bot.is_blacklisted(&banned));
+
+        let result = client.try_submit_time(&banned, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(result, Err(Ok(Error::PlayerBlocked)));
+    }
+
+    #[test]
+    fn test_anti_bot_error_codes_match() {
+        assert_eq!(ANTI_BOT_BOT_DETECTED, anti_bot::AntiBotError::BotDetected as u32);
+        assert_eq!(ANTI_BOT_PENALTY_ACTIVE, anti_bot::AntiBotError::PenaltyActive as u32);
+    }
+
+    #[test]
+    fn test_flagged_player_kept_off_leaderboards() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let anti_bot = setup_anti_bot(&env, &client, &admin);
+
+        let flagged = Address::generate(&env);
+        let honest = Address::generate(&env);
+        anti_bot.flag_player(&flagged, &symbol_short!("pattern"), &8);
+
+        let id = client.submit_time(&flagged, &1u32, &60_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        client.submit_time(&honest, &1u32, &90_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+
+        let submission = client.get_submission(&id).unwrap();
+        assert!(submission.flagged);
+        assert_eq!(submission.status, RecordStatus::Finalized);
+
+        let board = client.get_leaderboard(&1u32, &TimePeriod::AllTime);
+        assert_eq!(board.len(), 1);
+        assert_eq!(board.get(0).unwrap().player, honest);
+        assert_eq!(client.get_best_time(&1u32).unwrap().player, honest);
+    }
+
+    #[test]
+    fn test_player_flagged_during_window_stays_unranked() {
+        let env = Env::default();
+        let (client, admin, token) = setup_challenges(&env, 3_600);
+        let anti_bot = setup_anti_bot(&env, &client, &admin);
+
+        let player = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+
+        let id = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        assert!(!client.get_submission(&id).unwrap().flagged);
+
+        anti_bot.flag_player(&player, &symbol_short!("pattern"), &8);
+        env.ledger().with_mut(|li| li.timestamp += 3_600);
+        client.finalize_record(&id);
+
+        assert!(client.get_submission(&id).unwrap().flagged);
+        assert!(client.get_best_time(&1u32).is_none());
+        // Bond is still returned; the record just isn't ranked
+        assert_eq!(token.balance(&player), 1_000);
+    }
+
+    #[contract]
+    struct TrappingAntiBot;
+
+    #[contractimpl]
+    impl TrappingAntiBot {
+        pub fn is_flagged(_env: Env, _player: Address) -> bool {
+            panic!("anti-bot unavailable")
+        }
+    }
+
+    #[test]
+    fn test_trapping_anti_bot_does_not_block_settlement() {
+        let env = Env::default();
+        let (client, admin, token) = setup_challenges(&env, 3_600);
+
+        let player = Address::generate(&env);
+        let challenger = Address::generate(&env);
+        fund(&env, &token, &player, 1_000);
+        fund(&env, &token, &challenger, 1_000);
+
+        let finalized = client.submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        env.ledger().with_mut(|li| li.timestamp += 10);
+        let resolved = client.submit_time(&player, &2u32, &80_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+        client.challenge_record(
+            &challenger,
+            &resolved,
+            &DisputeReason::ReplayMismatch,
+            &Bytes::from_array(&env, &[7u8; 16]),
+        );
+
+        // The anti-bot link breaks while both records are pending
+        client.set_anti_bot(&Some(env.register_contract(None, TrappingAntiBot)));
+
+        client.resolve_challenge(&admin, &resolved, &false);
+        env.ledger().with_mut(|li| li.timestamp += 3_600);
+        client.finalize_record(&finalized);
+
+        for id in [finalized, resolved] {
+            let submission = client.get_submission(&id).unwrap();
+            assert_eq!(submission.status, RecordStatus::Finalized);
+            assert!(!submission.flagged);
+        }
+        assert_eq!(client.get_best_time(&1u32).unwrap().player, player);
+        assert_eq!(client.get_best_time(&2u32).unwrap().player, player);
+        assert_eq!(token.balance(&player), 1_100);
+        assert_eq!(token.balance(&challenger), 900);
+    }
+
+    #[test]
+    fn test_unregistered_time_attack_cannot_screen() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let anti_bot_id = env.register_contract(None, anti_bot::AntiBot);
+        anti_bot::AntiBotClient::new(&env, &anti_bot_id).initialize(&admin);
+        client.set_anti_bot(&Some(anti_bot_id));
+
+        let player = Address::generate(&env);
+        let result = client.try_submit_time(&player, &1u32, &90_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(result, Err(Ok(Error::AntiBotUnavailable)));
+    }
+
+    #[test]
+    fn test_monthly_and_season_boards_reset() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_season_length(&(45 * 86_400));
+        assert_eq!(client.get_season_length(), 45 * 86_400);
+
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        client.submit_time(&player1, &1u32, &100_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::Monthly).len(), 1);
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::Season).len(), 1);
+
+        // 31 days later: month rolls over, the 45-day season does not
+        env.ledger().with_mut(|li| li.timestamp += 31 * 86_400);
+        client.submit_time(&player2, &1u32, &120_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+
+        let monthly = client.get_leaderboard(&1u32, &TimePeriod::Monthly);
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly.get(0).unwrap().player, player2);
+        assert_eq!(client.get_leaderboard(&1u32, &TimePeriod::Season).len(), 2);
+
+        // 15 more days: the season rolls over too
+        env.ledger().with_mut(|li| li.timestamp += 15 * 86_400);
+        client.submit_time(&player1, &1u32, &110_000u64, &BytesN::from_array(&env, &[3u8; 32]));
+        let season = client.get_leaderboard(&1u32, &TimePeriod::Season);
+        assert_eq!(season.len(), 1);
+        assert_eq!(season.get(0).unwrap().player, player1);
+    }
+
+    #[test]
+    fn test_personal_best_and_player_rank() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, TimeAttack);
+        let client = TimeAttackClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        assert!(client.get_personal_best(&player1, &1u32).is_none());
+        assert_eq!(client.get_player_rank(&1u32, &TimePeriod::AllTime, &player1), 0);
+
+        client.submit_time(&player1, &1u32, &150_000u64, &BytesN::from_array(&env, &[1u8; 32]));
+        env.ledger().with_mut(|li| li.timestamp += 61);
+        client.submit_time(&player2, &1u32, &120_000u64, &BytesN::from_array(&env, &[2u8; 32]));
+        env.ledger().with_mut(|li| li.timestamp += 61);
+        client.submit_time(&player1, &1u32, &130_000u64, &BytesN::from_array(&env, &[3u8; 32]));
+        env.ledger().with_mut(|li| li.timestamp += 61);
+        // Slower run does not replace the personal best
+        client.submit_time(&player1, &1u32, &160_000u64, &BytesN::from_array(&env, &[4u8; 32]));
+
+        assert_eq!(
+            client.get_personal_best(&player1, &1u32).unwrap().completion_time_ms,
+            130_000
+        );
+        assert_eq!(
+            client.get_personal_best(&player2, &1u32).unwrap().completion_time_ms,
+            120_000
+        );
+        assert!(client.get_personal_best(&player1, &2u32).is_none());
+
+        assert_eq!(client.get_player_rank(&1u32, &TimePeriod::AllTime, &player2), 1);
+        assert_eq!(client.get_player_rank(&1u32, &TimePeriod::AllTime, &player1), 2);
+        assert_eq!(client.get_player_rank(&1u32, &TimePeriod::Monthly, &player1), 2);
+    }
 }